@@ -0,0 +1,92 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+use nala_interpreter::{
+    ast::Program, interpreter::eval_program, io_context::TestContext, parser::parse_code,
+    vm::run_program,
+};
+
+// Counts up through nested function calls, binding and reading numbers on every iteration.
+const LOOPS: &str = r#"
+    func double(num: Number): Number {
+        num * 2;
+    }
+
+    mut i = 0;
+    mut total = 0;
+
+    wiles (i < 2000) {
+        total = total + double(i);
+        i = i + 1;
+    }
+"#;
+
+// Passes strings through functions and bindings, and builds a longer one up a piece at a time.
+const STRINGS: &str = r#"
+    const greeting = 'The quick brown fox jumps over the lazy dog';
+
+    func echo(message: String): String {
+        message;
+    }
+
+    mut i = 0;
+    mut sentence = '';
+
+    wiles (i < 1000) {
+        if (i < 50) {
+            sentence = sentence + echo(greeting);
+        } else {
+            const copy = echo(sentence);
+        }
+
+        i = i + 1;
+    }
+"#;
+
+// Reads from arrays and objects which are passed around by reference.
+const COLLECTIONS: &str = r#"
+    const words = ['alpha', 'beta', 'gamma', 'delta', 'epsilon', 'zeta', 'eta', 'theta'];
+    const counts = { alpha: 0, beta: 0 };
+
+    func pick(list: Array<String>, index: Number): String {
+        list[index];
+    }
+
+    mut i = 0;
+
+    wiles (i < 1000) {
+        if (pick(words, i - floor(i / 8) * 8) == 'alpha') {
+            counts.alpha = counts.alpha + 1;
+        }
+
+        i = i + 1;
+    }
+"#;
+
+fn bench_script(c: &mut Criterion, name: &str, code: &str) {
+    let program = parse_code(code.to_owned()).unwrap();
+
+    c.bench_function(&format!("{name} (interpreter)"), |b| {
+        b.iter_batched(
+            || program.clone(),
+            |program: Program| eval_program(program, &mut TestContext::new()).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    c.bench_function(&format!("{name} (vm)"), |b| {
+        b.iter_batched(
+            || program.clone(),
+            |program: Program| run_program(program, &mut TestContext::new()).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn values(c: &mut Criterion) {
+    bench_script(c, "loops", LOOPS);
+    bench_script(c, "strings", STRINGS);
+    bench_script(c, "collections", COLLECTIONS);
+}
+
+criterion_group!(benches, values);
+criterion_main!(benches);
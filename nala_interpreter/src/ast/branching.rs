@@ -30,3 +30,10 @@ pub struct MatchCase {
     pub pattern: Pattern,
     pub block: Vec<Line>,
 }
+
+#[derive(Clone, Debug)]
+pub struct TryCatch {
+    pub block: Vec<Line>,
+    pub ident: String,
+    pub catch_block: Vec<Line>,
+}
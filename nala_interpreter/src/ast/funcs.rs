@@ -1,3 +1,5 @@
+use std::rc::Rc;
+
 use super::{types::type_literal_variant::TypeVariantLiteral, *};
 
 #[derive(Debug, Clone)]
@@ -6,7 +8,8 @@ pub struct FuncDeclare {
     pub params: Vec<ParamDeclare>,
     pub return_type: TypeVariantLiteral,
     pub type_param: Option<String>,
-    pub block: Box<FuncVariant>,
+    pub block: Rc<FuncVariant>,
+    pub resolution: Resolution,
 }
 
 #[derive(Debug, Clone)]
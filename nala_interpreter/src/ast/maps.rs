@@ -0,0 +1,12 @@
+use super::*;
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    pub entries: Vec<MapEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MapEntry {
+    pub key: Box<Expr>,
+    pub value: Box<Expr>,
+}
@@ -1,29 +1,35 @@
 pub mod arrays;
 pub mod branching;
 pub mod funcs;
+pub mod maps;
 pub mod math;
 pub mod objects;
 pub mod patterns;
+pub mod sets;
 pub mod terms;
+pub mod trivia;
 pub mod types;
 
 use std::fmt;
 
 use terms::Literal;
 
-use crate::builtins::BuiltinFunc;
+use crate::{builtins::BuiltinFunc, vm::Closure};
 
 use self::arrays::*;
 use self::branching::IfElseChain;
 use self::branching::Match;
+use self::branching::TryCatch;
 use self::funcs::*;
+use self::maps::*;
 use self::math::*;
 use self::objects::*;
+use self::sets::*;
 use self::types::type_literal_variant::TypeVariantLiteral;
 use self::types::variant_declare::VariantDeclare;
 use self::types::StructLiteralField;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Program {
     Block(Vec<Line>),
     Lines(Vec<Line>),
@@ -33,6 +39,7 @@ pub enum Program {
 pub enum FuncVariant {
     Nala(Vec<Line>),
     Builtin(BuiltinFunc),
+    Compiled(Closure),
 }
 
 impl fmt::Debug for FuncVariant {
@@ -44,8 +51,12 @@ impl fmt::Debug for FuncVariant {
 #[derive(Debug, Clone)]
 pub enum Line {
     Assign(PlaceExpression, Expr),
+    // Kept only so that the formatter can preserve blank lines, see `trivia`.
+    Blank,
     Break(Expr),
-    Declare(String, Expr, Option<TypeVariantLiteral>, bool),
+    // The comment's text including delimiters, and whether it trails code on the same line.
+    Comment(String, bool),
+    Declare(String, Expr, Option<TypeVariantLiteral>, bool, Resolution),
     Enum(String, Option<String>, Vec<VariantDeclare>),
     Expr(Expr),
     For(String, Expr, Vec<Self>),
@@ -54,6 +65,8 @@ pub enum Line {
     Wiles(Expr, Vec<Self>),
     Struct(String, Option<String>, Vec<StructLiteralField>),
     Match(Match),
+    Test(String, Vec<Self>),
+    TryCatch(TryCatch),
 }
 
 #[derive(Debug, Clone)]
@@ -75,12 +88,27 @@ pub enum Primary {
     Literal(Literal),
     Array(Array),
     Object(Object),
+    Map(Map),
+    Set(Set),
     EnumVariant(String, String, Option<Box<Expr>>),
 }
 
 #[derive(Debug, Clone)]
 pub enum PlaceExpression {
-    Identifier(String),
+    Identifier(String, Resolution),
     Index(Box<PlaceExpression>, Box<Expr>),
     MemberAccess(Box<PlaceExpression>, String),
 }
+
+// Where the binding an identifier refers to lives, as found by `resolver::resolve`. Globals are
+// looked up by name, since a host or the REPL can add them between programs. Every other binding is
+// kept in a slot of its scope, `depth` scopes up from the scope the identifier appears in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Resolution {
+    #[default]
+    Global,
+    Local {
+        depth: usize,
+        slot: usize,
+    },
+}
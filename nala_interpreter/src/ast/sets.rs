@@ -0,0 +1,6 @@
+use super::*;
+
+#[derive(Debug, Clone)]
+pub struct Set {
+    pub elems: Vec<Expr>,
+}
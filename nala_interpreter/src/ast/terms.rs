@@ -1,11 +1,16 @@
+use std::rc::Rc;
+
+use super::Resolution;
+
 #[derive(Debug, Clone)]
 pub enum Term {
-    Identifier(String),
+    Identifier(String, Resolution),
     Literal(Literal),
 }
 
 #[derive(Debug, Clone)]
 pub enum Literal {
     Number(f32),
-    String(String),
+    // Shared with every value evaluated from the literal.
+    String(Rc<str>),
 }
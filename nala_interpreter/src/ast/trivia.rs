@@ -0,0 +1,146 @@
+use super::{FuncVariant, Line};
+
+// Annotates a sequence of lines, given with their spans in `input`, with the trivia the formatter
+// needs. The lexer skips comments, so they're recovered here from the source between `start` and
+// `end`, the bounds of the block or program holding the lines. Blank lines separating two lines in
+// the source become a `Line::Blank`, and each comment notes whether it trails code on its line.
+//
+// Comments inside a line, such as between the elements of an array or before an `else`, have no
+// place of their own in the tree, so they're moved to just after the line.
+pub fn with_trivia(
+    input: &str,
+    start: usize,
+    end: usize,
+    lines: Vec<(usize, Line, usize)>,
+) -> Vec<Line> {
+    let mut annotated = Vec::<Line>::with_capacity(lines.len());
+    let mut position = start;
+
+    for (line_start, line, line_end) in lines {
+        for (offset, text) in find_comments(input, position, line_start) {
+            push_item(&mut annotated, input, offset, comment(input, offset, text));
+        }
+
+        let mut inner = find_comments(input, line_start, line_end);
+        let mut nested = vec![];
+        nested_comments(&line, &mut nested);
+
+        // Comments in the line's blocks were already recovered along with the block's own lines.
+        inner.retain(|(_, text)| match nested.iter().position(|n| n == text) {
+            Some(index) => {
+                nested.remove(index);
+                false
+            }
+            None => true,
+        });
+
+        push_item(&mut annotated, input, line_start, line);
+
+        for (i, (_, text)) in inner.into_iter().enumerate() {
+            annotated.push(Line::Comment(String::from(text), i == 0));
+        }
+
+        position = line_end;
+    }
+
+    for (offset, text) in find_comments(input, position, end) {
+        push_item(&mut annotated, input, offset, comment(input, offset, text));
+    }
+
+    annotated
+}
+
+// Adds a line starting at `offset`, after a `Line::Blank` if blank lines separate it from the one
+// before.
+fn push_item(annotated: &mut Vec<Line>, input: &str, offset: usize, line: Line) {
+    if !annotated.is_empty() && newlines_before(input, offset) > 1 {
+        annotated.push(Line::Blank);
+    }
+
+    annotated.push(line);
+}
+
+// A comment at the very start of the input has no line to trail.
+fn comment(input: &str, offset: usize, text: &str) -> Line {
+    let trailing = newlines_before(input, offset) == 0 && !input[..offset].trim_end().is_empty();
+    Line::Comment(String::from(text), trailing)
+}
+
+fn newlines_before(input: &str, offset: usize) -> usize {
+    let preceding = &input[..offset];
+    preceding[preceding.trim_end().len()..]
+        .matches('\n')
+        .count()
+}
+
+// The comments between `start` and `end`, with their offsets. Strings are skipped, so that their
+// contents aren't mistaken for comments.
+fn find_comments(input: &str, start: usize, end: usize) -> Vec<(usize, &str)> {
+    let source = &input[..end];
+    let mut comments = vec![];
+    let mut i = start;
+
+    while i < end {
+        let rest = &source[i..];
+
+        let len = if rest.starts_with('\'') {
+            rest[1..].find('\'').map_or(rest.len(), |close| close + 2)
+        } else if rest.starts_with("//") {
+            let len = rest.find(|c| c == '\n' || c == '\r').unwrap_or(rest.len());
+            comments.push((i, &rest[..len]));
+            len
+        } else if rest.starts_with("/*") {
+            let len = rest[2..].find("*/").map_or(rest.len(), |close| close + 4);
+            comments.push((i, &rest[..len]));
+            len
+        } else {
+            rest.chars().next().map_or(1, char::len_utf8)
+        };
+
+        i += len;
+    }
+
+    comments
+}
+
+// The text of every comment recovered within the blocks of `line`.
+fn nested_comments<'a>(line: &'a Line, comments: &mut Vec<&'a str>) {
+    let mut visit = |block: &'a Vec<Line>| {
+        for line in block {
+            match line {
+                Line::Comment(text, _) => comments.push(text),
+                line => nested_comments(line, comments),
+            }
+        }
+    };
+
+    match line {
+        Line::For(_, _, block) | Line::Wiles(_, block) | Line::Test(_, block) => visit(block),
+        Line::Func(func) => {
+            if let FuncVariant::Nala(block) = func.block.as_ref() {
+                visit(block);
+            }
+        }
+        Line::IfElseChain(chain) => {
+            visit(&chain.block);
+
+            for else_if in chain.else_ifs.iter() {
+                visit(&else_if.block);
+            }
+
+            if let Some(else_block) = &chain.else_block {
+                visit(&else_block.block);
+            }
+        }
+        Line::Match(m) => {
+            for case in m.cases.iter() {
+                visit(&case.block);
+            }
+        }
+        Line::TryCatch(try_catch) => {
+            visit(&try_catch.block);
+            visit(&try_catch.catch_block);
+        }
+        _ => (),
+    }
+}
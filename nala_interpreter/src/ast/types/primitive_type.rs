@@ -6,7 +6,9 @@ pub enum PrimitiveType {
     Bool,
     Break,
     Func,
+    Map,
     Number,
+    Set,
     String,
     Void,
 }
@@ -18,7 +20,9 @@ impl fmt::Display for PrimitiveType {
             PrimitiveType::Bool => "Bool",
             PrimitiveType::Break => "<Break>",
             PrimitiveType::Func => "Func",
+            PrimitiveType::Map => "Map",
             PrimitiveType::Number => "Number",
+            PrimitiveType::Set => "Set",
             PrimitiveType::String => "String",
             PrimitiveType::Void => "Void",
         };
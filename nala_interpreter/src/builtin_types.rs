@@ -7,19 +7,29 @@ use crate::ast::types::{
 };
 
 pub fn get_builtin_structs() -> Vec<(String, Option<String>, Vec<StructLiteralField>)> {
-    vec![(
-        String::from("HttpResult"),
-        Some(String::from("T")),
-        get_http_result_struct(),
-    )]
+    vec![
+        (String::from("Error"), None, get_error_struct()),
+        (
+            String::from("HttpResult"),
+            Some(String::from("T")),
+            get_http_result_struct(),
+        ),
+    ]
 }
 
 pub fn get_builtin_enums() -> Vec<(String, Option<String>, Vec<VariantDeclare>)> {
-    vec![(
-        String::from("Option"),
-        Some(String::from("T")),
-        get_option_enum(),
-    )]
+    vec![
+        (
+            String::from("Option"),
+            Some(String::from("T")),
+            get_option_enum(),
+        ),
+        (
+            String::from("Result"),
+            Some(String::from("T")),
+            get_result_enum(),
+        ),
+    ]
 }
 
 fn get_option_enum() -> Vec<VariantDeclare> {
@@ -32,6 +42,37 @@ fn get_option_enum() -> Vec<VariantDeclare> {
     ]
 }
 
+// Enums take a single type parameter, so errors are always described by a string.
+fn get_result_enum() -> Vec<VariantDeclare> {
+    vec![
+        VariantDeclare::Data(
+            String::from("Ok"),
+            TypeVariantLiteral::Type(TypeLiteral::UserDefined(String::from("T"))),
+        ),
+        VariantDeclare::Data(
+            String::from("Err"),
+            TypeVariantLiteral::Type(TypeLiteral::PrimitiveType(PrimitiveType::String)),
+        ),
+    ]
+}
+
+fn get_error_struct() -> Vec<StructLiteralField> {
+    vec![
+        StructLiteralField {
+            ident: String::from("kind"),
+            value: StructLiteralFieldValue::Type(TypeVariantLiteral::Type(
+                TypeLiteral::PrimitiveType(PrimitiveType::String),
+            )),
+        },
+        StructLiteralField {
+            ident: String::from("message"),
+            value: StructLiteralFieldValue::Type(TypeVariantLiteral::Type(
+                TypeLiteral::PrimitiveType(PrimitiveType::String),
+            )),
+        },
+    ]
+}
+
 // TODO: Make a helper function to simplify creating these structs.
 fn get_http_result_struct() -> Vec<StructLiteralField> {
     vec![
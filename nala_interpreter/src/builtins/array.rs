@@ -1,126 +1,596 @@
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::{cell::RefCell, cmp::Ordering, collections::HashMap, rc::Rc};
 
 use crate::{
-    ast::{types::primitive_type::PrimitiveType, *},
     errors::RuntimeError,
-    io_context::IoContext,
+    evaluator::Evaluator,
     resolved::{
         func_value::{FuncValue, Param},
+        hash_key::HashKey,
         value::Value,
     },
-    types::{composite_type::CompositeType, nala_type::NalaType, type_variant::TypeVariant},
 };
 
+use super::{
+    map::remove_entry,
+    string::{find_grapheme_index, grapheme_len},
+    util::*,
+};
+
+// Arrays are shared, so `push`, `pop`, `insert` and `remove` change the array they're passed, which
+// every binding to it sees. The other builtins leave their arguments alone and return new arrays.
+// Like `len`, `push` and `remove` also take sets.
+
+pub fn get_all_block() -> FuncValue {
+    build_block(
+        vec![array_param(), param("predicate", func_type(bool_type()))],
+        bool_type(),
+        builtin_all,
+    )
+}
+
+pub fn get_any_block() -> FuncValue {
+    build_block(
+        vec![array_param(), param("predicate", func_type(bool_type()))],
+        bool_type(),
+        builtin_any,
+    )
+}
+
+pub fn get_concat_block() -> FuncValue {
+    build_block(
+        vec![
+            param("first", array_type(generic_type("T"))),
+            param("second", array_type(generic_type("T"))),
+        ],
+        array_type(generic_type("T")),
+        builtin_concat,
+    )
+}
+
+// Takes either an array or a string, so its parameters are left generic and checked when called.
+pub fn get_contains_block() -> FuncValue {
+    build_block(
+        vec![
+            param("collection", generic_type("T")),
+            param("item", generic_type("U")),
+        ],
+        bool_type(),
+        builtin_contains,
+    )
+}
+
+pub fn get_enumerate_block() -> FuncValue {
+    build_block(
+        vec![array_param()],
+        array_type(struct_type(vec![
+            ("index", number_type()),
+            ("value", generic_type("T")),
+        ])),
+        builtin_enumerate,
+    )
+}
+
+pub fn get_filter_block() -> FuncValue {
+    build_block(
+        vec![array_param(), param("predicate", func_type(bool_type()))],
+        array_type(generic_type("T")),
+        builtin_filter,
+    )
+}
+
+pub fn get_find_block() -> FuncValue {
+    build_block(
+        vec![array_param(), param("predicate", func_type(bool_type()))],
+        option_type(generic_type("T")),
+        builtin_find,
+    )
+}
+
+pub fn get_flatten_block() -> FuncValue {
+    build_block(
+        vec![param("array", array_type(array_type(generic_type("T"))))],
+        array_type(generic_type("T")),
+        builtin_flatten,
+    )
+}
+
+// Like `contains`, takes either an array or a string.
+pub fn get_index_of_block() -> FuncValue {
+    build_block(
+        vec![
+            param("collection", generic_type("T")),
+            param("item", generic_type("U")),
+        ],
+        option_type(number_type()),
+        builtin_index_of,
+    )
+}
+
+pub fn get_insert_block() -> FuncValue {
+    build_block(
+        vec![
+            array_param(),
+            param("index", number_type()),
+            param("value", generic_type("T")),
+        ],
+        void_type(),
+        builtin_insert,
+    )
+}
+
+// Takes an array, map, set or string, so its parameter is left generic and checked when called.
 pub fn get_len_block() -> FuncValue {
-    let inner_type = TypeVariant::Type(NalaType::Generic(String::from("T")));
+    build_block(
+        vec![param("value", generic_type("T"))],
+        number_type(),
+        builtin_len,
+    )
+}
+
+pub fn get_map_block() -> FuncValue {
+    build_block(
+        vec![array_param(), param("func", func_type(generic_type("U")))],
+        array_type(generic_type("U")),
+        builtin_map,
+    )
+}
 
-    let outer_type = TypeVariant::Composite(CompositeType {
-        outer: NalaType::PrimitiveType(PrimitiveType::Array),
-        inner: vec![inner_type],
-        generic_type_param: Some(String::from("T")),
-    });
+pub fn get_pop_block() -> FuncValue {
+    build_block(
+        vec![array_param()],
+        option_type(generic_type("T")),
+        builtin_pop,
+    )
+}
 
-    let params = vec![Param {
-        ident: String::from("array"),
-        param_type: outer_type,
-    }];
+// Takes either an array or a set, so its parameters are left generic and checked when called.
+pub fn get_push_block() -> FuncValue {
+    build_block(
+        vec![
+            param("collection", generic_type("T")),
+            param("value", generic_type("U")),
+        ],
+        void_type(),
+        builtin_push,
+    )
+}
 
-    let return_type = TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Number));
+pub fn get_range_block() -> FuncValue {
+    build_block(
+        vec![param("start", number_type()), param("end", number_type())],
+        array_type(number_type()),
+        builtin_range,
+    )
+}
 
-    FuncValue {
-        params,
-        return_type,
-        type_param: None,
-        closure_scope: 0,
-        block: Box::new(FuncVariant::Builtin(builtin_len)),
-    }
+pub fn get_reduce_block() -> FuncValue {
+    build_block(
+        vec![
+            array_param(),
+            param("initial", generic_type("U")),
+            param("func", func_type(generic_type("U"))),
+        ],
+        generic_type("U"),
+        builtin_reduce,
+    )
+}
+
+// Takes an array and an index, a map and a key, or a set and an element, so its parameters are left
+// generic and checked when called. Removing from a map returns an `Option`, as the key may not be
+// present. Removing from a set returns `Void`, so that it can be used as a statement; use `has` to
+// check whether the element was there.
+pub fn get_remove_block() -> FuncValue {
+    build_block(
+        vec![
+            param("collection", generic_type("T")),
+            param("key", generic_type("U")),
+        ],
+        generic_type("V"),
+        builtin_remove,
+    )
+}
+
+pub fn get_reverse_block() -> FuncValue {
+    build_block(
+        vec![array_param()],
+        array_type(generic_type("T")),
+        builtin_reverse,
+    )
 }
 
 pub fn get_slice_block() -> FuncValue {
-    let inner_type = TypeVariant::Type(NalaType::Generic(String::from("T")));
+    build_block(
+        vec![
+            array_param(),
+            param("start", number_type()),
+            param("end", number_type()),
+        ],
+        array_type(generic_type("T")),
+        builtin_slice,
+    )
+}
 
-    let outer_type = TypeVariant::Composite(CompositeType {
-        outer: NalaType::PrimitiveType(PrimitiveType::Array),
-        inner: vec![inner_type],
-        generic_type_param: Some(String::from("T")),
-    });
+pub fn get_sort_by_block() -> FuncValue {
+    build_block(
+        vec![array_param(), param("key", func_type(generic_type("K")))],
+        array_type(generic_type("T")),
+        builtin_sort_by,
+    )
+}
 
-    let array_param = Param {
-        ident: String::from("array"),
-        param_type: outer_type,
-    };
+pub fn get_zip_block() -> FuncValue {
+    build_block(
+        vec![
+            param("first", array_type(generic_type("T"))),
+            param("second", array_type(generic_type("U"))),
+        ],
+        array_type(struct_type(vec![
+            ("first", generic_type("T")),
+            ("second", generic_type("U")),
+        ])),
+        builtin_zip,
+    )
+}
 
-    let start_param = Param {
-        ident: String::from("start"),
-        param_type: TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Number)),
-    };
+fn builtin_all(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let predicate = get_func(&args[1]);
 
-    let end_param = Param {
-        ident: String::from("end"),
-        param_type: TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Number)),
-    };
+    for item in get_items(&args[0]) {
+        if !test(&predicate, item, evaluator)? {
+            return Ok(Value::Bool(false));
+        }
+    }
 
-    let inner_return_type = TypeVariant::Type(NalaType::Generic(String::from("T")));
+    Ok(Value::Bool(true))
+}
 
-    let return_type = TypeVariant::Composite(CompositeType {
-        outer: NalaType::PrimitiveType(PrimitiveType::Array),
-        inner: vec![inner_return_type],
-        generic_type_param: Some(String::from("T")),
-    });
+fn builtin_any(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let predicate = get_func(&args[1]);
 
-    FuncValue {
-        params: vec![array_param, start_param, end_param],
-        return_type,
-        type_param: None,
-        closure_scope: 0,
-        block: Box::new(FuncVariant::Builtin(builtin_slice)),
+    for item in get_items(&args[0]) {
+        if test(&predicate, item, evaluator)? {
+            return Ok(Value::Bool(true));
+        }
     }
+
+    Ok(Value::Bool(false))
+}
+
+fn builtin_concat(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let mut items = get_items(&args[0]);
+    items.extend(get_items(&args[1]));
+    evaluator.build_array(items)
 }
 
-fn builtin_len(
-    args: HashMap<String, Value>,
-    _context: &mut dyn IoContext,
+fn builtin_contains(
+    args: Vec<Value>,
+    _evaluator: &mut dyn Evaluator,
 ) -> Result<Value, RuntimeError> {
-    let array = args.get("array").unwrap();
+    let collection = &args[0];
+    let item = &args[1];
+    Ok(Value::Bool(find_index(collection, item)?.is_some()))
+}
 
-    if let Value::Array(array) = array {
-        let array = Arc::clone(array);
-        let array = array.lock().unwrap();
-        Ok(Value::Num(array.len() as f32))
-    } else {
-        unreachable!()
+fn builtin_enumerate(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let entries = get_items(&args[0])
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| build_pair(("index", Value::Num(index as f32)), ("value", value)))
+        .collect();
+
+    evaluator.build_array(entries)
+}
+
+fn builtin_filter(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let predicate = get_func(&args[1]);
+    let mut kept = vec![];
+
+    for item in get_items(&args[0]) {
+        if test(&predicate, item.clone(), evaluator)? {
+            kept.push(item);
+        }
     }
+
+    evaluator.build_array(kept)
+}
+
+fn builtin_find(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let predicate = get_func(&args[1]);
+
+    for item in get_items(&args[0]) {
+        if test(&predicate, item.clone(), evaluator)? {
+            return Ok(build_some(item));
+        }
+    }
+
+    Ok(build_none())
+}
+
+fn builtin_flatten(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let mut items = vec![];
+
+    for inner in get_items(&args[0]) {
+        if let Value::Array(inner) = inner {
+            items.extend(inner.borrow().iter().cloned());
+        } else {
+            unreachable!()
+        }
+    }
+
+    evaluator.build_array(items)
 }
 
-fn builtin_slice(
-    args: HashMap<String, Value>,
-    _context: &mut dyn IoContext,
+fn builtin_index_of(
+    args: Vec<Value>,
+    _evaluator: &mut dyn Evaluator,
 ) -> Result<Value, RuntimeError> {
-    let array = if let Value::Array(array) = args.get("array").unwrap() {
-        array
-    } else {
-        unreachable!()
+    let collection = &args[0];
+    let item = &args[1];
+
+    Ok(match find_index(collection, item)? {
+        Some(index) => build_some(Value::Num(index as f32)),
+        None => build_none(),
+    })
+}
+
+fn builtin_insert(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let array = get_array(&args[0]);
+    let index = get_index(&args[1], "index")?;
+    let value = args[2].clone();
+
+    evaluator.check_element(&array.borrow(), &value)?;
+
+    let len = array.borrow().len();
+    if index > len {
+        return Err(out_of_bounds_error(index, len));
+    }
+
+    array.borrow_mut().insert(index, value);
+    Ok(Value::Void)
+}
+
+fn builtin_len(args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    match &args[0] {
+        Value::Array(array) => Ok(Value::Num(array.borrow().len() as f32)),
+        Value::Map(map) => Ok(Value::Num(map.borrow().len() as f32)),
+        Value::Set(set) => Ok(Value::Num(set.borrow().len() as f32)),
+        Value::String(string) => Ok(Value::Num(grapheme_len(string) as f32)),
+        value => Err(RuntimeError::new(&format!(
+            "Cannot get the length of `{value}`, which is not an Array, Map, Set or String."
+        ))),
+    }
+}
+
+fn builtin_map(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let func = get_func(&args[1]);
+    let mut mapped = vec![];
+
+    for item in get_items(&args[0]) {
+        mapped.push(evaluator.call(Rc::clone(&func), vec![item])?);
+    }
+
+    evaluator.build_array(mapped)
+}
+
+fn builtin_pop(args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let array = get_array(&args[0]);
+    let popped = array.borrow_mut().pop();
+
+    Ok(match popped {
+        Some(value) => build_some(value),
+        None => build_none(),
+    })
+}
+
+fn builtin_push(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let value = args[1].clone();
+
+    match &args[0] {
+        Value::Array(array) => {
+            evaluator.check_element(&array.borrow(), &value)?;
+            array.borrow_mut().push(value);
+        }
+        Value::Set(set) => {
+            let elem = HashKey::from_value(&value)?;
+            evaluator.check_set_element(&set.borrow(), &elem)?;
+            set.borrow_mut().insert(elem);
+        }
+        collection => Err(RuntimeError::new(&format!(
+            "Cannot push onto `{collection}`, which is neither an Array nor a Set."
+        )))?,
+    }
+
+    Ok(Value::Void)
+}
+
+fn builtin_range(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let start = get_whole_number(&args[0], "start")?;
+    let end = get_whole_number(&args[1], "end")?;
+
+    // Checked before the numbers are generated, so that a huge range fails without using the memory.
+    let len = (end - start).max(0.0) as usize;
+    evaluator.check_collection_len(len)?;
+
+    let numbers = (0..len).map(|i| Value::Num(start + i as f32)).collect();
+    Ok(Value::Array(Rc::new(RefCell::new(numbers))))
+}
+
+fn builtin_reduce(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let func = get_func(&args[2]);
+    let mut result = args[1].clone();
+
+    for item in get_items(&args[0]) {
+        result = evaluator.call(Rc::clone(&func), vec![result, item])?;
+    }
+
+    Ok(result)
+}
+
+fn builtin_remove(args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let array = match (&args[0], &args[1]) {
+        (Value::Array(array), Value::Num(_)) => array,
+        (Value::Array(_), index) => Err(RuntimeError::new(&format!(
+            "Cannot remove from an Array at `{index}`, which is not a Number."
+        )))?,
+        (Value::Map(map), key) => return remove_entry(map, key),
+        (Value::Set(set), elem) => {
+            let elem = HashKey::from_value(elem)?;
+            set.borrow_mut().shift_remove(&elem);
+            return Ok(Value::Void);
+        }
+        (collection, _) => Err(RuntimeError::new(&format!(
+            "Cannot remove from `{collection}`, which is not an Array, Map or Set."
+        )))?,
     };
 
-    let start = if let Value::Num(start) = args.get("start").unwrap() {
-        *start as usize
+    let index = get_index(&args[1], "index")?;
+
+    let len = array.borrow().len();
+    if index >= len {
+        return Err(out_of_bounds_error(index, len));
+    }
+
+    let removed = array.borrow_mut().remove(index);
+    Ok(removed)
+}
+
+fn builtin_reverse(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let mut items = get_items(&args[0]);
+    items.reverse();
+    evaluator.build_array(items)
+}
+
+fn builtin_slice(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let items = get_items(&args[0]);
+    let start = get_index(&args[1], "start")?;
+    let end = get_index(&args[2], "end")?;
+
+    if start > end || end > items.len() {
+        return Err(RuntimeError::new(&format!(
+            "Slice range {start}..{end} is out of bounds for an array of length {}.",
+            items.len()
+        )));
+    }
+
+    evaluator.build_array(items[start..end].to_vec())
+}
+
+// Sorts by the keys `key` gives each element, which must be all Numbers or all Strings. Elements with
+// equal keys keep their order.
+fn builtin_sort_by(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let key = get_func(&args[1]);
+    let mut keyed = vec![];
+
+    for item in get_items(&args[0]) {
+        let item_key = evaluator.call(Rc::clone(&key), vec![item.clone()])?;
+
+        if !matches!(item_key, Value::Num(_) | Value::String(_)) {
+            return Err(RuntimeError::new(&format!(
+                "Cannot sort by `{item_key}`, which is neither a Number nor a String."
+            )));
+        }
+
+        keyed.push((item_key, item));
+    }
+
+    if let Some((first, _)) = keyed.first() {
+        if let Some((other, _)) = keyed
+            .iter()
+            .find(|(k, _)| k.is_string() != first.is_string())
+        {
+            return Err(RuntimeError::new(&format!(
+                "Cannot sort by keys of different types, such as `{first}` and `{other}`."
+            )));
+        }
+    }
+
+    keyed.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+    evaluator.build_array(keyed.into_iter().map(|(_, item)| item).collect())
+}
+
+fn builtin_zip(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let pairs = get_items(&args[0])
+        .into_iter()
+        .zip(get_items(&args[1]))
+        .map(|(first, second)| build_pair(("first", first), ("second", second)))
+        .collect();
+
+    evaluator.build_array(pairs)
+}
+
+fn array_param() -> Param {
+    param("array", array_type(generic_type("T")))
+}
+
+// A copy of the elements of an array argument, so that callbacks are free to change the array.
+fn get_items(value: &Value) -> Vec<Value> {
+    get_array(value).borrow().clone()
+}
+
+fn get_whole_number(value: &Value, ident: &str) -> Result<f32, RuntimeError> {
+    let num = if let Value::Num(num) = value {
+        *num
     } else {
         unreachable!()
     };
 
-    let end = if let Value::Num(end) = args.get("end").unwrap() {
-        *end as usize
+    if num.fract() != 0.0 {
+        Err(RuntimeError::new(&format!(
+            "Expected `{ident}` to be a whole number, but got {num}."
+        )))
     } else {
-        unreachable!()
-    };
+        Ok(num)
+    }
+}
+
+// Calls a predicate, whose return type is checked to be `Bool` when it's passed.
+fn test(
+    predicate: &Rc<FuncValue>,
+    item: Value,
+    evaluator: &mut dyn Evaluator,
+) -> Result<bool, RuntimeError> {
+    match evaluator.call(Rc::clone(predicate), vec![item])? {
+        Value::Bool(result) => Ok(result),
+        _ => unreachable!(),
+    }
+}
 
-    let array = Arc::clone(array);
-    let array = array.lock().unwrap();
+// Finds `item` in an array, or a part of a string, returning its index in characters.
+fn find_index(collection: &Value, item: &Value) -> Result<Option<usize>, RuntimeError> {
+    match (collection, item) {
+        (Value::Array(items), item) => Ok(items.borrow().iter().position(|i| i == item)),
+        (Value::String(text), Value::String(part)) => Ok(find_grapheme_index(text, part)),
+        (Value::String(_), item) => Err(RuntimeError::new(&format!(
+            "Cannot search a String for `{item}`, which is not a String."
+        ))),
+        (collection, _) => Err(RuntimeError::new(&format!(
+            "Cannot search `{collection}`, which is neither an Array nor a String."
+        ))),
+    }
+}
+
+fn compare_keys(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Num(a), Value::Num(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => unreachable!("Keys are checked to be of the same type before sorting."),
+    }
+}
+
+fn build_pair(first: (&str, Value), second: (&str, Value)) -> Value {
+    let fields = HashMap::from([
+        (String::from(first.0), first.1),
+        (String::from(second.0), second.1),
+    ]);
+
+    Value::Object(Rc::new(RefCell::new(fields)))
+}
 
-    Ok(Value::Array(Arc::new(Mutex::new(
-        array[start..end].to_owned(),
-    ))))
+fn out_of_bounds_error(index: usize, len: usize) -> RuntimeError {
+    RuntimeError::new(&format!(
+        "Index {index} is out of bounds for an array of length {len}."
+    ))
 }
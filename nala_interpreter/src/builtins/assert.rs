@@ -0,0 +1,173 @@
+use std::{collections::BTreeSet, rc::Rc, sync::Arc};
+
+use crate::{
+    ast::{types::primitive_type::PrimitiveType, FuncVariant},
+    errors::{ErrorKind, RuntimeError},
+    evaluator::Evaluator,
+    resolved::{
+        func_value::{FuncValue, Param},
+        value::{EnumVariantValue, Value},
+    },
+    scopes::ScopeRef,
+    types::{nala_type::NalaType, type_variant::TypeVariant},
+};
+
+pub fn get_assert_block() -> FuncValue {
+    let condition_param = Param {
+        ident: String::from("condition"),
+        param_type: TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Bool)),
+    };
+
+    let return_type = TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Void));
+
+    FuncValue {
+        params: vec![condition_param],
+        return_type,
+        type_param: None,
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(builtin_assert))),
+    }
+}
+
+pub fn get_assert_eq_block() -> FuncValue {
+    let expected_param = Param {
+        ident: String::from("expected"),
+        param_type: TypeVariant::Type(NalaType::Generic(String::from("T"))),
+    };
+
+    let actual_param = Param {
+        ident: String::from("actual"),
+        param_type: TypeVariant::Type(NalaType::Generic(String::from("T"))),
+    };
+
+    let return_type = TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Void));
+
+    FuncValue {
+        params: vec![expected_param, actual_param],
+        return_type,
+        type_param: None,
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(builtin_assert_eq))),
+    }
+}
+
+fn builtin_assert(args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    if let Value::Bool(true) = &args[0] {
+        Ok(Value::Void)
+    } else {
+        Err(RuntimeError::with_kind(
+            ErrorKind::Assertion,
+            "Assertion failed: condition was false.",
+        ))
+    }
+}
+
+fn builtin_assert_eq(
+    args: Vec<Value>,
+    _evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let expected = &args[0];
+    let actual = &args[1];
+
+    if expected == actual {
+        return Ok(Value::Void);
+    }
+
+    let mut differences = Vec::<String>::new();
+    diff_values(expected, actual, "", &mut differences);
+
+    let differences = differences
+        .iter()
+        .map(|d| format!("\n    {d}"))
+        .collect::<String>();
+
+    Err(RuntimeError::with_kind(
+        ErrorKind::Assertion,
+        &format!(
+            "Assertion failed: values are not equal.\n  expected: {expected:?}\n  actual:   {actual:?}\n  differences:{differences}"
+        ),
+    ))
+}
+
+// Walks both values in parallel, recording a line for each place they differ. `path` describes the
+// location of the current values relative to the values passed to `assertEq`.
+fn diff_values(expected: &Value, actual: &Value, path: &str, differences: &mut Vec<String>) {
+    let location = if path.is_empty() { "<root>" } else { path };
+
+    match (expected, actual) {
+        (Value::Array(expected_items), Value::Array(actual_items)) => {
+            if Rc::ptr_eq(expected_items, actual_items) {
+                return;
+            }
+
+            let expected_items = expected_items.borrow();
+            let actual_items = actual_items.borrow();
+
+            if expected_items.len() != actual_items.len() {
+                differences.push(format!(
+                    "{location}: expected length {0}, found length {1}",
+                    expected_items.len(),
+                    actual_items.len()
+                ));
+            }
+
+            for (i, (expected, actual)) in
+                expected_items.iter().zip(actual_items.iter()).enumerate()
+            {
+                diff_values(expected, actual, &format!("{path}[{i}]"), differences);
+            }
+        }
+        (Value::Object(expected_fields), Value::Object(actual_fields)) => {
+            if Rc::ptr_eq(expected_fields, actual_fields) {
+                return;
+            }
+
+            let expected_fields = expected_fields.borrow();
+            let actual_fields = actual_fields.borrow();
+
+            let keys: BTreeSet<&String> =
+                expected_fields.keys().chain(actual_fields.keys()).collect();
+
+            for key in keys {
+                let field_path = format!("{path}.{key}");
+
+                match (expected_fields.get(key), actual_fields.get(key)) {
+                    (Some(expected), Some(actual)) => {
+                        diff_values(expected, actual, &field_path, differences)
+                    }
+                    (Some(expected), None) => differences.push(format!(
+                        "{field_path}: expected {expected:?}, found no such field"
+                    )),
+                    (None, Some(actual)) => differences.push(format!(
+                        "{field_path}: expected no such field, found {actual:?}"
+                    )),
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+        (
+            Value::Variant(EnumVariantValue {
+                variant_ident: expected_variant,
+                data: Some(expected_data),
+                ..
+            }),
+            Value::Variant(EnumVariantValue {
+                variant_ident: actual_variant,
+                data: Some(actual_data),
+                ..
+            }),
+        ) if expected_variant == actual_variant => diff_values(
+            expected_data,
+            actual_data,
+            &format!("{path}({expected_variant})"),
+            differences,
+        ),
+        (expected, actual) => {
+            if expected != actual {
+                differences.push(format!(
+                    "{location}: expected {expected:?}, found {actual:?}"
+                ));
+            }
+        }
+    }
+}
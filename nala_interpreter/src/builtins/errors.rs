@@ -0,0 +1,35 @@
+use std::{rc::Rc, sync::Arc};
+
+use crate::{
+    ast::{types::primitive_type::PrimitiveType, FuncVariant},
+    errors::{ErrorKind, RuntimeError},
+    evaluator::Evaluator,
+    resolved::{
+        func_value::{FuncValue, Param},
+        value::Value,
+    },
+    scopes::ScopeRef,
+    types::{nala_type::NalaType, type_variant::TypeVariant},
+};
+
+pub fn get_panic_block() -> FuncValue {
+    let message_param = Param {
+        ident: String::from("message"),
+        param_type: TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::String)),
+    };
+
+    let return_type = TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Void));
+
+    FuncValue {
+        params: vec![message_param],
+        return_type,
+        type_param: None,
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(builtin_panic))),
+    }
+}
+
+fn builtin_panic(args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let message = &args[0].as_string().unwrap();
+    Err(RuntimeError::with_kind(ErrorKind::Panic, &message))
+}
@@ -0,0 +1,185 @@
+use std::{cell::RefCell, io, rc::Rc};
+
+use crate::{
+    errors::RuntimeError,
+    evaluator::Evaluator,
+    resolved::{func_value::FuncValue, value::Value},
+};
+
+use super::util::*;
+
+// Files are reached through the host's `IoContext`. Failures are returned as `Result::Err` rather
+// than raised, as a missing file is usually something a script should handle. Builtins which only
+// write hold the path they wrote to on success.
+
+pub fn get_append_file_block() -> FuncValue {
+    build_block(
+        vec![
+            param("path", string_type()),
+            param("contents", string_type()),
+        ],
+        result_type(string_type()),
+        builtin_append_file,
+    )
+}
+
+pub fn get_exists_block() -> FuncValue {
+    build_block(
+        vec![param("path", string_type())],
+        bool_type(),
+        builtin_exists,
+    )
+}
+
+pub fn get_list_dir_block() -> FuncValue {
+    build_block(
+        vec![param("path", string_type())],
+        result_type(array_type(string_type())),
+        builtin_list_dir,
+    )
+}
+
+pub fn get_make_dir_block() -> FuncValue {
+    build_block(
+        vec![param("path", string_type())],
+        result_type(string_type()),
+        builtin_make_dir,
+    )
+}
+
+pub fn get_read_file_block() -> FuncValue {
+    build_block(
+        vec![param("path", string_type())],
+        result_type(string_type()),
+        builtin_read_file,
+    )
+}
+
+pub fn get_read_lines_block() -> FuncValue {
+    build_block(
+        vec![param("path", string_type())],
+        result_type(array_type(string_type())),
+        builtin_read_lines,
+    )
+}
+
+pub fn get_remove_file_block() -> FuncValue {
+    build_block(
+        vec![param("path", string_type())],
+        result_type(string_type()),
+        builtin_remove_file,
+    )
+}
+
+pub fn get_write_file_block() -> FuncValue {
+    build_block(
+        vec![
+            param("path", string_type()),
+            param("contents", string_type()),
+        ],
+        result_type(string_type()),
+        builtin_write_file,
+    )
+}
+
+fn builtin_append_file(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let path = get_string(&args[0]);
+    let result = evaluator.ctx().append_file(path, get_string(&args[1]));
+    Ok(written(result, path))
+}
+
+fn builtin_exists(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    Ok(Value::Bool(evaluator.ctx().exists(get_string(&args[0]))))
+}
+
+fn builtin_list_dir(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    match evaluator.ctx().list_dir(get_string(&args[0])) {
+        Ok(names) => string_array(names, evaluator),
+        Err(error) => Ok(io_err(error)),
+    }
+}
+
+fn builtin_make_dir(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let path = get_string(&args[0]);
+    let result = evaluator.ctx().make_dir(path);
+    Ok(written(result, path))
+}
+
+fn builtin_read_file(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    match evaluator.ctx().read_file(get_string(&args[0])) {
+        Ok(contents) => {
+            evaluator.check_string_len(contents.len())?;
+            Ok(build_ok(Value::String(contents.into())))
+        }
+        Err(error) => Ok(io_err(error)),
+    }
+}
+
+fn builtin_read_lines(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    match evaluator.ctx().read_file(get_string(&args[0])) {
+        Ok(contents) => string_array(contents.lines().map(String::from).collect(), evaluator),
+        Err(error) => Ok(io_err(error)),
+    }
+}
+
+fn builtin_remove_file(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let path = get_string(&args[0]);
+    let result = evaluator.ctx().remove_file(path);
+    Ok(written(result, path))
+}
+
+fn builtin_write_file(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let path = get_string(&args[0]);
+    let result = evaluator.ctx().write_file(path, get_string(&args[1]));
+    Ok(written(result, path))
+}
+
+fn string_array(
+    strings: Vec<String>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    evaluator.check_collection_len(strings.len())?;
+
+    for string in strings.iter() {
+        evaluator.check_string_len(string.len())?;
+    }
+
+    let items = strings
+        .into_iter()
+        .map(|s| Value::String(s.into()))
+        .collect();
+
+    Ok(build_ok(Value::Array(Rc::new(RefCell::new(items)))))
+}
+
+fn written(result: io::Result<()>, path: &str) -> Value {
+    match result {
+        Ok(()) => build_ok(Value::String(path.into())),
+        Err(error) => io_err(error),
+    }
+}
+
+fn io_err(error: io::Error) -> Value {
+    build_err(&error.to_string())
+}
@@ -1,20 +1,20 @@
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
 
-use reqwest;
+use reqwest::StatusCode;
 use serde_json;
 
 use crate::{
     ast::{types::primitive_type::PrimitiveType, *},
     errors::RuntimeError,
-    io_context::IoContext,
+    evaluator::Evaluator,
+    io_context::HttpRequest,
     resolved::{
         func_value::{FuncValue, Param},
+        ser::to_value,
         struct_field::StructField,
         value::Value,
     },
+    scopes::ScopeRef,
     types::{nala_type::NalaType, type_variant::TypeVariant},
 };
 
@@ -23,6 +23,7 @@ use super::util::{build_none, build_some};
 pub fn get_http_block() -> FuncValue {
     let return_type = TypeVariant::Type(NalaType::Generic(String::from("T")));
 
+    // A `body` is optional, so it isn't declared here, but one is sent if it's a `String`.
     let options_fields = vec![
         StructField {
             ident: String::from("method"),
@@ -32,10 +33,6 @@ pub fn get_http_block() -> FuncValue {
             ident: String::from("url"),
             value_type: TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::String)),
         },
-        StructField {
-            ident: String::from("body"),
-            value_type: TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::String)),
-        },
     ];
 
     let params = vec![Param {
@@ -47,93 +44,58 @@ pub fn get_http_block() -> FuncValue {
         params,
         return_type,
         type_param: None,
-        closure_scope: 0,
-        block: Box::new(FuncVariant::Builtin(builtin_http)),
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(builtin_http))),
     }
 }
 
-fn builtin_http(
-    args: HashMap<String, Value>,
-    _ctx: &mut dyn IoContext,
-) -> Result<Value, RuntimeError> {
-    let options = args.get("options").unwrap();
+fn builtin_http(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let options = &args[0];
 
-    let mutex = if let Value::Object(reference) = options {
-        Arc::clone(&reference)
+    let options = if let Value::Object(reference) = options {
+        Rc::clone(&reference)
     } else {
         unreachable!()
     };
 
-    let options = mutex.lock().unwrap();
-
-    let url = options["url"].as_string().unwrap();
-    let method = options["method"].as_string().unwrap();
+    let options = options.borrow();
 
     let body = if let Some(Value::String(body)) = options.get("body") {
-        Some(body)
+        Some(body.to_string())
     } else {
         None
     };
 
-    let add_method = |client: reqwest::blocking::Client| match method.as_str() {
-        "GET" => client.get(url),
-        "POST" => client.post(url),
-        "PUT" => client.put(url),
-        _ => todo!(),
-    };
-
-    let client = add_method(reqwest::blocking::Client::new());
-
-    let response = if let Some(body) = body {
-        client.body(body.clone()).send()
-    } else {
-        client.send()
+    let request = HttpRequest {
+        method: options["method"].as_string().unwrap(),
+        url: options["url"].as_string().unwrap(),
+        body,
     };
 
-    let fields = match response {
+    let fields = match evaluator.ctx().http(&request) {
         Ok(response) => {
-            let status_code = build_some(Value::String(response.status().to_string()));
+            let status = StatusCode::from_u16(response.status)
+                .map(|status| status.to_string())
+                .unwrap_or_else(|_| response.status.to_string());
 
-            let body = match response.json::<serde_json::Value>() {
-                Ok(value) => build_some(build_value(value)),
+            let body = match serde_json::from_str::<serde_json::Value>(&response.body) {
+                Ok(value) => build_some(to_value(&value)?),
                 Err(_) => build_none(),
             };
 
             HashMap::from([
-                (String::from("statusCode"), status_code),
+                (
+                    String::from("statusCode"),
+                    build_some(Value::String(status.into())),
+                ),
                 (String::from("body"), body),
             ])
         }
-        Err(error) => {
-            let status_code = error
-                .status()
-                .map(|code| build_some(Value::String(code.to_string())))
-                .unwrap_or(build_none());
-
-            HashMap::from([
-                (String::from("statusCode"), status_code),
-                (String::from("body"), build_none()),
-            ])
-        }
+        Err(_) => HashMap::from([
+            (String::from("statusCode"), build_none()),
+            (String::from("body"), build_none()),
+        ]),
     };
 
-    Ok(Value::Object(Arc::new(Mutex::new(fields))))
-}
-
-fn build_value(value: serde_json::Value) -> Value {
-    match value {
-        serde_json::Value::Array(items) => Value::Array(Arc::new(Mutex::new(
-            items.into_iter().map(build_value).collect::<Vec<Value>>(),
-        ))),
-        serde_json::Value::Null => build_none(),
-        serde_json::Value::Bool(value) => Value::Bool(value),
-        serde_json::Value::Number(_) => todo!(),
-        serde_json::Value::String(value) => Value::String(value),
-        serde_json::Value::Object(fields) => Value::Object(Arc::new(Mutex::new(
-            fields
-                .into_iter()
-                .map(|(key, value)| (key, build_value(value)))
-                .collect(),
-        ))),
-    }
+    Ok(Value::Object(Rc::new(RefCell::new(fields))))
 }
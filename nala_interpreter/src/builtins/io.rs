@@ -1,16 +1,36 @@
-use std::collections::HashMap;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 
 use crate::{
     ast::{types::primitive_type::PrimitiveType, *},
     errors::RuntimeError,
+    evaluator::Evaluator,
     io_context::IoContext,
     resolved::{
         func_value::{FuncValue, Param},
         value::Value,
     },
-    types::{nala_type::NalaType, type_variant::TypeVariant},
+    scopes::ScopeRef,
+    types::{composite_type::CompositeType, nala_type::NalaType, type_variant::TypeVariant},
 };
 
+pub fn get_args_block() -> FuncValue {
+    let return_type = TypeVariant::Composite(CompositeType {
+        outer: NalaType::PrimitiveType(PrimitiveType::Array),
+        inner: vec![TypeVariant::Type(NalaType::PrimitiveType(
+            PrimitiveType::String,
+        ))],
+        generic_type_param: None,
+    });
+
+    FuncValue {
+        params: vec![],
+        return_type,
+        type_param: None,
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(builtin_args))),
+    }
+}
+
 pub fn get_print_block() -> FuncValue {
     let message_param = Param {
         ident: String::from("message"),
@@ -23,8 +43,8 @@ pub fn get_print_block() -> FuncValue {
         params: vec![message_param],
         return_type,
         type_param: None,
-        closure_scope: 0,
-        block: Box::new(FuncVariant::Builtin(builtin_print)),
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(builtin_print))),
     }
 }
 
@@ -35,8 +55,8 @@ pub fn get_read_block() -> FuncValue {
         params: vec![],
         return_type,
         type_param: None,
-        closure_scope: 0,
-        block: Box::new(FuncVariant::Builtin(builtin_read)),
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(builtin_read))),
     }
 }
 
@@ -47,33 +67,37 @@ pub fn get_readnum_block() -> FuncValue {
         params: vec![],
         return_type,
         type_param: None,
-        closure_scope: 0,
-        block: Box::new(FuncVariant::Builtin(builtin_readnum)),
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(builtin_readnum))),
     }
 }
 
-fn builtin_print(
-    args: HashMap<String, Value>,
-    ctx: &mut dyn IoContext,
-) -> Result<Value, RuntimeError> {
-    let message = args.get("message").unwrap();
-    ctx.print(&message.to_string());
+fn builtin_args(_args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let args = evaluator
+        .ctx()
+        .args()
+        .into_iter()
+        .map(|arg| Value::String(arg.into()))
+        .collect();
+    Ok(Value::Array(Rc::new(RefCell::new(args))))
+}
+
+fn builtin_print(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let message = &args[0];
+    evaluator.ctx().print(&message.to_string());
     Ok(Value::Void)
 }
 
-fn builtin_read(
-    _args: HashMap<String, Value>,
-    ctx: &mut dyn IoContext,
-) -> Result<Value, RuntimeError> {
-    let input = ctx.read();
-    Ok(Value::String(input.trim().to_string()))
+fn builtin_read(_args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let input = read_input(evaluator.ctx())?;
+    Ok(Value::String(input.trim().into()))
 }
 
 fn builtin_readnum(
-    _args: HashMap<String, Value>,
-    ctx: &mut dyn IoContext,
+    _args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
 ) -> Result<Value, RuntimeError> {
-    let input = ctx.read();
+    let input = read_input(evaluator.ctx())?;
 
     input
         .trim()
@@ -82,3 +106,8 @@ fn builtin_readnum(
         .map(|num| Value::Num(num))
         .map_err(|_| RuntimeError::new(&format!("Could not parse input `{input}` as type Number.")))
 }
+
+fn read_input(ctx: &mut dyn IoContext) -> Result<String, RuntimeError> {
+    ctx.read()
+        .ok_or_else(|| RuntimeError::new("Reached the end of input."))
+}
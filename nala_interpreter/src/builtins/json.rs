@@ -0,0 +1,240 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use serde_json::{Map as JsonMap, Number as JsonNumber, Value as Json};
+
+use crate::{
+    ast::types::primitive_type::PrimitiveType,
+    errors::{ErrorKind, RuntimeError},
+    evaluator::Evaluator,
+    resolved::{
+        func_value::FuncValue, hash_key::HashKey, ser::to_value, value::EnumVariantValue,
+        value::Value,
+    },
+    types::{
+        composite_type::CompositeType, fit::is_option, nala_type::NalaType,
+        type_variant::TypeVariant,
+    },
+};
+
+use super::util::*;
+
+// JSON null is `Option::None`. Going the other way, `Option::Some` is written as the value it holds.
+
+pub fn get_json_parse_block() -> FuncValue {
+    // Passing a type argument, as in `jsonParse::<Person>(text)`, checks the parsed value against it.
+    FuncValue {
+        type_param: Some(String::from("T")),
+        ..build_block(
+            vec![param("text", string_type())],
+            result_type(generic_type("T")),
+            builtin_json_parse,
+        )
+    }
+}
+
+pub fn get_json_stringify_block() -> FuncValue {
+    build_block(
+        vec![
+            param("value", generic_type("T")),
+            param("pretty", bool_type()),
+        ],
+        string_type(),
+        builtin_json_stringify,
+    )
+}
+
+fn builtin_json_parse(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let json = match serde_json::from_str::<Json>(get_string(&args[0])) {
+        Ok(json) => json,
+        Err(error) => return Ok(build_err(&format!("Invalid JSON: {error}."))),
+    };
+
+    let target = evaluator.get_type("T")?;
+
+    // Without a type argument, an empty array's elements have no type.
+    let typed = target.find_generic_type_param().is_none();
+
+    // JSON that has no value in the language, such as a mixed array, is an `Err` like invalid JSON,
+    // while exceeding a limit still stops the program.
+    let value = match from_json(&json, typed, evaluator) {
+        Ok(value) => value,
+        Err(error) if error.kind == ErrorKind::Runtime => return Ok(build_err(&error.message)),
+        Err(error) => return Err(error),
+    };
+
+    if evaluator.fits_type(&value, &target)? {
+        fill_missing_options(&value, &target);
+        Ok(build_ok(value))
+    } else {
+        Ok(build_err(&format!(
+            "Parsed JSON does not fit type `{target}`."
+        )))
+    }
+}
+
+fn builtin_json_stringify(
+    args: Vec<Value>,
+    _evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let json = to_json(&args[0])?;
+
+    let text = if let Value::Bool(true) = args[1] {
+        serde_json::to_string_pretty(&json)
+    } else {
+        serde_json::to_string(&json)
+    };
+
+    // Serializing a `serde_json::Value` to a string can't fail.
+    Ok(Value::String(text.unwrap().into()))
+}
+
+// The elements of an array must share a type, as in an array literal. Values sharing an array with
+// nulls are wrapped in `Option::Some`, so that they share the type of the nulls.
+fn from_json(
+    json: &Json,
+    typed: bool,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    match json {
+        Json::Null => Ok(build_none()),
+        Json::Bool(b) => Ok(Value::Bool(*b)),
+        Json::Number(num) => to_value(num),
+        Json::String(s) => Ok(Value::String(s.as_str().into())),
+        Json::Array(items) if items.is_empty() && !typed => Err(RuntimeError::new(
+            "Cannot infer the type of an empty array. Pass a type argument, as in `jsonParse::<Array<Number>>(text)`.",
+        )),
+        Json::Array(items) => {
+            let has_null = items.iter().any(Json::is_null);
+
+            let values = items
+                .iter()
+                .map(|item| match from_json(item, typed, evaluator)? {
+                    value if has_null && !item.is_null() => Ok(build_some(value)),
+                    value => Ok(value),
+                })
+                .collect::<Result<Vec<Value>, RuntimeError>>()?;
+
+            evaluator.check_collection_len(values.len())?;
+
+            // Checked against a non-null element, as a leading `None` can't have its type inferred.
+            let first = items.iter().position(|item| !item.is_null()).unwrap_or(0);
+
+            if let Some(first) = values.get(first) {
+                for value in values.iter() {
+                    evaluator.check_element(std::slice::from_ref(first), value)?;
+                }
+            }
+
+            Ok(Value::Array(Rc::new(RefCell::new(values))))
+        }
+        Json::Object(object) => {
+            let fields = object
+                .iter()
+                .map(|(key, entry)| Ok((key.clone(), from_json(entry, typed, evaluator)?)))
+                .collect::<Result<HashMap<String, Value>, RuntimeError>>()?;
+
+            Ok(Value::Object(Rc::new(RefCell::new(fields))))
+        }
+    }
+}
+
+// Optional fields of the target type may be absent from the JSON, and are filled in as `None` so
+// that they can be accessed.
+fn fill_missing_options(value: &Value, target: &TypeVariant) {
+    let (outer, inner) = match target {
+        TypeVariant::Composite(CompositeType { outer, inner, .. }) => (outer, inner.as_slice()),
+        TypeVariant::Type(outer) => (outer, &[][..]),
+    };
+
+    match (value, outer) {
+        (Value::Object(fields), NalaType::Struct(expected_fields)) => {
+            let mut fields = fields.borrow_mut();
+
+            for expected_field in expected_fields.iter() {
+                match fields.get(&expected_field.ident) {
+                    Some(field) => fill_missing_options(field, &expected_field.value_type),
+                    None => {
+                        fields.insert(expected_field.ident.clone(), build_none());
+                    }
+                }
+            }
+        }
+        (Value::Array(items), NalaType::PrimitiveType(PrimitiveType::Array)) => {
+            for item in items.borrow().iter() {
+                fill_missing_options(item, &inner[0]);
+            }
+        }
+        (
+            Value::Variant(EnumVariantValue {
+                data: Some(data), ..
+            }),
+            NalaType::Enum(_, _),
+        ) if is_option(target) => {
+            fill_missing_options(data, &inner[0]);
+        }
+        _ => (),
+    }
+}
+
+fn to_json(value: &Value) -> Result<Json, RuntimeError> {
+    match value {
+        Value::Array(items) => items.borrow().iter().map(to_json).collect(),
+        Value::Bool(b) => Ok(Json::Bool(*b)),
+        Value::Map(map) => {
+            let mut object = JsonMap::new();
+
+            for (key, entry) in map.borrow().iter() {
+                if let HashKey::String(key) = key {
+                    object.insert(key.to_string(), to_json(entry)?);
+                } else {
+                    return Err(RuntimeError::new(&format!(
+                        "Cannot convert `{value:?}` to JSON. Only maps with `String` keys can be converted."
+                    )));
+                }
+            }
+
+            Ok(Json::Object(object))
+        }
+        Value::Num(num) => num_to_json(*num),
+        Value::Object(fields) => fields
+            .borrow()
+            .iter()
+            .map(|(ident, value)| Ok((ident.clone(), to_json(value)?)))
+            .collect(),
+        Value::Set(set) => set
+            .borrow()
+            .iter()
+            .map(|e| to_json(&e.to_value()))
+            .collect(),
+        Value::String(s) => Ok(Json::String(s.to_string())),
+        Value::Variant(EnumVariantValue {
+            enum_ident,
+            variant_ident: _,
+            data,
+        }) if enum_ident == "Option" => match data {
+            Some(data) => to_json(data),
+            None => Ok(Json::Null),
+        },
+        value => Err(RuntimeError::new(&format!(
+            "Cannot convert `{value:?}` to JSON."
+        ))),
+    }
+}
+
+// Whole numbers are written without a fractional part, and others as they're printed, rather than
+// with the noise of widening them to a double.
+fn num_to_json(num: f32) -> Result<Json, RuntimeError> {
+    if num.fract() == 0.0 && num.abs() < i64::MAX as f32 {
+        return Ok(Json::from(num as i64));
+    }
+
+    num.to_string()
+        .parse::<f64>()
+        .ok()
+        .and_then(JsonNumber::from_f64)
+        .map(Json::Number)
+        .ok_or_else(|| RuntimeError::new(&format!("Cannot convert `{num}` to JSON.")))
+}
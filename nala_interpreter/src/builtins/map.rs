@@ -0,0 +1,114 @@
+use std::cell::RefCell;
+
+use indexmap::IndexMap;
+
+use crate::{
+    errors::RuntimeError,
+    evaluator::Evaluator,
+    interpreter::{maps::map_entries, sets::set_elements},
+    resolved::{
+        func_value::{FuncValue, Param},
+        hash_key::HashKey,
+        value::Value,
+    },
+};
+
+use super::util::*;
+
+// Keys and values are returned in the order their entries were first inserted.
+
+pub fn get_entries_block() -> FuncValue {
+    build_block(
+        vec![map_param()],
+        array_type(struct_type(vec![
+            ("key", generic_type("K")),
+            ("value", generic_type("V")),
+        ])),
+        builtin_entries,
+    )
+}
+
+// Takes either a map or a set, so its parameters are left generic and checked when called.
+pub fn get_has_block() -> FuncValue {
+    build_block(
+        vec![
+            param("collection", generic_type("T")),
+            param("key", generic_type("K")),
+        ],
+        bool_type(),
+        builtin_has,
+    )
+}
+
+pub fn get_keys_block() -> FuncValue {
+    build_block(
+        vec![map_param()],
+        array_type(generic_type("K")),
+        builtin_keys,
+    )
+}
+
+// Like `has`, takes either a map or a set. The values of a set are its elements.
+pub fn get_values_block() -> FuncValue {
+    build_block(
+        vec![param("collection", generic_type("T"))],
+        array_type(generic_type("V")),
+        builtin_values,
+    )
+}
+
+fn builtin_entries(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let entries = map_entries(&get_map(&args[0]));
+    evaluator.build_array(entries)
+}
+
+fn builtin_has(args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let key = HashKey::from_value(&args[1])?;
+
+    match &args[0] {
+        Value::Map(map) => Ok(Value::Bool(map.borrow().contains_key(&key))),
+        Value::Set(set) => Ok(Value::Bool(set.borrow().contains(&key))),
+        collection => Err(not_map_or_set_error(collection)),
+    }
+}
+
+fn builtin_keys(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let keys = get_map(&args[0])
+        .borrow()
+        .keys()
+        .map(|key| key.to_value())
+        .collect();
+
+    evaluator.build_array(keys)
+}
+
+fn builtin_values(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let values = match &args[0] {
+        Value::Map(map) => map.borrow().values().cloned().collect(),
+        Value::Set(set) => set_elements(set),
+        collection => Err(not_map_or_set_error(collection))?,
+    };
+
+    evaluator.build_array(values)
+}
+
+// Removes the entry for `key`, if there is one, keeping the order of the remaining entries.
+pub(super) fn remove_entry(
+    map: &RefCell<IndexMap<HashKey, Value>>,
+    key: &Value,
+) -> Result<Value, RuntimeError> {
+    let key = HashKey::from_value(key)?;
+
+    Ok(match map.borrow_mut().shift_remove(&key) {
+        Some(value) => build_some(value),
+        None => build_none(),
+    })
+}
+
+fn not_map_or_set_error(collection: &Value) -> RuntimeError {
+    RuntimeError::new(&format!("Expected a Map or a Set, but got `{collection}`."))
+}
+
+fn map_param() -> Param {
+    param("map", map_type(generic_type("K"), generic_type("V")))
+}
@@ -1,13 +1,14 @@
-use std::collections::HashMap;
+use std::{rc::Rc, sync::Arc};
 
 use crate::{
     ast::{types::primitive_type::PrimitiveType, *},
     errors::RuntimeError,
-    io_context::IoContext,
+    evaluator::Evaluator,
     resolved::{
         func_value::{FuncValue, Param},
         value::Value,
     },
+    scopes::ScopeRef,
     types::{nala_type::NalaType, type_variant::TypeVariant},
 };
 
@@ -23,16 +24,13 @@ pub fn get_floor_block() -> FuncValue {
         params: vec![num_param],
         return_type,
         type_param: None,
-        closure_scope: 0,
-        block: Box::new(FuncVariant::Builtin(builtin_floor)),
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(builtin_floor))),
     }
 }
 
-fn builtin_floor(
-    args: HashMap<String, Value>,
-    _context: &mut dyn IoContext,
-) -> Result<Value, RuntimeError> {
-    let num = args.get("num").unwrap();
+fn builtin_floor(args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let num = &args[0];
 
     if let Value::Num(num) = num {
         Ok(Value::Num(num.floor()))
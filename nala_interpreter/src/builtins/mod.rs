@@ -1,33 +1,195 @@
 mod array;
+mod assert;
+mod errors;
+mod fs;
 mod http;
 mod io;
+mod json;
+mod map;
 mod math;
-mod util;
+mod set;
+mod string;
+mod system;
+pub mod util;
 mod void;
 
-use std::collections::HashMap;
+use std::{cell::Cell, rc::Rc, sync::Arc};
 
+use crate::ast::FuncVariant;
+use crate::capabilities::{Capabilities, Capability};
+use crate::errors::ErrorKind;
 use crate::resolved::func_value::FuncValue;
 use crate::resolved::value::Value;
-use crate::{errors::RuntimeError, io_context::IoContext};
+use crate::{errors::RuntimeError, evaluator::Evaluator};
 
+use self::assert::*;
+use self::errors::*;
+use self::fs::*;
 use self::http::*;
 use self::io::*;
+use self::json::*;
+use self::map::*;
 use self::math::*;
+use self::set::*;
+use self::string::*;
+use self::system::*;
 use self::{array::*, void::get_void_block};
 
-pub type BuiltinFunc =
-    fn(HashMap<String, Value>, &mut dyn IoContext) -> Result<Value, RuntimeError>;
+// Builtins are reference counted closures rather than plain function pointers so that host
+// applications can register functions which capture their own state. Arguments are passed in the
+// order of the function's parameters.
+pub type BuiltinFunc = Arc<dyn Fn(Vec<Value>, &mut dyn Evaluator) -> Result<Value, RuntimeError>>;
 
-pub fn get_builtins() -> Vec<(String, FuncValue)> {
+// Every builtin function, along with the capability it needs, if any.
+pub fn get_builtins() -> Vec<(String, FuncValue, Option<Capability>)> {
     vec![
-        (String::from("floor"), get_floor_block()),
-        (String::from("http"), get_http_block()),
-        (String::from("len"), get_len_block()),
-        (String::from("print"), get_print_block()),
-        (String::from("read"), get_read_block()),
-        (String::from("readnum"), get_readnum_block()),
-        (String::from("slice"), get_slice_block()),
-        (String::from("void"), get_void_block()),
+        (String::from("all"), get_all_block(), None),
+        (String::from("any"), get_any_block(), None),
+        (
+            String::from("appendFile"),
+            get_append_file_block(),
+            Some(Capability::Write),
+        ),
+        (String::from("args"), get_args_block(), None),
+        (String::from("assert"), get_assert_block(), None),
+        (String::from("assertEq"), get_assert_eq_block(), None),
+        (String::from("chars"), get_chars_block(), None),
+        (String::from("concat"), get_concat_block(), None),
+        (String::from("contains"), get_contains_block(), None),
+        (String::from("difference"), get_difference_block(), None),
+        (String::from("endsWith"), get_ends_with_block(), None),
+        (String::from("entries"), get_entries_block(), None),
+        (String::from("enumerate"), get_enumerate_block(), None),
+        (String::from("env"), get_env_block(), Some(Capability::Env)),
+        (
+            String::from("exists"),
+            get_exists_block(),
+            Some(Capability::Read),
+        ),
+        (String::from("filter"), get_filter_block(), None),
+        (String::from("find"), get_find_block(), None),
+        (String::from("flatten"), get_flatten_block(), None),
+        (String::from("floor"), get_floor_block(), None),
+        (String::from("has"), get_has_block(), None),
+        (
+            String::from("http"),
+            get_http_block(),
+            Some(Capability::Net),
+        ),
+        (String::from("indexOf"), get_index_of_block(), None),
+        (String::from("insert"), get_insert_block(), None),
+        (String::from("intersection"), get_intersection_block(), None),
+        (String::from("join"), get_join_block(), None),
+        (String::from("jsonParse"), get_json_parse_block(), None),
+        (
+            String::from("jsonStringify"),
+            get_json_stringify_block(),
+            None,
+        ),
+        (String::from("keys"), get_keys_block(), None),
+        (String::from("len"), get_len_block(), None),
+        (
+            String::from("listDir"),
+            get_list_dir_block(),
+            Some(Capability::Read),
+        ),
+        (String::from("lower"), get_lower_block(), None),
+        (
+            String::from("makeDir"),
+            get_make_dir_block(),
+            Some(Capability::Write),
+        ),
+        (String::from("map"), get_map_block(), None),
+        (
+            String::from("now"),
+            get_now_block(),
+            Some(Capability::Clock),
+        ),
+        (String::from("panic"), get_panic_block(), None),
+        (String::from("pop"), get_pop_block(), None),
+        (String::from("print"), get_print_block(), None),
+        (String::from("push"), get_push_block(), None),
+        (
+            String::from("random"),
+            get_random_block(),
+            Some(Capability::Random),
+        ),
+        (String::from("range"), get_range_block(), None),
+        (
+            String::from("read"),
+            get_read_block(),
+            Some(Capability::Stdin),
+        ),
+        (
+            String::from("readFile"),
+            get_read_file_block(),
+            Some(Capability::Read),
+        ),
+        (
+            String::from("readLines"),
+            get_read_lines_block(),
+            Some(Capability::Read),
+        ),
+        (
+            String::from("readnum"),
+            get_readnum_block(),
+            Some(Capability::Stdin),
+        ),
+        (String::from("reduce"), get_reduce_block(), None),
+        (String::from("remove"), get_remove_block(), None),
+        (
+            String::from("removeFile"),
+            get_remove_file_block(),
+            Some(Capability::Write),
+        ),
+        (String::from("replace"), get_replace_block(), None),
+        (String::from("reverse"), get_reverse_block(), None),
+        (String::from("slice"), get_slice_block(), None),
+        (String::from("sortBy"), get_sort_by_block(), None),
+        (String::from("split"), get_split_block(), None),
+        (String::from("startsWith"), get_starts_with_block(), None),
+        (String::from("substring"), get_substring_block(), None),
+        (String::from("toSet"), get_to_set_block(), None),
+        (String::from("trim"), get_trim_block(), None),
+        (String::from("union"), get_union_block(), None),
+        (String::from("upper"), get_upper_block(), None),
+        (String::from("values"), get_values_block(), None),
+        (String::from("void"), get_void_block(), None),
+        (
+            String::from("writeFile"),
+            get_write_file_block(),
+            Some(Capability::Write),
+        ),
+        (String::from("zip"), get_zip_block(), None),
     ]
 }
+
+// Wraps a builtin so that calling it raises a permission error unless `allowed` holds `capability`
+// at the time. The builtin stays in scope either way, so that scripts can catch the error.
+pub fn gate_builtin(
+    ident: String,
+    func: FuncValue,
+    capability: Capability,
+    allowed: Rc<Cell<Capabilities>>,
+) -> FuncValue {
+    let inner = match func.block.as_ref() {
+        FuncVariant::Builtin(inner) => inner.clone(),
+        _ => return func,
+    };
+
+    let gated: BuiltinFunc = Arc::new(move |args, evaluator| {
+        if allowed.get().allows(capability) {
+            inner(args, evaluator)
+        } else {
+            Err(RuntimeError::with_kind(
+                ErrorKind::Permission,
+                &format!("Calling `{ident}` requires the `{capability}` capability."),
+            ))
+        }
+    });
+
+    FuncValue {
+        block: Rc::new(FuncVariant::Builtin(gated)),
+        ..func
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    errors::RuntimeError,
+    evaluator::Evaluator,
+    interpreter::sets::set_elements,
+    resolved::{
+        func_value::{FuncValue, Param},
+        hash_key::HashKey,
+        value::Value,
+    },
+};
+
+use super::util::*;
+
+// These return new sets, leaving their arguments alone. Elements are in the order of the first set
+// they were found in. Sets are added to and removed from with `push` and `remove`, as arrays are.
+
+pub fn get_difference_block() -> FuncValue {
+    build_block(
+        vec![set_param("first"), set_param("second")],
+        set_type(generic_type("T")),
+        builtin_difference,
+    )
+}
+
+pub fn get_intersection_block() -> FuncValue {
+    build_block(
+        vec![set_param("first"), set_param("second")],
+        set_type(generic_type("T")),
+        builtin_intersection,
+    )
+}
+
+pub fn get_to_set_block() -> FuncValue {
+    build_block(
+        vec![param("array", array_type(generic_type("T")))],
+        set_type(generic_type("T")),
+        builtin_to_set,
+    )
+}
+
+pub fn get_union_block() -> FuncValue {
+    build_block(
+        vec![set_param("first"), set_param("second")],
+        set_type(generic_type("T")),
+        builtin_union,
+    )
+}
+
+fn builtin_difference(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let second = get_set(&args[1]);
+    let second = second.borrow();
+
+    let kept = get_set(&args[0])
+        .borrow()
+        .iter()
+        .filter(|elem| !second.contains(*elem))
+        .map(HashKey::to_value)
+        .collect();
+
+    evaluator.build_set(kept)
+}
+
+fn builtin_intersection(
+    args: Vec<Value>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let second = get_set(&args[1]);
+    let second = second.borrow();
+
+    let kept = get_set(&args[0])
+        .borrow()
+        .iter()
+        .filter(|elem| second.contains(*elem))
+        .map(HashKey::to_value)
+        .collect();
+
+    evaluator.build_set(kept)
+}
+
+fn builtin_to_set(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let items = get_array(&args[0]).borrow().clone();
+    evaluator.build_set(items)
+}
+
+fn builtin_union(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let mut elems = set_elements(&get_set(&args[0]));
+    elems.extend(set_elements(&get_set(&args[1])));
+
+    evaluator.build_set(elems)
+}
+
+fn set_param(ident: &str) -> Param {
+    param(ident, set_type(generic_type("T")))
+}
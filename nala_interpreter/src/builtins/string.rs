@@ -0,0 +1,245 @@
+use std::{cell::RefCell, rc::Rc};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    errors::RuntimeError,
+    evaluator::Evaluator,
+    resolved::{func_value::FuncValue, value::Value},
+};
+
+use super::util::*;
+
+// Strings are measured and indexed in grapheme clusters, which are what readers see as characters,
+// so that an accented letter or an emoji counts once however many code points make it up.
+
+pub fn get_chars_block() -> FuncValue {
+    build_block(
+        vec![param("text", string_type())],
+        array_type(string_type()),
+        builtin_chars,
+    )
+}
+
+pub fn get_ends_with_block() -> FuncValue {
+    build_block(
+        vec![param("text", string_type()), param("suffix", string_type())],
+        bool_type(),
+        builtin_ends_with,
+    )
+}
+
+pub fn get_join_block() -> FuncValue {
+    build_block(
+        vec![
+            param("parts", array_type(string_type())),
+            param("separator", string_type()),
+        ],
+        string_type(),
+        builtin_join,
+    )
+}
+
+pub fn get_lower_block() -> FuncValue {
+    build_block(
+        vec![param("text", string_type())],
+        string_type(),
+        builtin_lower,
+    )
+}
+
+pub fn get_replace_block() -> FuncValue {
+    build_block(
+        vec![
+            param("text", string_type()),
+            param("pattern", string_type()),
+            param("replacement", string_type()),
+        ],
+        string_type(),
+        builtin_replace,
+    )
+}
+
+pub fn get_split_block() -> FuncValue {
+    build_block(
+        vec![
+            param("text", string_type()),
+            param("separator", string_type()),
+        ],
+        array_type(string_type()),
+        builtin_split,
+    )
+}
+
+pub fn get_starts_with_block() -> FuncValue {
+    build_block(
+        vec![param("text", string_type()), param("prefix", string_type())],
+        bool_type(),
+        builtin_starts_with,
+    )
+}
+
+pub fn get_substring_block() -> FuncValue {
+    build_block(
+        vec![
+            param("text", string_type()),
+            param("start", number_type()),
+            param("end", number_type()),
+        ],
+        string_type(),
+        builtin_substring,
+    )
+}
+
+pub fn get_trim_block() -> FuncValue {
+    build_block(
+        vec![param("text", string_type())],
+        string_type(),
+        builtin_trim,
+    )
+}
+
+pub fn get_upper_block() -> FuncValue {
+    build_block(
+        vec![param("text", string_type())],
+        string_type(),
+        builtin_upper,
+    )
+}
+
+fn builtin_chars(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let text = get_string(&args[0]);
+    build_string_array(text.graphemes(true), evaluator)
+}
+
+fn builtin_ends_with(
+    args: Vec<Value>,
+    _evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let text = get_string(&args[0]);
+    let suffix = get_string(&args[1]);
+    Ok(Value::Bool(text.ends_with(suffix)))
+}
+
+fn builtin_join(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let separator = get_string(&args[1]);
+
+    let parts = if let Value::Array(parts) = &args[0] {
+        parts.borrow()
+    } else {
+        unreachable!()
+    };
+
+    let parts: Vec<&str> = parts.iter().map(get_string).collect();
+
+    // Checked before joining, so that the limit also bounds what's allocated.
+    let len = parts.iter().map(|part| part.len()).sum::<usize>()
+        + separator.len() * parts.len().saturating_sub(1);
+
+    evaluator.check_string_len(len)?;
+    Ok(Value::String(parts.join(separator).into()))
+}
+
+fn builtin_lower(args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let text = get_string(&args[0]);
+    Ok(Value::String(text.to_lowercase().into()))
+}
+
+fn builtin_replace(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let text = get_string(&args[0]);
+    let pattern = get_string(&args[1]);
+    let replacement = get_string(&args[2]);
+
+    if pattern.is_empty() {
+        return Err(RuntimeError::new("Cannot replace an empty pattern."));
+    }
+
+    // Checked before replacing, so that the limit also bounds what's allocated.
+    let matches = text.matches(pattern).count();
+    evaluator
+        .check_string_len(text.len() - matches * pattern.len() + matches * replacement.len())?;
+
+    Ok(Value::String(text.replace(pattern, replacement).into()))
+}
+
+fn builtin_split(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let text = get_string(&args[0]);
+    let separator = get_string(&args[1]);
+
+    // Splitting on nothing splits between every character.
+    if separator.is_empty() {
+        build_string_array(text.graphemes(true), evaluator)
+    } else {
+        build_string_array(text.split(separator), evaluator)
+    }
+}
+
+fn builtin_starts_with(
+    args: Vec<Value>,
+    _evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let text = get_string(&args[0]);
+    let prefix = get_string(&args[1]);
+    Ok(Value::Bool(text.starts_with(prefix)))
+}
+
+fn builtin_substring(
+    args: Vec<Value>,
+    _evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let text = get_string(&args[0]);
+    let start = get_index(&args[1], "start")?;
+    let end = get_index(&args[2], "end")?;
+
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+
+    if start > end || end > graphemes.len() {
+        return Err(RuntimeError::new(&format!(
+            "Substring range {start}..{end} is out of bounds for a string of length {}.",
+            graphemes.len()
+        )));
+    }
+
+    Ok(Value::String(graphemes[start..end].concat().into()))
+}
+
+fn builtin_trim(args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let text = get_string(&args[0]);
+    Ok(Value::String(text.trim().into()))
+}
+
+fn builtin_upper(args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let text = get_string(&args[0]);
+    Ok(Value::String(text.to_uppercase().into()))
+}
+
+// The number of characters in `text`.
+pub fn grapheme_len(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+// Finds the first match of `part` in `text` that starts on a character boundary, returning its
+// index in characters. Matches inside a character, such as of an accent combined with a letter,
+// don't count.
+pub fn find_grapheme_index(text: &str, part: &str) -> Option<usize> {
+    let boundaries: Vec<usize> = text
+        .grapheme_indices(true)
+        .map(|(offset, _)| offset)
+        .chain([text.len()])
+        .collect();
+
+    text.match_indices(part).find_map(|(offset, _)| {
+        let start = boundaries.binary_search(&offset).ok()?;
+        boundaries.binary_search(&(offset + part.len())).ok()?;
+        Some(start)
+    })
+}
+
+fn build_string_array<'a>(
+    strings: impl Iterator<Item = &'a str>,
+    evaluator: &mut dyn Evaluator,
+) -> Result<Value, RuntimeError> {
+    let strings: Vec<Value> = strings.map(|string| Value::String(string.into())).collect();
+    evaluator.check_collection_len(strings.len())?;
+    Ok(Value::Array(Rc::new(RefCell::new(strings))))
+}
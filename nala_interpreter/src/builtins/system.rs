@@ -0,0 +1,67 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+    errors::RuntimeError,
+    evaluator::Evaluator,
+    resolved::{func_value::FuncValue, value::Value},
+};
+
+use super::util::*;
+
+// The environment, clock and random numbers are reached through the host's `IoContext`, so that
+// tests can fake them.
+
+pub fn get_env_block() -> FuncValue {
+    build_block(
+        vec![param("name", string_type())],
+        option_type(string_type()),
+        builtin_env,
+    )
+}
+
+pub fn get_now_block() -> FuncValue {
+    build_block(
+        vec![],
+        struct_type(vec![
+            ("days", number_type()),
+            ("seconds", number_type()),
+            ("millis", number_type()),
+        ]),
+        builtin_now,
+    )
+}
+
+pub fn get_random_block() -> FuncValue {
+    build_block(vec![], number_type(), builtin_random)
+}
+
+fn builtin_env(args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    match evaluator.ctx().env_var(get_string(&args[0])) {
+        Some(value) => Ok(build_some(Value::String(value.into()))),
+        None => Ok(build_none()),
+    }
+}
+
+// A `Number` can't hold the seconds since the Unix epoch exactly, so the time is split into whole
+// days since the epoch, seconds into the day and milliseconds into the second.
+fn builtin_now(_args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    let now = evaluator.ctx().now();
+    let secs = now.as_secs();
+
+    let fields = HashMap::from([
+        (String::from("days"), Value::Num((secs / 86_400) as f32)),
+        (String::from("seconds"), Value::Num((secs % 86_400) as f32)),
+        (
+            String::from("millis"),
+            Value::Num(now.subsec_millis() as f32),
+        ),
+    ]);
+
+    Ok(Value::Object(Rc::new(RefCell::new(fields))))
+}
+
+fn builtin_random(_args: Vec<Value>, evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
+    // Narrowing to a `Number` could round up to 1, which is outside the range.
+    let num = (evaluator.ctx().random() as f32).min(1.0 - f32::EPSILON / 2.0);
+    Ok(Value::Num(num))
+}
@@ -1,4 +1,21 @@
-use crate::resolved::value::{EnumVariantValue, Value};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+    ast::{types::primitive_type::PrimitiveType, FuncVariant},
+    errors::RuntimeError,
+    evaluator::Evaluator,
+    resolved::{
+        enum_variants::EnumVariant,
+        func_value::{FuncValue, Param},
+        hash_key::HashKey,
+        struct_field::StructField,
+        value::{EnumVariantValue, Value},
+    },
+    scopes::ScopeRef,
+    types::{composite_type::CompositeType, nala_type::NalaType, type_variant::TypeVariant},
+};
 
 pub fn build_some(data: Value) -> Value {
     let variant = EnumVariantValue {
@@ -19,3 +36,196 @@ pub fn build_none() -> Value {
 
     Value::Variant(variant)
 }
+
+// The type `Option<T>` for a concrete `inner`, as declared by `get_builtin_enums`.
+pub fn option_type(inner: TypeVariant) -> TypeVariant {
+    let variants = vec![
+        EnumVariant::Data(String::from("Some"), inner.clone()),
+        EnumVariant::Empty(String::from("None")),
+    ];
+
+    TypeVariant::Composite(CompositeType {
+        outer: NalaType::Enum(String::from("Option"), variants),
+        inner: vec![inner],
+        generic_type_param: None,
+    })
+}
+
+pub fn build_ok(data: Value) -> Value {
+    let variant = EnumVariantValue {
+        enum_ident: String::from("Result"),
+        variant_ident: String::from("Ok"),
+        data: Some(Box::new(data)),
+    };
+
+    Value::Variant(variant)
+}
+
+pub fn build_err(message: &str) -> Value {
+    let variant = EnumVariantValue {
+        enum_ident: String::from("Result"),
+        variant_ident: String::from("Err"),
+        data: Some(Box::new(Value::String(message.into()))),
+    };
+
+    Value::Variant(variant)
+}
+
+// The type `Result<T>` for `inner`, as declared by `get_builtin_enums`.
+pub fn result_type(inner: TypeVariant) -> TypeVariant {
+    let variants = vec![
+        EnumVariant::Data(String::from("Ok"), inner.clone()),
+        EnumVariant::Data(String::from("Err"), string_type()),
+    ];
+
+    TypeVariant::Composite(CompositeType {
+        outer: NalaType::Enum(String::from("Result"), variants),
+        generic_type_param: inner.find_generic_type_param(),
+        inner: vec![inner],
+    })
+}
+
+pub fn get_string(value: &Value) -> &str {
+    if let Value::String(string) = value {
+        string
+    } else {
+        unreachable!()
+    }
+}
+
+// Gets a number argument named `ident` for use as an index.
+pub fn get_index(value: &Value, ident: &str) -> Result<usize, RuntimeError> {
+    let num = if let Value::Num(num) = value {
+        *num
+    } else {
+        unreachable!()
+    };
+
+    if num < 0.0 || num.fract() != 0.0 {
+        Err(RuntimeError::new(&format!(
+            "Expected `{ident}` to be a whole number of at least 0, but got {num}."
+        )))
+    } else {
+        Ok(num as usize)
+    }
+}
+
+pub fn build_block(
+    params: Vec<Param>,
+    return_type: TypeVariant,
+    func: fn(Vec<Value>, &mut dyn Evaluator) -> Result<Value, RuntimeError>,
+) -> FuncValue {
+    FuncValue {
+        params,
+        return_type,
+        type_param: None,
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(func))),
+    }
+}
+
+pub fn param(ident: &str, param_type: TypeVariant) -> Param {
+    Param {
+        ident: String::from(ident),
+        param_type,
+    }
+}
+
+pub fn bool_type() -> TypeVariant {
+    TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Bool))
+}
+
+pub fn number_type() -> TypeVariant {
+    TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Number))
+}
+
+pub fn string_type() -> TypeVariant {
+    TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::String))
+}
+
+pub fn void_type() -> TypeVariant {
+    TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Void))
+}
+
+pub fn generic_type(ident: &str) -> TypeVariant {
+    TypeVariant::generic(String::from(ident))
+}
+
+pub fn array_type(inner: TypeVariant) -> TypeVariant {
+    TypeVariant::Composite(CompositeType {
+        outer: NalaType::PrimitiveType(PrimitiveType::Array),
+        generic_type_param: inner.find_generic_type_param(),
+        inner: vec![inner],
+    })
+}
+
+pub fn map_type(key: TypeVariant, value: TypeVariant) -> TypeVariant {
+    TypeVariant::Composite(CompositeType {
+        outer: NalaType::PrimitiveType(PrimitiveType::Map),
+        generic_type_param: key
+            .find_generic_type_param()
+            .or_else(|| value.find_generic_type_param()),
+        inner: vec![key, value],
+    })
+}
+
+pub fn set_type(inner: TypeVariant) -> TypeVariant {
+    TypeVariant::Composite(CompositeType {
+        outer: NalaType::PrimitiveType(PrimitiveType::Set),
+        generic_type_param: inner.find_generic_type_param(),
+        inner: vec![inner],
+    })
+}
+
+// The type of a function returning `return_type`. Function types only constrain what's returned.
+pub fn func_type(return_type: TypeVariant) -> TypeVariant {
+    TypeVariant::Composite(CompositeType {
+        outer: NalaType::PrimitiveType(PrimitiveType::Func),
+        inner: vec![return_type],
+        generic_type_param: None,
+    })
+}
+
+pub fn struct_type(fields: Vec<(&str, TypeVariant)>) -> TypeVariant {
+    let fields = fields
+        .into_iter()
+        .map(|(ident, value_type)| StructField {
+            ident: String::from(ident),
+            value_type,
+        })
+        .collect();
+
+    TypeVariant::Type(NalaType::Struct(fields))
+}
+
+pub fn get_array(value: &Value) -> Rc<RefCell<Vec<Value>>> {
+    if let Value::Array(array) = value {
+        Rc::clone(array)
+    } else {
+        unreachable!()
+    }
+}
+
+pub fn get_map(value: &Value) -> Rc<RefCell<IndexMap<HashKey, Value>>> {
+    if let Value::Map(map) = value {
+        Rc::clone(map)
+    } else {
+        unreachable!()
+    }
+}
+
+pub fn get_set(value: &Value) -> Rc<RefCell<IndexSet<HashKey>>> {
+    if let Value::Set(set) = value {
+        Rc::clone(set)
+    } else {
+        unreachable!()
+    }
+}
+
+pub fn get_func(value: &Value) -> Rc<FuncValue> {
+    if let Value::Func(func) = value {
+        Rc::clone(func)
+    } else {
+        unreachable!()
+    }
+}
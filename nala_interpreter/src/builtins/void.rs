@@ -1,13 +1,14 @@
-use std::collections::HashMap;
+use std::{rc::Rc, sync::Arc};
 
 use crate::{
     ast::{types::primitive_type::PrimitiveType, FuncVariant},
     errors::RuntimeError,
-    io_context::IoContext,
+    evaluator::Evaluator,
     resolved::{
         func_value::{FuncValue, Param},
         value::Value,
     },
+    scopes::ScopeRef,
     types::{nala_type::NalaType, type_variant::TypeVariant},
 };
 
@@ -23,14 +24,11 @@ pub fn get_void_block() -> FuncValue {
         params: vec![param],
         return_type,
         type_param: None,
-        closure_scope: 0,
-        block: Box::new(FuncVariant::Builtin(builtin_void)),
+        closure_scope: ScopeRef::default(),
+        block: Rc::new(FuncVariant::Builtin(Arc::new(builtin_void))),
     }
 }
 
-fn builtin_void(
-    _args: HashMap<String, Value>,
-    _ctx: &mut dyn IoContext,
-) -> Result<Value, RuntimeError> {
+fn builtin_void(_args: Vec<Value>, _evaluator: &mut dyn Evaluator) -> Result<Value, RuntimeError> {
     Ok(Value::Void)
 }
@@ -0,0 +1,84 @@
+use std::fmt;
+
+// Something outside the interpreter that builtins can reach, which hosts may deny to scripts they
+// don't trust.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Capability {
+    Net,
+    Read,
+    Write,
+    Stdin,
+    Env,
+    Clock,
+    Random,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 7] = [
+        Capability::Net,
+        Capability::Read,
+        Capability::Write,
+        Capability::Stdin,
+        Capability::Env,
+        Capability::Clock,
+        Capability::Random,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Capability::Net => "net",
+            Capability::Read => "read",
+            Capability::Write => "write",
+            Capability::Stdin => "stdin",
+            Capability::Env => "env",
+            Capability::Clock => "clock",
+            Capability::Random => "random",
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// The set of capabilities a program is allowed. Every capability is allowed by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capabilities(u8);
+
+impl Capabilities {
+    pub fn all() -> Self {
+        Capability::ALL
+            .iter()
+            .fold(Self::none(), |capabilities, capability| {
+                capabilities.with(*capability)
+            })
+    }
+
+    pub fn none() -> Self {
+        Capabilities(0)
+    }
+
+    pub fn with(self, capability: Capability) -> Self {
+        Capabilities(self.0 | capability.bit())
+    }
+
+    pub fn without(self, capability: Capability) -> Self {
+        Capabilities(self.0 & !capability.bit())
+    }
+
+    pub fn allows(self, capability: Capability) -> bool {
+        self.0 & capability.bit() != 0
+    }
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::all()
+    }
+}
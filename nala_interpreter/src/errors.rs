@@ -1,12 +1,80 @@
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+
+use crate::resolved::value::Value;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    Assertion,
+    // A resource limit set by the host was exceeded, see `Limits`.
+    Limit,
+    Panic,
+    Parse,
+    // A builtin needed a capability the host didn't allow, see `Capabilities`.
+    Permission,
+    Runtime,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind_name = match self {
+            ErrorKind::Assertion => "AssertionError",
+            ErrorKind::Limit => "LimitError",
+            ErrorKind::Panic => "Panic",
+            ErrorKind::Parse => "ParseError",
+            ErrorKind::Permission => "PermissionError",
+            ErrorKind::Runtime => "RuntimeError",
+        };
+
+        write!(f, "{}", kind_name)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RuntimeError {
     pub message: String,
+    pub kind: ErrorKind,
 }
 
 impl RuntimeError {
     pub fn new(message: &str) -> RuntimeError {
+        RuntimeError::with_kind(ErrorKind::Runtime, message)
+    }
+
+    pub fn with_kind(kind: ErrorKind, message: &str) -> RuntimeError {
         RuntimeError {
             message: message.to_string(),
+            kind,
         }
     }
+
+    // Whether a `catch` block may handle the error. Scripts can't recover from exceeding their
+    // limits, as that would let them run on regardless.
+    pub fn is_catchable(&self) -> bool {
+        self.kind != ErrorKind::Limit
+    }
+
+    // Builds the value bound to the identifier of a `catch` block. This matches the shape of the
+    // builtin `Error` struct.
+    pub fn to_value(&self) -> Value {
+        let fields = HashMap::from([
+            (
+                String::from("kind"),
+                Value::String(self.kind.to_string().into()),
+            ),
+            (
+                String::from("message"),
+                Value::String(self.message.as_str().into()),
+            ),
+        ]);
+
+        Value::Object(Rc::new(RefCell::new(fields)))
+    }
 }
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for RuntimeError {}
@@ -0,0 +1,50 @@
+use std::rc::Rc;
+
+use indexmap::IndexSet;
+
+use crate::{
+    errors::RuntimeError,
+    io_context::IoContext,
+    resolved::{func_value::FuncValue, hash_key::HashKey, value::Value},
+    types::type_variant::TypeVariant,
+};
+
+// A handle on the engine running a builtin, through which the builtin can reach the host and call
+// back into the script, such as to apply a function it was passed.
+pub trait Evaluator {
+    fn ctx(&mut self) -> &mut dyn IoContext;
+
+    // Calls `func` as a script would, checking its arguments and return value and counting the call
+    // against the program's limits.
+    fn call(&mut self, func: Rc<FuncValue>, args: Vec<Value>) -> Result<Value, RuntimeError>;
+
+    // Builds an array the way an array literal does, checking that `values` share a type.
+    fn build_array(&mut self, values: Vec<Value>) -> Result<Value, RuntimeError>;
+
+    // Checks that a collection of `len` elements fits the program's limits.
+    fn check_collection_len(&mut self, len: usize) -> Result<(), RuntimeError>;
+
+    // Checks that a string of `len` bytes fits the program's limits.
+    fn check_string_len(&mut self, len: usize) -> Result<(), RuntimeError>;
+
+    // Checks that `value` can be added to an array holding `items`.
+    fn check_element(&mut self, items: &[Value], value: &Value) -> Result<(), RuntimeError>;
+
+    // Builds a set the way a set literal does, checking that `values` share a type.
+    fn build_set(&mut self, values: Vec<Value>) -> Result<Value, RuntimeError>;
+
+    // Checks that `elem` can be added to `set`.
+    fn check_set_element(
+        &mut self,
+        set: &IndexSet<HashKey>,
+        elem: &HashKey,
+    ) -> Result<(), RuntimeError>;
+
+    // Gets the type bound to `ident` in the scope of the call, such as the type argument passed to a
+    // generic builtin.
+    fn get_type(&mut self, ident: &str) -> Result<TypeVariant, RuntimeError>;
+
+    // Checks whether `value` fits `expected`, as a function's arguments are checked against its
+    // parameters.
+    fn fits_type(&mut self, value: &Value, expected: &TypeVariant) -> Result<bool, RuntimeError>;
+}
@@ -0,0 +1,471 @@
+use crate::{
+    ast::{
+        arrays::Array,
+        branching::{IfElseChain, Match, TryCatch},
+        funcs::{Call, FuncDeclare, ParamDeclare},
+        maps::Map,
+        math::{Addition, Multiplication},
+        objects::Object,
+        patterns::Pattern,
+        sets::Set,
+        terms::{Literal, Term},
+        types::{
+            type_literal_variant::TypeVariantLiteral, variant_declare::VariantDeclare,
+            StructLiteralField, StructLiteralFieldValue,
+        },
+        *,
+    },
+    parser::parse_code,
+};
+
+const INDENT: &str = "    ";
+
+// Array and object literals longer than this are split across lines, one item per line.
+const MAX_INLINE_LIST_WIDTH: usize = 60;
+
+pub fn format_code(code: &str) -> Result<String, String> {
+    let program = parse_code(code.to_owned())?;
+    Ok(format_program(&program))
+}
+
+// Renders a program back to source in the canonical style. Blank lines and comments are preserved,
+// as long as the program was parsed from source rather than built by hand.
+pub fn format_program(program: &Program) -> String {
+    let mut formatter = Formatter {
+        out: String::new(),
+        indent: 0,
+    };
+
+    match program {
+        Program::Block(lines) => {
+            formatter.block(lines);
+            formatter.out.push('\n');
+        }
+        Program::Lines(lines) => formatter.lines(lines),
+    }
+
+    formatter.out
+}
+
+struct Formatter {
+    out: String,
+    indent: usize,
+}
+
+impl Formatter {
+    fn lines(&mut self, lines: &[Line]) {
+        for line in lines {
+            self.line(line);
+        }
+    }
+
+    fn line(&mut self, line: &Line) {
+        match line {
+            Line::Blank => self.out.push('\n'),
+            Line::Comment(text, true) if self.out.ends_with('\n') => {
+                self.out.pop();
+                self.out.push(' ');
+                self.out.push_str(text);
+                self.out.push('\n');
+            }
+            line => {
+                self.out.push_str(&self.indentation());
+                self.line_body(line);
+                self.out.push('\n');
+            }
+        }
+    }
+
+    fn line_body(&mut self, line: &Line) {
+        match line {
+            Line::Assign(place, expr) => {
+                let code = format!("{} = {};", self.place(place), self.expr(expr));
+                self.out.push_str(&code);
+            }
+            Line::Blank => (),
+            Line::Break(expr) => {
+                let code = format!("break({});", self.expr(expr));
+                self.out.push_str(&code);
+            }
+            Line::Comment(text, _) => self.out.push_str(text),
+            Line::Declare(ident, expr, declared_type, is_mutable, _) => {
+                let keyword = if *is_mutable { "mut" } else { "const" };
+
+                let declared_type = declared_type
+                    .as_ref()
+                    .map(|t| format!(": {}", type_variant(t)))
+                    .unwrap_or_default();
+
+                let code = format!("{keyword} {ident}{declared_type} = {};", self.expr(expr));
+                self.out.push_str(&code);
+            }
+            Line::Enum(ident, type_param, variants) => {
+                self.enum_declare(ident, type_param, variants)
+            }
+            Line::Expr(expr) => {
+                let code = format!("{};", self.expr(expr));
+                self.out.push_str(&code);
+            }
+            Line::For(ident, expr, block) => {
+                let code = format!("for {ident} in {} ", self.expr(expr));
+                self.out.push_str(&code);
+                self.block(block);
+            }
+            Line::Func(func) => self.func_declare(func),
+            Line::IfElseChain(chain) => self.if_else_chain(chain),
+            Line::Match(the_match) => self.match_block(the_match),
+            Line::Struct(ident, type_param, fields) => {
+                self.out
+                    .push_str(&format!("struct {ident}{} ", type_params(type_param)));
+                self.struct_fields(fields);
+            }
+            Line::Test(name, block) => {
+                self.out.push_str(&format!("test '{name}' "));
+                self.block(block);
+            }
+            Line::TryCatch(try_catch) => self.try_catch(try_catch),
+            Line::Wiles(expr, block) => {
+                let code = format!("wiles ({}) ", self.expr(expr));
+                self.out.push_str(&code);
+                self.block(block);
+            }
+        }
+    }
+
+    // Writes a block starting at the current position, leaving the position after the closing brace.
+    fn block(&mut self, lines: &[Line]) {
+        self.out.push_str("{\n");
+        self.indent += 1;
+        self.lines(lines);
+        self.indent -= 1;
+        self.out.push_str(&self.indentation());
+        self.out.push('}');
+    }
+
+    fn enum_declare(
+        &mut self,
+        ident: &str,
+        type_param: &Option<String>,
+        variants: &[VariantDeclare],
+    ) {
+        self.out
+            .push_str(&format!("enum {ident}{} {{\n", type_params(type_param)));
+
+        let indentation = format!("{}{INDENT}", self.indentation());
+
+        for variant in variants {
+            let variant = match variant {
+                VariantDeclare::Empty(ident) => ident.clone(),
+                VariantDeclare::Data(ident, data_type) => {
+                    format!("{ident}({})", type_variant(data_type))
+                }
+            };
+
+            self.out.push_str(&format!("{indentation}{variant},\n"));
+        }
+
+        self.out.push_str(&self.indentation());
+        self.out.push('}');
+    }
+
+    fn func_declare(&mut self, func: &FuncDeclare) {
+        let params = func
+            .params
+            .iter()
+            .map(param)
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        self.out.push_str(&format!(
+            "func {}{}({params}): {} ",
+            func.ident,
+            type_params(&func.type_param),
+            type_variant(&func.return_type)
+        ));
+
+        match func.block.as_ref() {
+            FuncVariant::Nala(block) => self.block(block),
+            FuncVariant::Builtin(_) | FuncVariant::Compiled(_) => {
+                unreachable!("Only parsed functions have source to format.")
+            }
+        }
+    }
+
+    fn if_else_chain(&mut self, chain: &IfElseChain) {
+        let code = format!("if ({}) ", self.expr(&chain.cond));
+        self.out.push_str(&code);
+        self.block(&chain.block);
+
+        for else_if in chain.else_ifs.iter() {
+            let code = format!(" elif ({}) ", self.expr(&else_if.cond));
+            self.out.push_str(&code);
+            self.block(&else_if.block);
+        }
+
+        if let Some(else_block) = &chain.else_block {
+            self.out.push_str(" else ");
+            self.block(&else_block.block);
+        }
+    }
+
+    fn match_block(&mut self, the_match: &Match) {
+        let code = format!("match ({}) {{\n", self.expr(&the_match.expr));
+        self.out.push_str(&code);
+        self.indent += 1;
+
+        for case in the_match.cases.iter() {
+            let code = format!("{}{} => ", self.indentation(), pattern(&case.pattern));
+            self.out.push_str(&code);
+            self.block(&case.block);
+            self.out.push('\n');
+        }
+
+        self.indent -= 1;
+        self.out.push_str(&self.indentation());
+        self.out.push('}');
+    }
+
+    fn struct_fields(&mut self, fields: &[StructLiteralField]) {
+        self.out.push_str("{\n");
+        self.indent += 1;
+
+        for field in fields {
+            self.out.push_str(&self.indentation());
+            self.out.push_str(&format!("{}: ", field.ident));
+
+            match &field.value {
+                StructLiteralFieldValue::Nested(fields) => self.struct_fields(fields),
+                StructLiteralFieldValue::Type(field_type) => {
+                    self.out.push_str(&type_variant(field_type))
+                }
+            }
+
+            self.out.push_str(",\n");
+        }
+
+        self.indent -= 1;
+        self.out.push_str(&self.indentation());
+        self.out.push('}');
+    }
+
+    fn try_catch(&mut self, try_catch: &TryCatch) {
+        self.out.push_str("try ");
+        self.block(&try_catch.block);
+        self.out.push_str(&format!(" catch ({}) ", try_catch.ident));
+        self.block(&try_catch.catch_block);
+    }
+
+    fn expr(&mut self, expr: &Expr) -> String {
+        match expr {
+            Expr::Addition(addition) => self.addition(addition),
+            Expr::Eq(left, right) => format!("{} == {}", self.expr(left), self.addition(right)),
+            Expr::Gt(left, right) => format!("{} > {}", self.expr(left), self.addition(right)),
+            Expr::Lt(left, right) => format!("{} < {}", self.expr(left), self.addition(right)),
+        }
+    }
+
+    fn addition(&mut self, addition: &Addition) -> String {
+        match addition {
+            Addition::Add(left, right) => {
+                format!("{} + {}", self.addition(left), self.multiplication(right))
+            }
+            Addition::Sub(left, right) => {
+                format!("{} - {}", self.addition(left), self.multiplication(right))
+            }
+            Addition::Multiplication(multiplication) => self.multiplication(multiplication),
+        }
+    }
+
+    fn multiplication(&mut self, multiplication: &Multiplication) -> String {
+        match multiplication {
+            Multiplication::Mult(left, right) => {
+                format!("{} * {}", self.multiplication(left), term(right))
+            }
+            Multiplication::Div(left, right) => {
+                format!("{} / {}", self.multiplication(left), term(right))
+            }
+            Multiplication::Unary(Unary::Primary(primary)) => self.primary(primary),
+        }
+    }
+
+    fn primary(&mut self, primary: &Primary) -> String {
+        match primary {
+            Primary::Array(array) => self.array(array),
+            Primary::Call(call) => self.call(call),
+            Primary::EnumVariant(enum_ident, variant_ident, data) => match data {
+                Some(data) => format!("{enum_ident}::{variant_ident}({})", self.expr(data)),
+                None => format!("{enum_ident}::{variant_ident}"),
+            },
+            Primary::Literal(literal) => self::literal(literal),
+            Primary::Map(map) => self.map(map),
+            Primary::Object(object) => self.object(object),
+            Primary::Set(set) => self.set(set),
+        }
+    }
+
+    fn array(&mut self, array: &Array) -> String {
+        self.indent += 1;
+        let elems = array.elems.iter().map(|elem| self.expr(elem)).collect();
+        self.indent -= 1;
+
+        self.list(elems, "[", "]", "")
+    }
+
+    fn call(&mut self, call: &Call) -> String {
+        match call {
+            Call::Call(place, type_arg, args) => {
+                let type_arg = type_arg
+                    .as_ref()
+                    .map(|t| format!("::<{}>", type_variant(t)))
+                    .unwrap_or_default();
+
+                format!("{}{type_arg}({})", self.place(place), self.elems(args))
+            }
+            Call::PlaceExpression(place) => self.place(place),
+        }
+    }
+
+    fn elems(&mut self, elems: &[Expr]) -> String {
+        elems
+            .iter()
+            .map(|elem| self.expr(elem))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn map(&mut self, map: &Map) -> String {
+        if map.entries.is_empty() {
+            return String::from("Map {}");
+        }
+
+        self.indent += 1;
+
+        let entries = map
+            .entries
+            .iter()
+            .map(|entry| format!("{}: {}", self.expr(&entry.key), self.expr(&entry.value)))
+            .collect();
+
+        self.indent -= 1;
+
+        self.list(entries, "Map {", "}", " ")
+    }
+
+    fn set(&mut self, set: &Set) -> String {
+        if set.elems.is_empty() {
+            return String::from("Set {}");
+        }
+
+        self.indent += 1;
+        let elems = set.elems.iter().map(|elem| self.expr(elem)).collect();
+        self.indent -= 1;
+
+        self.list(elems, "Set {", "}", " ")
+    }
+
+    fn object(&mut self, object: &Object) -> String {
+        if object.entries.is_empty() {
+            return String::from("{}");
+        }
+
+        self.indent += 1;
+
+        let entries = object
+            .entries
+            .iter()
+            .map(|entry| format!("{}: {}", entry.key, self.expr(&entry.value)))
+            .collect();
+
+        self.indent -= 1;
+
+        self.list(entries, "{", "}", " ")
+    }
+
+    // Renders the items of an array or object literal on one line if they fit, otherwise one item
+    // per line. Items must have been rendered one level deeper than the current indentation, so
+    // that any nested literals which had to be split are indented correctly.
+    fn list(&self, items: Vec<String>, open: &str, close: &str, padding: &str) -> String {
+        let inline = format!("{open}{padding}{}{padding}{close}", items.join(", "));
+
+        if inline.len() <= MAX_INLINE_LIST_WIDTH && !inline.contains('\n') {
+            return inline;
+        }
+
+        let indentation = format!("{}{INDENT}", self.indentation());
+
+        let items: String = items
+            .iter()
+            .map(|item| format!("{indentation}{item},\n"))
+            .collect();
+
+        format!("{open}\n{items}{}{close}", self.indentation())
+    }
+
+    fn place(&mut self, place: &PlaceExpression) -> String {
+        match place {
+            PlaceExpression::Identifier(ident, _) => ident.clone(),
+            PlaceExpression::Index(place, index) => {
+                format!("{}[{}]", self.place(place), self.expr(index))
+            }
+            PlaceExpression::MemberAccess(place, member) => {
+                format!("{}.{member}", self.place(place))
+            }
+        }
+    }
+
+    fn indentation(&self) -> String {
+        INDENT.repeat(self.indent)
+    }
+}
+
+fn term(term: &Term) -> String {
+    match term {
+        Term::Identifier(ident, _) => ident.clone(),
+        Term::Literal(literal) => self::literal(literal),
+    }
+}
+
+fn literal(literal: &Literal) -> String {
+    match literal {
+        Literal::Number(number) => number.to_string(),
+        Literal::String(string) => format!("'{string}'"),
+    }
+}
+
+fn pattern(pattern: &Pattern) -> String {
+    match pattern {
+        Pattern::Variant(enum_ident, variant_ident, Some(ident)) => {
+            format!("{enum_ident}::{variant_ident}({ident})")
+        }
+        Pattern::Variant(enum_ident, variant_ident, None) => {
+            format!("{enum_ident}::{variant_ident}")
+        }
+    }
+}
+
+fn param(param: &ParamDeclare) -> String {
+    format!("{}: {}", param.ident, type_variant(&param.param_type))
+}
+
+fn type_params(type_param: &Option<String>) -> String {
+    type_param
+        .as_ref()
+        .map(|t| format!("<{t}>"))
+        .unwrap_or_default()
+}
+
+// Unlike `Display for TypeVariantLiteral`, type arguments are separated by a space.
+fn type_variant(type_variant: &TypeVariantLiteral) -> String {
+    match type_variant {
+        TypeVariantLiteral::Composite(outer, inner) => {
+            let inner = inner
+                .iter()
+                .map(self::type_variant)
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            format!("{outer}<{inner}>")
+        }
+        TypeVariantLiteral::Type(the_type) => the_type.to_string(),
+    }
+}
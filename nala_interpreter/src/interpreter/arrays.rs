@@ -1,7 +1,4 @@
-use std::{
-    sync::{Arc, Mutex},
-    usize,
-};
+use std::{cell::RefCell, rc::Rc, usize};
 
 use crate::{
     ast::{arrays::*, Expr},
@@ -9,35 +6,74 @@ use crate::{
     io_context::IoContext,
     resolved::value::Value,
     scopes::Scopes,
-    types::{fit::fits_type, inference::infer_type},
+    types::{fit::fits_type, inference::infer_type, type_variant::TypeVariant},
 };
 
-use super::basic::*;
+use super::{
+    basic::*,
+    maps::{index_map, set_entry},
+};
 
 pub fn eval_index(
-    array: &Value,
+    collection: &Value,
     index_expr: &Expr,
     scopes: &mut Scopes,
     current_scope: usize,
     ctx: &mut dyn IoContext,
 ) -> Result<Value, RuntimeError> {
     let index = eval_expr(index_expr, scopes, current_scope, ctx)?;
+    index_value(collection, &index)
+}
+
+// Gets the element at `index` of an array, or the value at the key `index` of a map.
+pub(crate) fn index_value(collection: &Value, index: &Value) -> Result<Value, RuntimeError> {
+    match collection {
+        Value::Array(array) => index_array(array, index),
+        Value::Map(map) => index_map(map, index),
+        _ => Err(RuntimeError::new(
+            "Cannot index into a value which is not an array or map.",
+        )),
+    }
+}
 
+fn index_array(array: &RefCell<Vec<Value>>, index: &Value) -> Result<Value, RuntimeError> {
     if let Value::Num(index) = index {
-        if let Value::Array(array) = array {
-            let array = Arc::clone(&array);
-            let array = array.lock().unwrap();
-            Ok(array.get(index as usize).unwrap().clone())
-        } else {
-            Err(RuntimeError::new(
-                "Cannot index into a value which is not an array.",
-            ))
-        }
+        let array = array.borrow();
+        Ok(array.get(*index as usize).unwrap().clone())
     } else {
         Err(RuntimeError::new("Cannot index using non-numeric value."))
     }
 }
 
+// Assigns `value` at `index` of an array, or to the key `index` of a map.
+pub(crate) fn set_index(
+    collection: Value,
+    index: Value,
+    value: Value,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<(), RuntimeError> {
+    if let Value::Void = value {
+        Err(RuntimeError::new("Cannot assign a value of type Void."))?;
+    }
+
+    match collection {
+        Value::Array(array) => {
+            let index = if let Value::Num(index) = index {
+                index
+            } else {
+                Err(RuntimeError::new("Index does not resolve to a Number."))?
+            };
+
+            let mut array = array.borrow_mut();
+            array[index as usize] = value;
+            Ok(())
+        }
+        Value::Map(map) => set_entry(&map, &index, value, scopes, current_scope),
+        _ => Err(RuntimeError::new("Trying to index into a non-Array.")),
+    }
+}
+
 pub fn eval_array(
     array: &Array,
     scopes: &mut Scopes,
@@ -45,11 +81,21 @@ pub fn eval_array(
     ctx: &mut dyn IoContext,
 ) -> Result<Value, RuntimeError> {
     let values = eval_elems(&array.elems, scopes, current_scope, ctx)?;
+    build_array(values, scopes, current_scope)
+}
+
+// Builds an array from its evaluated elements, which must all be of the same type.
+pub(crate) fn build_array(
+    values: Vec<Value>,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<Value, RuntimeError> {
+    scopes.budget.check_collection_len(values.len())?;
 
-    if let Some(first) = values.clone().first() {
+    if let Some(first) = values.first() {
         let first_type = infer_type(&first, scopes, current_scope)?;
 
-        for value in values.clone() {
+        for value in values.iter() {
             let second_type = infer_type(&value, scopes, current_scope)?;
 
             // TODO: This partially works. In the case of generic values like Option, if the first
@@ -61,12 +107,35 @@ pub fn eval_array(
             // for the array as a whole. If we get that working, maybe we can leverage that here to
             // be lazy and just compare each value here to that inferred type.
             if !fits_type(&value, &first_type, scopes, current_scope)? {
-                return Err(RuntimeError::new(
-                    &format!("Arrays can contain elements of only a single type. Found elements of types `{first_type}` and `{second_type}`.",
-                )));
+                return Err(mixed_types_error(&first_type, &second_type));
             }
         }
     };
 
-    Ok(Value::Array(Arc::new(Mutex::new(values))))
+    Ok(Value::Array(Rc::new(RefCell::new(values))))
+}
+
+// Checks that `value` can be added to an array holding `items`, being of the same type as them.
+pub(crate) fn check_element(
+    items: &[Value],
+    value: &Value,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<(), RuntimeError> {
+    scopes.budget.check_collection_len(items.len() + 1)?;
+
+    if let Some(first) = items.first() {
+        let first_type = infer_type(first, scopes, current_scope)?;
+
+        if !fits_type(value, &first_type, scopes, current_scope)? {
+            let value_type = infer_type(value, scopes, current_scope)?;
+            return Err(mixed_types_error(&first_type, &value_type));
+        }
+    }
+
+    Ok(())
+}
+
+fn mixed_types_error(first_type: &TypeVariant, second_type: &TypeVariant) -> RuntimeError {
+    RuntimeError::new(&format!("Arrays can contain elements of only a single type. Found elements of types `{first_type}` and `{second_type}`."))
 }
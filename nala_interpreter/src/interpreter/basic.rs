@@ -44,12 +44,14 @@ fn eval_line(
             let result = eval_expr(expr, scopes, current_scope, ctx)?;
             eval_assign(ident, &result, scopes, current_scope, ctx)
         }
+        Line::Blank | Line::Comment(_, _) => Ok(Value::Void),
         Line::Break(expr) => eval_break(expr, scopes, current_scope, ctx),
-        Line::Declare(ident, expr, declared_type, is_mutable) => eval_declare(
+        Line::Declare(ident, expr, declared_type, is_mutable, resolution) => eval_declare(
             ident,
             eval_expr(&expr, scopes, current_scope, ctx)?,
             declared_type.clone(),
             is_mutable.clone(),
+            *resolution,
             scopes,
             current_scope,
         ),
@@ -72,6 +74,9 @@ fn eval_line(
             scopes,
             current_scope,
         ),
+        // Test blocks only run under the test runner, see `eval_tests`.
+        Line::Test(_, _) => Ok(Value::Void),
+        Line::TryCatch(try_catch) => eval_try_catch(try_catch, scopes, current_scope, ctx),
         Line::Wiles(expr, block) => eval_wiles(&expr, block, scopes, current_scope, ctx),
     }
 }
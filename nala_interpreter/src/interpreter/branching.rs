@@ -1,9 +1,10 @@
-use super::basic::*;
-use std::sync::Arc;
+use std::{cell::RefCell, rc::Rc};
+
+use super::{basic::*, maps::map_entries, sets::set_elements};
 
 use crate::{
     ast::{
-        branching::{Else, ElseIf, IfElseChain, Match, MatchCase},
+        branching::{Else, ElseIf, IfElseChain, Match, MatchCase, TryCatch},
         *,
     },
     errors::RuntimeError,
@@ -13,6 +14,9 @@ use crate::{
     types::inference::infer_type,
 };
 
+// The slot the resolver gives a loop variable or caught error, the first binding in its block.
+const FIRST_SLOT: Resolution = Resolution::Local { depth: 0, slot: 0 };
+
 pub fn eval_if_else_chain(
     chain: &IfElseChain,
     scopes: &mut Scopes,
@@ -27,23 +31,26 @@ pub fn eval_if_else_chain(
     } = chain;
 
     if eval_cond(cond, scopes, current_scope, ctx)? {
-        let block_scope = scopes.new_scope(Some(current_scope));
-        return eval_lines(&block, scopes, block_scope, ctx);
+        return scopes.in_new_scope(Some(current_scope), |scopes, block_scope| {
+            eval_lines(&block, scopes, block_scope, ctx)
+        });
     }
 
     for else_if in else_ifs.iter() {
         let ElseIf { cond, block } = else_if;
 
         if eval_cond(cond, scopes, current_scope, ctx)? {
-            let block_scope = scopes.new_scope(Some(current_scope));
-            return eval_lines(block, scopes, block_scope, ctx);
+            return scopes.in_new_scope(Some(current_scope), |scopes, block_scope| {
+                eval_lines(block, scopes, block_scope, ctx)
+            });
         }
     }
 
     if let Some(else_block) = else_block {
         let Else { block } = else_block;
-        let block_scope = scopes.new_scope(Some(current_scope));
-        return eval_lines(&block, scopes, block_scope, ctx);
+        return scopes.in_new_scope(Some(current_scope), |scopes, block_scope| {
+            eval_lines(&block, scopes, block_scope, ctx)
+        });
     }
 
     Ok(Value::Void)
@@ -58,9 +65,7 @@ fn eval_cond(
     if let Value::Bool(cond) = eval_expr(cond, scopes, current_scope, ctx)? {
         Ok(cond)
     } else {
-        Err(RuntimeError::new(
-            "Cannot use non-boolean expressions inside 'if' conditions.",
-        ))
+        Err(non_bool_if_cond_error())
     }
 }
 
@@ -76,16 +81,32 @@ pub fn eval_for(
 
     let mut loop_result = Value::Void;
 
-    if let Value::Array(array) = result {
-        let array = Arc::clone(&array);
-        let array = array.lock().unwrap();
+    // Maps and sets are iterated over as an array of their entries, taken when the loop begins.
+    let array = match &result {
+        Value::Array(array) => Some(Rc::clone(array)),
+        Value::Map(map) => Some(Rc::new(RefCell::new(map_entries(map)))),
+        Value::Set(set) => Some(Rc::new(RefCell::new(set_elements(set)))),
+        _ => None,
+    };
+
+    if let Some(array) = array {
+        // Items are read one at a time, as the loop's body may modify the array.
+        let mut index = 0;
 
-        for (_, item) in array.iter().enumerate() {
-            let block_scope = scopes.new_scope(Some(current_scope));
+        loop {
+            let item = match array.borrow().get(index) {
+                Some(item) => item.clone(),
+                None => break,
+            };
 
-            scopes.add_binding(ident, item.clone(), None, block_scope, false)?;
+            index += 1;
+            scopes.budget.step()?;
 
-            loop_result = eval_lines(&block, scopes, block_scope, ctx)?;
+            loop_result = scopes.in_new_scope(Some(current_scope), |scopes, block_scope| {
+                scopes.add_resolved_binding(ident, FIRST_SLOT, item, None, block_scope, false)?;
+
+                eval_lines(&block, scopes, block_scope, ctx)
+            })?;
 
             if let Value::Break(value) = loop_result {
                 return Ok(*value);
@@ -94,10 +115,7 @@ pub fn eval_for(
 
         Ok(loop_result)
     } else {
-        Err(RuntimeError::new(&format!(
-            "Cannot iterate over values of non-Array types. Found '{result}' of type `{}`",
-            infer_type(&result, scopes, current_scope)?
-        )))
+        Err(non_array_for_error(&result, scopes, current_scope)?)
     }
 }
 
@@ -114,12 +132,11 @@ pub fn eval_wiles(
         let condition = if let Value::Bool(condition) = result {
             condition
         } else {
-            Err(RuntimeError::new(
-                "Wiles condition must resolve to a value of type Bool",
-            ))?
+            Err(non_bool_wiles_cond_error())?
         };
 
         if condition {
+            scopes.budget.step()?;
             let result = eval_lines(block, scopes, current_scope, ctx)?;
 
             if let Value::Break(value) = result {
@@ -159,15 +176,71 @@ pub fn eval_match(
         let MatchCase { pattern, block } = case;
 
         if let Some(bindings) = pattern.matches(&expr) {
-            let block_scope = scopes.new_scope(Some(current_scope));
+            return scopes.in_new_scope(Some(current_scope), |scopes, block_scope| {
+                for (slot, (ident, value)) in bindings.iter().enumerate() {
+                    let resolution = Resolution::Local { depth: 0, slot };
+                    scopes.add_resolved_binding(
+                        ident,
+                        resolution,
+                        value.clone(),
+                        None,
+                        block_scope,
+                        false,
+                    )?;
+                }
+
+                eval_lines(&block, scopes, block_scope, ctx)
+            });
+        }
+    }
 
-            for (ident, value) in bindings.iter() {
-                scopes.add_binding(ident, value.clone(), None, block_scope, false)?;
-            }
+    Ok(Value::Void)
+}
 
-            return eval_lines(&block, scopes, block_scope, ctx);
+pub fn eval_try_catch(
+    try_catch: &TryCatch,
+    scopes: &mut Scopes,
+    current_scope: usize,
+    ctx: &mut dyn IoContext,
+) -> Result<Value, RuntimeError> {
+    let TryCatch {
+        block,
+        ident,
+        catch_block,
+    } = try_catch;
+
+    let result = scopes.in_new_scope(Some(current_scope), |scopes, block_scope| {
+        eval_lines(block, scopes, block_scope, ctx)
+    });
+
+    match result {
+        Err(error) if error.is_catchable() => {
+            scopes.in_new_scope(Some(current_scope), |scopes, catch_scope| {
+                let error = error.to_value();
+                scopes.add_resolved_binding(ident, FIRST_SLOT, error, None, catch_scope, false)?;
+
+                eval_lines(catch_block, scopes, catch_scope, ctx)
+            })
         }
+        result => result,
     }
+}
 
-    Ok(Value::Void)
+pub(crate) fn non_bool_if_cond_error() -> RuntimeError {
+    RuntimeError::new("Cannot use non-boolean expressions inside 'if' conditions.")
+}
+
+pub(crate) fn non_bool_wiles_cond_error() -> RuntimeError {
+    RuntimeError::new("Wiles condition must resolve to a value of type Bool")
+}
+
+pub(crate) fn non_array_for_error(
+    result: &Value,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<RuntimeError, RuntimeError> {
+    Ok(RuntimeError::new(&format!(
+        "Cannot iterate over values of non-Array types. Found '{result}' of type `{}`",
+        infer_type(result, scopes, current_scope)?
+    )))
 }
@@ -19,16 +19,49 @@ pub fn eval_enum_variant(
     current_scope: usize,
     ctx: &mut dyn IoContext,
 ) -> Result<Value, RuntimeError> {
+    let existing_variant = lookup_variant(enum_ident, variant_ident, scopes, current_scope)?;
+
+    let data = if let Some(data) = data {
+        Some(eval_expr(&data, scopes, current_scope, ctx)?)
+    } else {
+        None
+    };
+
+    build_variant(
+        enum_ident,
+        variant_ident,
+        &existing_variant,
+        data,
+        scopes,
+        current_scope,
+    )
+}
+
+pub(crate) fn lookup_variant(
+    enum_ident: &str,
+    variant_ident: &str,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<EnumVariant, RuntimeError> {
     let (enum_variants, _enum_type_param) =
         scopes.get_type(enum_ident, current_scope)?.as_enum()?;
 
-    let existing_variant = find_variant(&enum_variants, variant_ident)?;
+    find_variant(&enum_variants, variant_ident)
+}
 
+// Builds a value of `existing_variant`, checking that its data fits the variant's declaration.
+pub(crate) fn build_variant(
+    enum_ident: &str,
+    variant_ident: &str,
+    existing_variant: &EnumVariant,
+    data: Option<Value>,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<Value, RuntimeError> {
     let data = if let Some(data) = data {
-        let data = eval_expr(&data, scopes, current_scope, ctx)?;
         let data_type = infer_type(&data, scopes, current_scope)?;
 
-        let expected_data_type = if let EnumVariant::Data(_, expected_data_type) = &existing_variant
+        let expected_data_type = if let EnumVariant::Data(_, expected_data_type) = existing_variant
         {
             expected_data_type
         } else {
@@ -1,6 +1,13 @@
-use std::collections::HashMap;
+use std::rc::Rc;
 
-use super::{basic::*, variables::*};
+use indexmap::IndexSet;
+
+use super::{
+    arrays::{build_array, check_element},
+    basic::*,
+    sets::{build_set, check_set_element},
+    variables::*,
+};
 
 use crate::{
     ast::{
@@ -9,9 +16,11 @@ use crate::{
         *,
     },
     errors::*,
+    evaluator::Evaluator,
     io_context::IoContext,
     resolved::{
         func_value::{FuncValue, Param},
+        hash_key::HashKey,
         value::Value,
     },
     scopes::Scopes,
@@ -32,30 +41,34 @@ pub fn eval_func_declare(
         params,
         return_type,
         type_param,
+        resolution,
     } = func;
 
-    let closure_scope = scopes.new_scope(Some(current_scope));
-
-    if let Some(type_param) = &type_param {
-        scopes.add_type_binding(
-            closure_scope,
-            &type_param,
-            TypeVariant::generic(type_param.clone()),
-        )?;
-    };
+    let func = scopes.in_new_scope(Some(current_scope), |scopes, closure_scope| {
+        if let Some(type_param) = &type_param {
+            scopes.add_type_binding(
+                closure_scope,
+                &type_param,
+                TypeVariant::generic(type_param.clone()),
+            )?;
+        };
 
-    let params = params_from_declares(&params, scopes, closure_scope)?;
-    let return_type = TypeVariant::from_literal(return_type, scopes, closure_scope)?;
+        let params = params_from_declares(&params, scopes, closure_scope)?;
+        let return_type = TypeVariant::from_literal(return_type, scopes, closure_scope)?;
 
-    scopes.add_binding(
-        &ident,
-        Value::Func(FuncValue {
+        Ok(FuncValue {
             block,
             params,
             return_type,
             type_param,
-            closure_scope,
-        }),
+            closure_scope: scopes.pin(closure_scope),
+        })
+    })?;
+
+    scopes.add_resolved_binding(
+        &ident,
+        resolution,
+        Value::Func(Rc::new(func)),
         None,
         current_scope,
         false,
@@ -76,32 +89,34 @@ pub fn eval_builtin_declare(
         type_param,
     } = func;
 
-    let closure_scope = scopes.new_scope(Some(current_scope));
-
-    if let Some(type_param) = &type_param {
-        scopes.add_type_binding(
-            closure_scope,
-            &type_param,
-            TypeVariant::generic(type_param.clone()),
-        )?;
-    };
+    let func = scopes.in_new_scope(Some(current_scope), |scopes, closure_scope| {
+        if let Some(type_param) = &type_param {
+            scopes.add_type_binding(
+                closure_scope,
+                &type_param,
+                TypeVariant::generic(type_param.clone()),
+            )?;
+        };
 
-    scopes.add_binding(
-        &ident,
-        Value::Func(FuncValue {
+        Ok(FuncValue {
             block,
             params,
             return_type,
             type_param,
-            closure_scope,
-        }),
+            closure_scope: scopes.pin(closure_scope),
+        })
+    })?;
+
+    scopes.add_binding(
+        &ident,
+        Value::Func(Rc::new(func)),
         None,
         current_scope,
         false,
     )
 }
 
-fn params_from_declares(
+pub(crate) fn params_from_declares(
     params: &Vec<ParamDeclare>,
     scopes: &mut Scopes,
     current_scope: usize,
@@ -138,36 +153,9 @@ pub fn eval_call(
         Call::Call(place, type_args, args) => {
             let block = eval_place_expr(place, scopes, current_scope, ctx)?;
 
-            if let Value::Func(FuncValue {
-                params,
-                block,
-                closure_scope,
-                type_param,
-                return_type: expected_return_type,
-            }) = block
-            {
-                let call_scope = scopes.new_scope(Some(closure_scope));
-
-                handle_type_args(type_args, type_param.clone(), scopes, call_scope)?;
-                let args = handle_args(args, params, scopes, call_scope, current_scope, ctx)?;
-
-                let return_value = match *block {
-                    FuncVariant::Nala(lines) => eval_lines(&lines, scopes, call_scope, ctx)?,
-                    FuncVariant::Builtin(func) => func(args, ctx)?,
-                };
-
-                let expected_return_type = if let Some(type_param) = type_param {
-                    let concrete_type = scopes.get_type(&type_param, call_scope)?;
-                    expected_return_type.make_concrete(Some(type_param), &concrete_type)
-                } else {
-                    expected_return_type
-                };
-
-                if fits_type(&return_value, &expected_return_type, scopes, current_scope)? {
-                    Ok(return_value)
-                } else {
-                    Err(RuntimeError::new(&format!("Tried to return value `{return_value:?}` of type `{0}` where value of type `{expected_return_type}` was expected.", infer_type(&return_value, scopes, current_scope)?)))
-                }
+            if let Value::Func(func) = block {
+                let args = eval_elems(args, scopes, current_scope, ctx)?;
+                call_func(func, type_args, args, scopes, current_scope, ctx)
             } else {
                 Err(RuntimeError::new(&format!("Cannot invoke a non-function.")))
             }
@@ -176,11 +164,149 @@ pub fn eval_call(
     }
 }
 
-fn handle_type_args(
+pub fn call_func(
+    func: Rc<FuncValue>,
     type_args: &Option<TypeVariantLiteral>,
-    type_param: Option<String>,
+    args: Vec<Value>,
+    scopes: &mut Scopes,
+    current_scope: usize,
+    ctx: &mut dyn IoContext,
+) -> Result<Value, RuntimeError> {
+    let FuncValue {
+        params,
+        block,
+        closure_scope,
+        type_param,
+        return_type: expected_return_type,
+    } = func.as_ref();
+
+    scopes.budget.step()?;
+
+    scopes.in_new_scope(Some(closure_scope.id()), |scopes, call_scope| {
+        handle_type_args(type_args, type_param, scopes, call_scope, current_scope)?;
+        handle_args(&args, params, scopes, call_scope, current_scope)?;
+
+        let return_value = match block.as_ref() {
+            FuncVariant::Nala(lines) => {
+                scopes.budget.enter_call()?;
+                let result = eval_lines(lines, scopes, call_scope, ctx);
+                scopes.budget.exit_call();
+                result?
+            }
+            FuncVariant::Builtin(func) => {
+                let mut evaluator = TreeEvaluator {
+                    scopes,
+                    scope: call_scope,
+                    ctx,
+                };
+
+                func(args, &mut evaluator)?
+            }
+            FuncVariant::Compiled(_) => Err(RuntimeError::new(
+                "Functions compiled for the bytecode VM can only be called by the VM.",
+            ))?,
+        };
+
+        check_return(
+            return_value,
+            expected_return_type,
+            type_param,
+            scopes,
+            call_scope,
+            current_scope,
+        )
+    })
+}
+
+// Lets builtins call back into the script from the scope of their call.
+struct TreeEvaluator<'a> {
+    scopes: &'a mut Scopes,
+    scope: usize,
+    ctx: &'a mut dyn IoContext,
+}
+
+impl Evaluator for TreeEvaluator<'_> {
+    fn ctx(&mut self) -> &mut dyn IoContext {
+        self.ctx
+    }
+
+    fn call(&mut self, func: Rc<FuncValue>, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        call_func(func, &None, args, self.scopes, self.scope, self.ctx)
+    }
+
+    fn build_array(&mut self, values: Vec<Value>) -> Result<Value, RuntimeError> {
+        build_array(values, self.scopes, self.scope)
+    }
+
+    fn check_collection_len(&mut self, len: usize) -> Result<(), RuntimeError> {
+        self.scopes.budget.check_collection_len(len)
+    }
+
+    fn check_string_len(&mut self, len: usize) -> Result<(), RuntimeError> {
+        self.scopes.budget.check_string_len(len)
+    }
+
+    fn check_element(&mut self, items: &[Value], value: &Value) -> Result<(), RuntimeError> {
+        check_element(items, value, self.scopes, self.scope)
+    }
+
+    fn build_set(&mut self, values: Vec<Value>) -> Result<Value, RuntimeError> {
+        build_set(values, self.scopes, self.scope)
+    }
+
+    fn check_set_element(
+        &mut self,
+        set: &IndexSet<HashKey>,
+        elem: &HashKey,
+    ) -> Result<(), RuntimeError> {
+        check_set_element(set, elem, self.scopes, self.scope)
+    }
+
+    fn get_type(&mut self, ident: &str) -> Result<TypeVariant, RuntimeError> {
+        self.scopes.get_type(ident, self.scope)
+    }
+
+    fn fits_type(&mut self, value: &Value, expected: &TypeVariant) -> Result<bool, RuntimeError> {
+        fits_type(value, expected, self.scopes, self.scope)
+    }
+}
+
+// Checks that a function's return value fits its return type, made concrete by the type argument
+// bound in `call_scope` if the function is generic.
+pub(crate) fn check_return(
+    return_value: Value,
+    expected_return_type: &TypeVariant,
+    type_param: &Option<String>,
     scopes: &mut Scopes,
     call_scope: usize,
+    current_scope: usize,
+) -> Result<Value, RuntimeError> {
+    let concrete_return_type;
+    let expected_return_type = if let Some(type_param) = type_param {
+        let concrete_type = scopes.get_type(&type_param, call_scope)?;
+        concrete_return_type = expected_return_type
+            .clone()
+            .make_concrete(Some(type_param.clone()), &concrete_type);
+        &concrete_return_type
+    } else {
+        expected_return_type
+    };
+
+    if fits_type(&return_value, &expected_return_type, scopes, current_scope)? {
+        Ok(return_value)
+    } else {
+        Err(RuntimeError::new(&format!("Tried to return value `{return_value:?}` of type `{0}` where value of type `{expected_return_type}` was expected.", infer_type(&return_value, scopes, current_scope)?)))
+    }
+}
+
+// Binds the type argument of a call, if any. It's resolved from the caller's scope, where the types it
+// names are declared.
+pub(crate) fn handle_type_args(
+    type_args: &Option<TypeVariantLiteral>,
+    type_param: &Option<String>,
+    scopes: &mut Scopes,
+    call_scope: usize,
+    current_scope: usize,
 ) -> Result<(), RuntimeError> {
     if let Some(type_arg) = type_args {
         if type_param.is_none() {
@@ -189,23 +315,41 @@ fn handle_type_args(
             )))?;
         }
 
-        let type_arg = TypeVariant::from_literal(type_arg.clone(), &mut Scopes::new(), 0)?;
-        scopes.add_type_binding(call_scope, &type_param.unwrap(), type_arg)?;
+        let type_arg = TypeVariant::from_literal(type_arg.clone(), scopes, current_scope)?;
+        scopes.add_type_binding(call_scope, type_param.as_ref().unwrap(), type_arg)?;
     }
 
     Ok(())
 }
 
 fn handle_args(
-    args: &Vec<Expr>,
-    params: Vec<Param>,
+    args: &[Value],
+    params: &[Param],
     scopes: &mut Scopes,
     call_scope: usize,
     current_scope: usize,
-    ctx: &mut dyn IoContext,
-) -> Result<HashMap<String, Value>, RuntimeError> {
-    let args = eval_elems(&*args, scopes, current_scope, ctx)?;
+) -> Result<(), RuntimeError> {
+    check_arg_count(params, args)?;
+
+    // The resolver places parameters in the first slots of the call scope.
+    for (slot, (param, arg)) in params.iter().zip(args.iter()).enumerate() {
+        check_arg(arg, param, scopes, call_scope, current_scope)?;
+
+        let resolution = Resolution::Local { depth: 0, slot };
+        scopes.add_resolved_binding(
+            &param.ident,
+            resolution,
+            arg.clone(),
+            None,
+            call_scope,
+            false,
+        )?;
+    }
 
+    Ok(())
+}
+
+pub(crate) fn check_arg_count(params: &[Param], args: &[Value]) -> Result<(), RuntimeError> {
     if params.len() != args.len() {
         return Err(RuntimeError::new(&format!(
             "Called function with wrong number of arguments: Expected {0}, got {1}.",
@@ -214,27 +358,36 @@ fn handle_args(
         )));
     }
 
-    let mut param_args: HashMap<String, Value> = HashMap::new();
-
-    for (i, param) in params.iter().enumerate() {
-        let arg = args.get(i).unwrap();
-
-        if !fits_type(arg, &param.param_type, scopes, current_scope)? {
-            return Err(wrong_arg_type_for_param_error(
-                arg,
-                infer_type(&arg, scopes, current_scope)?.to_string(),
-                param.param_type.to_string(),
-            ));
-        }
+    Ok(())
+}
 
-        let arg_type = infer_type(&arg, scopes, current_scope)?;
-        resolve_generics(&param.param_type, arg_type, scopes, call_scope)?;
+// Checks that `arg` fits `param`, binding any generic types it resolves in `call_scope`.
+pub(crate) fn check_arg(
+    arg: &Value,
+    param: &Param,
+    scopes: &mut Scopes,
+    call_scope: usize,
+    current_scope: usize,
+) -> Result<(), RuntimeError> {
+    if !fits_type(arg, &param.param_type, scopes, current_scope)? {
+        return Err(wrong_arg_type_for_param_error(
+            arg,
+            infer_type(&arg, scopes, current_scope)?.to_string(),
+            param.param_type.to_string(),
+        ));
+    }
 
-        scopes.add_binding(&param.ident, arg.clone(), None, call_scope, false)?;
-        param_args.entry(param.ident.clone()).or_insert(arg.clone());
+    // An empty collection has no element type to resolve generics with, but fits any parameter of
+    // its kind.
+    match arg {
+        Value::Array(items) if items.borrow().is_empty() => return Ok(()),
+        Value::Map(entries) if entries.borrow().is_empty() => return Ok(()),
+        Value::Set(elems) if elems.borrow().is_empty() => return Ok(()),
+        _ => (),
     }
 
-    Ok(param_args)
+    let arg_type = infer_type(&arg, scopes, current_scope)?;
+    resolve_generics(&param.param_type, arg_type, scopes, call_scope)
 }
 
 fn resolve_generics(
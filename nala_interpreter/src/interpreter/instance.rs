@@ -0,0 +1,186 @@
+use std::{rc::Rc, sync::Arc};
+
+use crate::{
+    ast::{types::type_literal_variant::TypeVariantLiteral, FuncVariant, Program},
+    errors::{ErrorKind, RuntimeError},
+    evaluator::Evaluator,
+    io_context::IoContext,
+    limits::{CancelHandle, Limits},
+    parser::parse_code,
+    resolved::{
+        func_value::{FuncValue, Param},
+        value::Value,
+    },
+    resolver::resolve,
+    scopes::Scopes,
+    types::{inference, type_variant::TypeVariant},
+};
+
+use super::{
+    basic::eval_lines,
+    functions::{call_func, eval_builtin_declare},
+    new_prelude_scope,
+};
+
+// An interpreter whose top-level scope outlives a single program, so that a host can evaluate code
+// incrementally and then drive the resulting bindings from Rust.
+pub struct Interpreter<C: IoContext> {
+    scopes: Scopes,
+    // Builtins, including those registered by the host, which globals may shadow.
+    prelude: usize,
+    top_scope: usize,
+    ctx: C,
+}
+
+impl<C: IoContext> Interpreter<C> {
+    pub fn new(ctx: C) -> Result<Self, RuntimeError> {
+        let mut scopes = Scopes::new();
+        let prelude = new_prelude_scope(&mut scopes)?;
+        let top_scope = scopes.new_scope(Some(prelude));
+
+        Ok(Self {
+            scopes,
+            prelude,
+            top_scope,
+            ctx,
+        })
+    }
+
+    pub fn eval_str(&mut self, code: &str) -> Result<Value, RuntimeError> {
+        let program = parse_code(code.to_owned())
+            .map_err(|message| RuntimeError::with_kind(ErrorKind::Parse, &message))?;
+
+        self.eval_program(program)
+    }
+
+    pub fn eval_program(&mut self, mut program: Program) -> Result<Value, RuntimeError> {
+        resolve(&mut program)?;
+        self.scopes.budget.start();
+
+        let lines = match program {
+            Program::Block(lines) => lines,
+            Program::Lines(lines) => lines,
+        };
+
+        eval_lines(&lines, &mut self.scopes, self.top_scope, &mut self.ctx)
+    }
+
+    // Limits apply to each evaluation and call separately, from when it starts.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.scopes.budget.set_limits(limits);
+    }
+
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.scopes.budget.cancel_handle()
+    }
+
+    pub fn get_global(&self, ident: &str) -> Result<Value, RuntimeError> {
+        self.scopes.get_value(ident, self.top_scope)
+    }
+
+    pub fn get_global_type(&self, ident: &str) -> Result<TypeVariant, RuntimeError> {
+        self.scopes.get_type(ident, self.top_scope)
+    }
+
+    // The identifiers of every global value and type, including builtins.
+    pub fn global_idents(&self) -> Vec<String> {
+        self.scopes.get_binding_idents(self.top_scope)
+    }
+
+    // Declares a mutable global, or re-assigns it if it already exists. Existing immutable bindings
+    // cannot be overwritten.
+    pub fn set_global(&mut self, ident: &str, value: Value) -> Result<(), RuntimeError> {
+        if value.is_void() {
+            return Err(RuntimeError::new(
+                "Cannot declare a variable with a value of type Void.",
+            ));
+        }
+
+        if self.scopes.binding_exists_local(ident, self.top_scope) {
+            self.scopes.mutate_value(ident, self.top_scope, value)?;
+        } else {
+            self.scopes
+                .add_binding(ident, value, None, self.top_scope, true)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn call_function(&mut self, ident: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        if let Value::Func(func) = self.get_global(ident)? {
+            self.scopes.budget.start();
+
+            call_func(
+                func,
+                &None,
+                args,
+                &mut self.scopes,
+                self.top_scope,
+                &mut self.ctx,
+            )
+        } else {
+            Err(RuntimeError::new(&format!(
+                "Cannot invoke non-function `{ident}`."
+            )))
+        }
+    }
+
+    // Exposes a Rust closure to scripts as a builtin function. Arguments are type checked against
+    // `params` and the result against `return_type` exactly as they are for Nala functions, and are
+    // passed to the closure in the order of `params`. The closure can reach the context and call
+    // functions it's passed through its `Evaluator`. Closures needing mutable state should capture
+    // it behind a `RefCell` or `Mutex`.
+    pub fn register_function<F>(
+        &mut self,
+        ident: &str,
+        params: Vec<Param>,
+        return_type: TypeVariant,
+        func: F,
+    ) -> Result<(), RuntimeError>
+    where
+        F: Fn(Vec<Value>, &mut dyn Evaluator) -> Result<Value, RuntimeError> + 'static,
+    {
+        let func = FuncValue {
+            block: Rc::new(FuncVariant::Builtin(Arc::new(func))),
+            params,
+            return_type,
+            type_param: None,
+            closure_scope: self.scopes.pin(self.prelude),
+        };
+
+        self.register_builtin(ident, func)
+    }
+
+    pub fn register_builtin(&mut self, ident: &str, func: FuncValue) -> Result<(), RuntimeError> {
+        eval_builtin_declare(ident.to_owned(), func, &mut self.scopes, self.prelude)?;
+        Ok(())
+    }
+
+    pub fn infer_type(&mut self, value: &Value) -> Result<TypeVariant, RuntimeError> {
+        inference::infer_type(value, &mut self.scopes, self.top_scope)
+    }
+
+    // Resolves a type annotation against the global types, such as the struct named by `Person`.
+    pub fn resolve_type(
+        &mut self,
+        literal: TypeVariantLiteral,
+    ) -> Result<TypeVariant, RuntimeError> {
+        TypeVariant::from_literal(literal, &mut self.scopes, self.top_scope)
+    }
+
+    pub fn scopes(&self) -> &Scopes {
+        &self.scopes
+    }
+
+    pub fn ctx(&self) -> &C {
+        &self.ctx
+    }
+
+    pub fn ctx_mut(&mut self) -> &mut C {
+        &mut self.ctx
+    }
+
+    pub fn into_ctx(self) -> C {
+        self.ctx
+    }
+}
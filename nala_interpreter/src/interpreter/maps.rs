@@ -0,0 +1,126 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use indexmap::IndexMap;
+
+use crate::{
+    ast::maps::*,
+    errors::RuntimeError,
+    io_context::IoContext,
+    resolved::{hash_key::HashKey, value::Value},
+    scopes::Scopes,
+    types::{fit::fits_type, inference::infer_type},
+};
+
+use super::basic::eval_expr;
+
+pub fn eval_map(
+    map: &Map,
+    scopes: &mut Scopes,
+    current_scope: usize,
+    ctx: &mut dyn IoContext,
+) -> Result<Value, RuntimeError> {
+    let mut entries = Vec::with_capacity(map.entries.len());
+
+    for MapEntry { key, value } in map.entries.iter() {
+        let key = eval_expr(key, scopes, current_scope, ctx)?;
+        let value = eval_expr(value, scopes, current_scope, ctx)?;
+        entries.push((key, value));
+    }
+
+    build_map(entries, scopes, current_scope)
+}
+
+// Builds a map from its evaluated entries, whose keys and values must each be of a single type. Later
+// entries replace earlier ones with the same key.
+pub(crate) fn build_map(
+    entries: Vec<(Value, Value)>,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<Value, RuntimeError> {
+    let mut map = IndexMap::new();
+
+    for (key, value) in entries {
+        let key = HashKey::from_value(&key)?;
+        check_entry(&map, &key, &value, scopes, current_scope)?;
+        map.insert(key, value);
+    }
+
+    Ok(Value::Map(Rc::new(RefCell::new(map))))
+}
+
+pub(crate) fn index_map(
+    map: &RefCell<IndexMap<HashKey, Value>>,
+    key: &Value,
+) -> Result<Value, RuntimeError> {
+    let key = HashKey::from_value(key)?;
+
+    match map.borrow().get(&key) {
+        Some(value) => Ok(value.clone()),
+        None => Err(RuntimeError::new(&format!(
+            "Key `{key:?}` does not exist in map."
+        ))),
+    }
+}
+
+pub(crate) fn set_entry(
+    map: &RefCell<IndexMap<HashKey, Value>>,
+    key: &Value,
+    value: Value,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<(), RuntimeError> {
+    let key = HashKey::from_value(key)?;
+    check_entry(&map.borrow(), &key, &value, scopes, current_scope)?;
+    map.borrow_mut().insert(key, value);
+
+    Ok(())
+}
+
+// Checks that an entry can be added to `map`, its key and value being of the same types as those of
+// the entries already there.
+fn check_entry(
+    map: &IndexMap<HashKey, Value>,
+    key: &HashKey,
+    value: &Value,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<(), RuntimeError> {
+    if !map.contains_key(key) {
+        scopes.budget.check_collection_len(map.len() + 1)?;
+    }
+
+    if let Some((first_key, first_value)) = map.first() {
+        let first_key = first_key.to_value();
+        let key = key.to_value();
+        let first_key_type = infer_type(&first_key, scopes, current_scope)?;
+
+        if !fits_type(&key, &first_key_type, scopes, current_scope)? {
+            let key_type = infer_type(&key, scopes, current_scope)?;
+            return Err(RuntimeError::new(&format!("Maps can contain keys of only a single type. Found keys of types `{first_key_type}` and `{key_type}`.")));
+        }
+
+        let first_value_type = infer_type(first_value, scopes, current_scope)?;
+
+        if !fits_type(value, &first_value_type, scopes, current_scope)? {
+            let value_type = infer_type(value, scopes, current_scope)?;
+            return Err(RuntimeError::new(&format!("Maps can contain values of only a single type. Found values of types `{first_value_type}` and `{value_type}`.")));
+        }
+    }
+
+    Ok(())
+}
+
+// The entries of `map` as `{ key, value }` objects, in order. This is what `for` loops iterate over.
+pub(crate) fn map_entries(map: &RefCell<IndexMap<HashKey, Value>>) -> Vec<Value> {
+    map.borrow()
+        .iter()
+        .map(|(key, value)| {
+            let fields = HashMap::from([
+                (String::from("key"), key.to_value()),
+                (String::from("value"), value.clone()),
+            ]);
+
+            Value::Object(Rc::new(RefCell::new(fields)))
+        })
+        .collect()
+}
@@ -1,12 +1,18 @@
-mod arrays;
+pub(crate) mod arrays;
 pub mod basic;
-mod branching;
+pub(crate) mod branching;
 pub mod enums;
-mod functions;
-mod objects;
-mod operations;
-mod types;
-mod variables;
+pub(crate) mod functions;
+mod instance;
+pub(crate) mod maps;
+pub(crate) mod objects;
+pub(crate) mod operations;
+pub(crate) mod sets;
+pub mod testing;
+pub(crate) mod types;
+pub(crate) mod variables;
+
+use std::rc::Rc;
 
 use crate::{
     ast::{terms::*, *},
@@ -14,7 +20,9 @@ use crate::{
     builtins::*,
     errors::RuntimeError,
     io_context::IoContext,
+    limits::{Budget, CancelHandle, Limits},
     resolved::value::Value,
+    resolver::resolve,
     scopes::*,
 };
 
@@ -25,13 +33,26 @@ use self::{
 };
 use basic::*;
 
+pub use self::instance::Interpreter;
+
 pub fn eval_program(program: Program, ctx: &mut impl IoContext) -> Result<Value, RuntimeError> {
+    eval_program_with_limits(program, ctx, Limits::default(), CancelHandle::new())
+}
+
+// Evaluates a program which is stopped once it exceeds `limits`, or when `cancel` is cancelled.
+pub fn eval_program_with_limits(
+    mut program: Program,
+    ctx: &mut impl IoContext,
+    limits: Limits,
+    cancel: CancelHandle,
+) -> Result<Value, RuntimeError> {
+    resolve(&mut program)?;
+
     let mut scopes = Scopes::new();
-    let top_scope = scopes.new_scope(None);
+    scopes.budget = Budget::new(limits, cancel);
+    scopes.budget.start();
 
-    load_builtin_types(&mut scopes, top_scope)?;
-    load_builtin_constants(&mut scopes, top_scope);
-    load_builtin_functions(&mut scopes, top_scope)?;
+    let top_scope = new_top_scope(&mut scopes)?;
 
     match program {
         Program::Block(lines) => eval_lines(&lines, &mut scopes, top_scope, ctx),
@@ -40,19 +61,39 @@ pub fn eval_program(program: Program, ctx: &mut impl IoContext) -> Result<Value,
 }
 
 pub fn eval_term(
-    term: Term,
+    term: &Term,
     scopes: &mut Scopes,
     current_scope: usize,
 ) -> Result<Value, RuntimeError> {
     match term {
-        Term::Identifier(ident) => Ok(scopes.get_value(&ident, current_scope)?),
+        Term::Identifier(ident, resolution) => {
+            Ok(scopes.get_resolved_value(ident, *resolution, current_scope)?)
+        }
         Term::Literal(value) => match value {
-            Literal::Number(value) => Ok(Value::Num(value)),
-            Literal::String(value) => Ok(Value::String(value)),
+            Literal::Number(value) => Ok(Value::Num(*value)),
+            Literal::String(value) => Ok(Value::String(Rc::clone(value))),
         },
     }
 }
 
+// The scope a program's globals are declared in. Its parent is the prelude, so that programs can
+// declare globals named after builtins, shadowing them.
+pub(crate) fn new_top_scope(scopes: &mut Scopes) -> Result<usize, RuntimeError> {
+    let prelude = new_prelude_scope(scopes)?;
+    Ok(scopes.new_scope(Some(prelude)))
+}
+
+// A scope holding every builtin type, constant and function.
+pub(crate) fn new_prelude_scope(scopes: &mut Scopes) -> Result<usize, RuntimeError> {
+    let prelude = scopes.new_scope(None);
+
+    load_builtin_types(scopes, prelude)?;
+    load_builtin_constants(scopes, prelude);
+    load_builtin_functions(scopes, prelude)?;
+
+    Ok(prelude)
+}
+
 fn load_builtin_types(scopes: &mut Scopes, current_scope: usize) -> Result<(), RuntimeError> {
     // TODO: This is going to quickly become problematic. Even with only two builtin types,
     // HttpResult<T> is dependent on Option<T> and needs to be loaded first. In this very simple
@@ -82,14 +123,32 @@ fn load_builtin_constants(scopes: &mut Scopes, top_scope: usize) {
     ]
     .into_iter()
     {
-        if let Err(e) = eval_declare(&ident, value, None, false, scopes, top_scope) {
+        if let Err(e) = eval_declare(
+            &ident,
+            value,
+            None,
+            false,
+            Resolution::Global,
+            scopes,
+            top_scope,
+        ) {
             panic!("Error loading builtin constants: {0}", e.message)
         }
     }
 }
 
 fn load_builtin_functions(scopes: &mut Scopes, top_scope: usize) -> Result<(), RuntimeError> {
-    for (ident, func) in get_builtins().into_iter() {
+    for (ident, func, capability) in get_builtins().into_iter() {
+        let func = match capability {
+            Some(capability) => gate_builtin(
+                ident.clone(),
+                func,
+                capability,
+                scopes.budget.capabilities(),
+            ),
+            None => func,
+        };
+
         if let Err(e) = eval_builtin_declare(ident, func, scopes, top_scope) {
             panic!("Error loading builtin functions: {0}", e.message)
         }
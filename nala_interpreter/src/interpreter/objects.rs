@@ -1,7 +1,4 @@
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use crate::{
     ast::objects::*, errors::RuntimeError, io_context::IoContext, resolved::value::Value, scopes::*,
@@ -11,8 +8,8 @@ use super::basic::eval_expr;
 
 pub fn eval_member_access(object: &Value, field: &String) -> Result<Value, RuntimeError> {
     if let Value::Object(reference) = object {
-        let object = Arc::clone(&reference);
-        let object = object.lock().unwrap();
+        let object = Rc::clone(&reference);
+        let object = object.borrow();
         if object.contains_key(field) {
             Ok(object[field].clone())
         } else {
@@ -36,7 +33,15 @@ pub fn eval_object(
     let object: HashMap<String, Value> =
         eval_object_entries(&object.entries, scopes, current_scope, ctx)?;
 
-    Ok(Value::Object(Arc::new(Mutex::new(object))))
+    build_object(object, scopes)
+}
+
+pub(crate) fn build_object(
+    fields: HashMap<String, Value>,
+    scopes: &Scopes,
+) -> Result<Value, RuntimeError> {
+    scopes.budget.check_collection_len(fields.len())?;
+    Ok(Value::Object(Rc::new(RefCell::new(fields))))
 }
 
 fn eval_object_entries(
@@ -24,7 +24,7 @@ pub fn do_add(
         }
         Value::String(left) => {
             if let Value::String(right) = right {
-                Ok(Value::String(left + &right))
+                concat_strings(&left, &right, scopes)
             } else {
                 unreachable!()
             }
@@ -33,6 +33,15 @@ pub fn do_add(
     }
 }
 
+pub(crate) fn concat_strings(
+    left: &str,
+    right: &str,
+    scopes: &Scopes,
+) -> Result<Value, RuntimeError> {
+    scopes.budget.check_string_len(left.len() + right.len())?;
+    Ok(Value::String(format!("{left}{right}").into()))
+}
+
 pub fn do_subtract(
     left: Value,
     right: Value,
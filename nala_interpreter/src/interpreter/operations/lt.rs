@@ -1,3 +1,5 @@
+use std::rc::Rc;
+
 use crate::{
     ast::types::primitive_type::PrimitiveType,
     errors::*,
@@ -46,7 +48,7 @@ fn num_lt(
 }
 
 fn string_lt(
-    left: String,
+    left: Rc<str>,
     right: Value,
     scopes: &mut Scopes,
     current_scope: usize,
@@ -1,4 +1,4 @@
-mod arithmatic;
+pub(crate) mod arithmatic;
 pub mod equals;
 mod errors;
 pub mod gt;
@@ -10,8 +10,8 @@ use crate::{
 };
 
 use super::{
-    arrays::eval_array, enums::eval_enum_variant, functions::*, objects::eval_object, Primary,
-    Unary,
+    arrays::eval_array, enums::eval_enum_variant, functions::*, maps::eval_map,
+    objects::eval_object, sets::eval_set, Primary, Unary,
 };
 
 use self::arithmatic::*;
@@ -50,13 +50,13 @@ pub fn eval_factor(
     match factor {
         Multiplication::Mult(left, right) => {
             let left = eval_factor(left, scopes, current_scope, ctx)?;
-            let right = eval_term(right.clone(), scopes, current_scope)?;
+            let right = eval_term(right, scopes, current_scope)?;
 
             do_multiply(left, right, scopes, current_scope)
         }
         Multiplication::Div(left, right) => {
             let left = eval_factor(left, scopes, current_scope, ctx)?;
-            let right = eval_term(right.clone(), scopes, current_scope)?;
+            let right = eval_term(right, scopes, current_scope)?;
 
             do_divide(left, right, scopes, current_scope)
         }
@@ -86,6 +86,8 @@ pub fn eval_primary(
         Primary::Literal(value) => Ok(Value::from_literal(value.clone())?),
         Primary::Array(array) => eval_array(array, scopes, current_scope, ctx),
         Primary::Object(object) => eval_object(object, scopes, current_scope, ctx),
+        Primary::Map(map) => eval_map(map, scopes, current_scope, ctx),
+        Primary::Set(set) => eval_set(set, scopes, current_scope, ctx),
         Primary::EnumVariant(enum_ident, variant_ident, data) => {
             eval_enum_variant(enum_ident, variant_ident, data, scopes, current_scope, ctx)
         }
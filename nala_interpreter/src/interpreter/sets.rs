@@ -0,0 +1,78 @@
+use std::{cell::RefCell, rc::Rc};
+
+use indexmap::IndexSet;
+
+use crate::{
+    ast::sets::*,
+    errors::RuntimeError,
+    io_context::IoContext,
+    resolved::{hash_key::HashKey, value::Value},
+    scopes::Scopes,
+    types::{fit::fits_type, inference::infer_type},
+};
+
+use super::basic::eval_expr;
+
+pub fn eval_set(
+    set: &Set,
+    scopes: &mut Scopes,
+    current_scope: usize,
+    ctx: &mut dyn IoContext,
+) -> Result<Value, RuntimeError> {
+    let mut values = Vec::with_capacity(set.elems.len());
+
+    for elem in set.elems.iter() {
+        values.push(eval_expr(elem, scopes, current_scope, ctx)?);
+    }
+
+    build_set(values, scopes, current_scope)
+}
+
+// Builds a set from its evaluated elements, which must all be of the same type. Duplicates are
+// dropped, keeping the first of them.
+pub(crate) fn build_set(
+    values: Vec<Value>,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<Value, RuntimeError> {
+    let mut set = IndexSet::new();
+
+    for value in values {
+        let elem = HashKey::from_value(&value)?;
+        check_set_element(&set, &elem, scopes, current_scope)?;
+        set.insert(elem);
+    }
+
+    Ok(Value::Set(Rc::new(RefCell::new(set))))
+}
+
+// Checks that `elem` can be added to `set`, being of the same type as the elements already there.
+pub(crate) fn check_set_element(
+    set: &IndexSet<HashKey>,
+    elem: &HashKey,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<(), RuntimeError> {
+    if set.contains(elem) {
+        return Ok(());
+    }
+
+    scopes.budget.check_collection_len(set.len() + 1)?;
+
+    if let Some(first) = set.first() {
+        let first_type = infer_type(&first.to_value(), scopes, current_scope)?;
+        let value = elem.to_value();
+
+        if !fits_type(&value, &first_type, scopes, current_scope)? {
+            let value_type = infer_type(&value, scopes, current_scope)?;
+            return Err(RuntimeError::new(&format!("Sets can contain elements of only a single type. Found elements of types `{first_type}` and `{value_type}`.")));
+        }
+    }
+
+    Ok(())
+}
+
+// The elements of `set` in order. This is what `for` loops iterate over.
+pub(crate) fn set_elements(set: &RefCell<IndexSet<HashKey>>) -> Vec<Value> {
+    set.borrow().iter().map(HashKey::to_value).collect()
+}
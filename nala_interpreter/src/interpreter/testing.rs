@@ -0,0 +1,90 @@
+use crate::{
+    ast::*,
+    errors::RuntimeError,
+    io_context::IoContext,
+    limits::{Budget, CancelHandle, Limits},
+    resolver::resolve,
+    scopes::Scopes,
+};
+
+use super::{basic::eval_lines, new_top_scope};
+
+#[derive(Debug)]
+pub struct TestResult {
+    pub name: String,
+    pub outcome: Result<(), RuntimeError>,
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+// Runs each top-level `test` block of the program in its own fresh set of scopes. Only the
+// program's declarations (functions, types and variables) are evaluated ahead of each test, other
+// top-level statements are skipped so that they don't run once per test.
+pub fn eval_tests(program: Program, ctx: &mut impl IoContext) -> Vec<TestResult> {
+    eval_tests_with_limits(program, ctx, Limits::default())
+}
+
+// Runs tests as `eval_tests` does, each one stopped once it exceeds `limits`.
+pub fn eval_tests_with_limits(
+    mut program: Program,
+    ctx: &mut impl IoContext,
+    limits: Limits,
+) -> Vec<TestResult> {
+    // A program the resolver rejects can't run, so each of its tests fails with the same error.
+    let resolved = resolve(&mut program);
+
+    let lines = match program {
+        Program::Block(lines) => lines,
+        Program::Lines(lines) => lines,
+    };
+
+    let declarations: Vec<Line> = lines
+        .iter()
+        .filter(|line| is_declaration(line))
+        .cloned()
+        .collect();
+
+    lines
+        .iter()
+        .filter_map(|line| match line {
+            Line::Test(name, block) => Some(TestResult {
+                name: name.clone(),
+                outcome: resolved
+                    .clone()
+                    .and_then(|_| eval_test(&declarations, block, ctx, limits.clone())),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn eval_test(
+    declarations: &Vec<Line>,
+    block: &Vec<Line>,
+    ctx: &mut dyn IoContext,
+    limits: Limits,
+) -> Result<(), RuntimeError> {
+    let mut scopes = Scopes::new();
+    scopes.budget = Budget::new(limits, CancelHandle::new());
+    scopes.budget.start();
+
+    let top_scope = new_top_scope(&mut scopes)?;
+
+    eval_lines(declarations, &mut scopes, top_scope, ctx)?;
+
+    let test_scope = scopes.new_scope(Some(top_scope));
+    eval_lines(block, &mut scopes, test_scope, ctx)?;
+
+    Ok(())
+}
+
+fn is_declaration(line: &Line) -> bool {
+    matches!(
+        line,
+        Line::Declare(..) | Line::Enum(..) | Line::Func(..) | Line::Struct(..)
+    )
+}
@@ -14,27 +14,27 @@ pub fn eval_struct(
     scopes: &mut Scopes,
     current_scope: usize,
 ) -> Result<Value, RuntimeError> {
-    let closure_scope = scopes.new_scope(Some(current_scope));
+    let binding = scopes.in_new_scope(Some(current_scope), |scopes, closure_scope| {
+        Ok(if let Some(type_param) = &type_param {
+            scopes.add_type_binding(
+                closure_scope,
+                &type_param,
+                TypeVariant::generic(type_param.clone()),
+            )?;
 
-    let binding = if let Some(type_param) = &type_param {
-        scopes.add_type_binding(
-            closure_scope,
-            &type_param,
-            TypeVariant::generic(type_param.clone()),
-        )?;
-
-        TypeVariant::Composite(CompositeType {
-            outer: NalaType::Struct(fields_from_literals(fields, scopes, closure_scope)?),
-            inner: vec![TypeVariant::generic(type_param.clone())],
-            generic_type_param: Some(type_param.clone()),
+            TypeVariant::Composite(CompositeType {
+                outer: NalaType::Struct(fields_from_literals(fields, scopes, closure_scope)?),
+                inner: vec![TypeVariant::generic(type_param.clone())],
+                generic_type_param: Some(type_param.clone()),
+            })
+        } else {
+            TypeVariant::Type(NalaType::Struct(fields_from_literals(
+                fields,
+                scopes,
+                closure_scope,
+            )?))
         })
-    } else {
-        TypeVariant::Type(NalaType::Struct(fields_from_literals(
-            fields,
-            scopes,
-            closure_scope,
-        )?))
-    };
+    })?;
 
     scopes.add_type_binding(current_scope, &ident, binding)?;
 
@@ -61,28 +61,28 @@ pub fn eval_enum(
     scopes: &mut Scopes,
     current_scope: usize,
 ) -> Result<Value, RuntimeError> {
-    let closure_scope = scopes.new_scope(Some(current_scope));
+    let binding = scopes.in_new_scope(Some(current_scope), |scopes, closure_scope| {
+        Ok(if let Some(type_param) = &type_param {
+            scopes.add_type_binding(
+                closure_scope,
+                &type_param,
+                TypeVariant::generic(type_param.clone()),
+            )?;
 
-    let binding = if let Some(type_param) = &type_param {
-        scopes.add_type_binding(
-            closure_scope,
-            &type_param,
-            TypeVariant::generic(type_param.clone()),
-        )?;
+            TypeVariant::Composite(CompositeType {
+                outer: NalaType::Enum(
+                    ident.to_string(),
+                    variants_from_literals(variants, scopes, closure_scope)?,
+                ),
+                inner: vec![TypeVariant::generic(type_param.clone())],
+                generic_type_param: Some(type_param.clone()),
+            })
+        } else {
+            let variants = variants_from_literals(variants, scopes, closure_scope)?;
 
-        TypeVariant::Composite(CompositeType {
-            outer: NalaType::Enum(
-                ident.to_string(),
-                variants_from_literals(variants, scopes, closure_scope)?,
-            ),
-            inner: vec![TypeVariant::generic(type_param.clone())],
-            generic_type_param: Some(type_param.clone()),
+            TypeVariant::Type(NalaType::Enum(ident.to_string(), variants))
         })
-    } else {
-        let variants = variants_from_literals(variants, scopes, closure_scope)?;
-
-        TypeVariant::Type(NalaType::Enum(ident.to_string(), variants))
-    };
+    })?;
 
     scopes.add_type_binding(current_scope, &ident, binding)?;
 
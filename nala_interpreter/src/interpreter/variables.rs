@@ -1,6 +1,10 @@
-use std::sync::Arc;
+use std::rc::Rc;
 
-use super::{arrays::eval_index, eval_expr, objects::*};
+use super::{
+    arrays::{eval_index, set_index},
+    eval_expr,
+    objects::*,
+};
 
 use crate::{
     ast::{types::type_literal_variant::TypeVariantLiteral, *},
@@ -16,9 +20,29 @@ pub fn eval_declare(
     value: Value,
     declared_type: Option<TypeVariantLiteral>,
     is_mutable: bool,
+    resolution: Resolution,
     scopes: &mut Scopes,
     current_scope: usize,
 ) -> Result<Value, RuntimeError> {
+    let declared_type = check_declare(ident, &value, declared_type, scopes, current_scope)?;
+    scopes.add_resolved_binding(
+        &ident,
+        resolution,
+        value,
+        declared_type,
+        current_scope,
+        is_mutable,
+    )
+}
+
+// Checks that `value` can be bound to `ident`, returning the declared type resolved, if any.
+pub(crate) fn check_declare(
+    ident: &str,
+    value: &Value,
+    declared_type: Option<TypeVariantLiteral>,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<Option<TypeVariant>, RuntimeError> {
     if let Value::Void = value {
         return Err(RuntimeError::new(
             "Cannot declare a variable with a value of type Void.",
@@ -29,29 +53,23 @@ pub fn eval_declare(
         let declared_type =
             TypeVariant::from_literal(declared_type_literal.clone(), scopes, current_scope)?;
 
-        if !fits_type(&value, &declared_type, scopes, current_scope)? {
-            let value_type = infer_type(&value, scopes, current_scope)?;
+        if !fits_type(value, &declared_type, scopes, current_scope)? {
+            let value_type = infer_type(value, scopes, current_scope)?;
             return Err(RuntimeError::new(&format!(
                 "Tried to declare variable `{ident}` with explicit type `{declared_type_literal}` but value `{value}` of type `{value_type}` does not fit that type.",
             )));
         }
 
-        scopes.add_binding(
-            &ident,
-            value,
-            Some(declared_type),
-            current_scope,
-            is_mutable,
-        )
+        Ok(Some(declared_type))
     } else {
-        let inferred_type = infer_type(&value, scopes, current_scope)?;
+        let inferred_type = infer_type(value, scopes, current_scope)?;
 
         if inferred_type.find_generic_type_param().is_some() {
-            return Err(RuntimeError::new(&format!(
+            Err(RuntimeError::new(&format!(
                 "Can't assign value of type `{inferred_type}` because its concrete type cannot be determined. Try declaring the type explicitly.",
-            )));
+            )))
         } else {
-            scopes.add_binding(&ident, value, None, current_scope, is_mutable)
+            Ok(None)
         }
     }
 }
@@ -64,77 +82,33 @@ pub fn eval_assign(
     ctx: &mut dyn IoContext,
 ) -> Result<Value, RuntimeError> {
     match variable {
-        PlaceExpression::Index(place, index_expr) => match &**place {
-            PlaceExpression::Index(_, _) => todo!(),
-            PlaceExpression::MemberAccess(place_expression, member) => {
-                let parent_obj = eval_place_expr(place_expression, scopes, current_scope, ctx)?;
-                let array = eval_member_access(&parent_obj, member)?;
-
-                let index =
-                    if let Value::Num(index) = eval_expr(index_expr, scopes, current_scope, ctx)? {
-                        index
-                    } else {
-                        todo!();
-                    };
-
-                if let Value::Array(array) = array {
-                    let array = Arc::clone(&array);
-                    let mut array = array.lock().unwrap();
-                    array[index as usize] = value.clone();
-                } else {
-                    Err(RuntimeError::new("Trying to index into a non-Array."))?
+        PlaceExpression::Index(place, index_expr) => {
+            // Assigning into an unbound identifier does nothing.
+            if let PlaceExpression::Identifier(ident, resolution) = &**place {
+                if !scopes.resolved_binding_exists(&ident, *resolution, current_scope) {
+                    return Ok(Value::Void);
                 }
             }
-            PlaceExpression::Identifier(ident) => {
-                if scopes.binding_exists(&ident, current_scope) {
-                    let index_result = eval_expr(&index_expr, scopes, current_scope, ctx)?;
-
-                    if let Value::Void = value {
-                        Err(RuntimeError::new("Cannot assign a value of type Void."))?;
-                    }
-
-                    let index = if let Value::Num(index) = index_result {
-                        index
-                    } else {
-                        Err(RuntimeError::new("Index does not resolve to a Number."))?
-                    };
-
-                    let array = scopes.get_value(&ident, current_scope)?;
-
-                    if let Value::Array(array) = array {
-                        let array = Arc::clone(&array);
-                        let mut array = array.lock().unwrap();
-                        array[index as usize] = value.clone();
-                    } else {
-                        Err(RuntimeError::new("Trying to index into a non-Array."))?
-                    }
-                }
-            }
-        },
-        PlaceExpression::Identifier(ident) => {
+
+            let collection = eval_place_expr(place, scopes, current_scope, ctx)?;
+            let index = eval_expr(index_expr, scopes, current_scope, ctx)?;
+            set_index(collection, index, value.clone(), scopes, current_scope)?;
+        }
+        PlaceExpression::Identifier(ident, resolution) => {
             if let Value::Void = value {
                 Err(RuntimeError::new("Cannot assign a value of type Void."))?;
             }
 
-            let existing = scopes.get_value(&ident, current_scope)?;
-
-            let existing_type = infer_type(&existing, scopes, current_scope)?;
-            let value_type = infer_type(&value, scopes, current_scope)?;
-
-            if existing_type == value_type {
-                scopes.mutate_value(&ident, current_scope, value.clone())?;
-            } else {
-                Err(RuntimeError::new(&format!(
-                    "Cannot assign a value of type {value_type} where {existing_type} is expected.",
-                )))?
-            }
+            let existing = scopes.get_resolved_value(&ident, *resolution, current_scope)?;
+            check_assign(&existing, value, scopes, current_scope)?;
+            scopes.mutate_resolved_value(&ident, *resolution, current_scope, value.clone())?;
         }
         PlaceExpression::MemberAccess(place_expression, member) => {
             let parent = eval_place_expr(place_expression, scopes, current_scope, ctx)?;
 
             if let Value::Object(parent) = parent {
-                let parent = Arc::clone(&parent);
-                let mut parent = parent.lock().unwrap();
+                let parent = Rc::clone(&parent);
+                let mut parent = parent.borrow_mut();
                 parent.insert(member.to_string(), value.clone());
             } else {
                 todo!()
@@ -145,6 +119,25 @@ pub fn eval_assign(
     Ok(Value::Void)
 }
 
+// Checks that `value` can replace `existing`, which requires both to be of the same type.
+pub(crate) fn check_assign(
+    existing: &Value,
+    value: &Value,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<(), RuntimeError> {
+    let existing_type = infer_type(existing, scopes, current_scope)?;
+    let value_type = infer_type(value, scopes, current_scope)?;
+
+    if existing_type == value_type {
+        Ok(())
+    } else {
+        Err(RuntimeError::new(&format!(
+            "Cannot assign a value of type {value_type} where {existing_type} is expected.",
+        )))
+    }
+}
+
 pub fn eval_place_expr(
     variable: &PlaceExpression,
     scopes: &mut Scopes,
@@ -156,7 +149,9 @@ pub fn eval_place_expr(
             let array = eval_place_expr(place, scopes, current_scope, ctx)?;
             eval_index(&array, expr, scopes, current_scope, ctx)
         }
-        PlaceExpression::Identifier(ident) => scopes.get_value(ident, current_scope),
+        PlaceExpression::Identifier(ident, resolution) => {
+            scopes.get_resolved_value(ident, *resolution, current_scope)
+        }
         PlaceExpression::MemberAccess(place_expression, member_access) => {
             let object = eval_place_expr(place_expression, scopes, current_scope, ctx)?;
             eval_member_access(&object, member_access)
@@ -0,0 +1,107 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+};
+
+use reqwest::{blocking::Client, Method};
+
+use super::{HttpRequest, HttpResponse, IoContext};
+
+// Gives scripts the real console, file system, environment and network. Hosts restrict scripts
+// through their capabilities rather than here.
+#[derive(Debug, Default)]
+pub struct ConsoleContext {
+    args: Vec<String>,
+}
+
+impl ConsoleContext {
+    pub fn new() -> ConsoleContext {
+        ConsoleContext::default()
+    }
+
+    pub fn with_args(args: Vec<String>) -> ConsoleContext {
+        ConsoleContext { args }
+    }
+}
+
+impl IoContext for ConsoleContext {
+    fn print(self: &mut Self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn read(self: &mut Self) -> Option<String> {
+        let mut line: String = String::new();
+
+        match io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line),
+        }
+    }
+
+    fn print_err(self: &mut Self, message: &str) {
+        eprintln!("{}", message);
+    }
+
+    fn args(self: &mut Self) -> Vec<String> {
+        self.args.clone()
+    }
+
+    fn env_var(self: &mut Self, name: &str) -> Option<String> {
+        env::var(name).ok()
+    }
+
+    fn read_file(self: &mut Self, path: &str) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn write_file(self: &mut Self, path: &str, contents: &str) -> io::Result<()> {
+        fs::write(path, contents)
+    }
+
+    fn append_file(self: &mut Self, path: &str, contents: &str) -> io::Result<()> {
+        OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)?
+            .write_all(contents.as_bytes())
+    }
+
+    fn exists(self: &mut Self, path: &str) -> bool {
+        fs::metadata(path).is_ok()
+    }
+
+    fn list_dir(self: &mut Self, path: &str) -> io::Result<Vec<String>> {
+        let mut names = fs::read_dir(path)?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+            .collect::<io::Result<Vec<String>>>()?;
+
+        names.sort();
+        Ok(names)
+    }
+
+    fn remove_file(self: &mut Self, path: &str) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn make_dir(self: &mut Self, path: &str) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+
+    fn http(self: &mut Self, request: &HttpRequest) -> Result<HttpResponse, String> {
+        let method = Method::from_bytes(request.method.as_bytes())
+            .map_err(|_| format!("Unknown HTTP method `{}`.", request.method))?;
+
+        let mut builder = Client::new().request(method, &request.url);
+
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+
+        let response = builder.send().map_err(|error| error.to_string())?;
+        let status = response.status().as_u16();
+        let body = response.text().map_err(|error| error.to_string())?;
+
+        Ok(HttpResponse { status, body })
+    }
+}
@@ -0,0 +1,102 @@
+mod console;
+mod test;
+
+use std::{
+    io,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub use self::{
+    console::ConsoleContext,
+    test::{TestContext, TranscriptEntry},
+};
+
+// The services a host provides to scripts. Every builtin reaching outside of the interpreter goes
+// through here, so that hosts decide what scripts can touch and tests can fake it. Hosts only need to
+// implement `print` and `read`: by default there are no files, environment variables or network.
+pub trait IoContext {
+    fn print(self: &mut Self, message: &str);
+    // Reads a line of input, or returns `None` once there's no more.
+    fn read(self: &mut Self) -> Option<String>;
+
+    // Reports an error, which by default is printed like any other output.
+    fn print_err(self: &mut Self, message: &str) {
+        self.print(message)
+    }
+
+    // Arguments passed to the script by the host, exposed to scripts via the `args` builtin.
+    fn args(self: &mut Self) -> Vec<String> {
+        vec![]
+    }
+
+    fn env_var(self: &mut Self, _name: &str) -> Option<String> {
+        None
+    }
+
+    // The time since the Unix epoch.
+    fn now(self: &mut Self) -> Duration {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+
+    // A random number in the range [0, 1).
+    fn random(self: &mut Self) -> f64 {
+        fastrand::f64()
+    }
+
+    fn read_file(self: &mut Self, _path: &str) -> io::Result<String> {
+        Err(unsupported())
+    }
+
+    fn write_file(self: &mut Self, _path: &str, _contents: &str) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn append_file(self: &mut Self, _path: &str, _contents: &str) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn exists(self: &mut Self, _path: &str) -> bool {
+        false
+    }
+
+    // The names of the entries of a directory, in order.
+    fn list_dir(self: &mut Self, _path: &str) -> io::Result<Vec<String>> {
+        Err(unsupported())
+    }
+
+    fn remove_file(self: &mut Self, _path: &str) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    // Creates a directory along with any missing parents.
+    fn make_dir(self: &mut Self, _path: &str) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    // Sends a request, returning an error if no response was received at all.
+    fn http(self: &mut Self, _request: &HttpRequest) -> Result<HttpResponse, String> {
+        Err(String::from("HTTP is not supported by this host."))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub body: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "The file system is not supported by this host.",
+    )
+}
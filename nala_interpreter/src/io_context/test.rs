@@ -0,0 +1,285 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    fmt, io,
+    time::Duration,
+};
+
+use fastrand::Rng;
+
+use super::{HttpRequest, HttpResponse, IoContext};
+
+// A deterministic context for tests. Output and input are recorded in the order they happen, and the
+// file system, environment, clock, random numbers and network are in-memory fakes set up with the
+// `mock_*` methods.
+#[derive(Clone, Debug)]
+pub struct TestContext {
+    outputs: Vec<String>,
+    errors: Vec<String>,
+    // Lines read in order, where `None` is the end of input.
+    inputs: VecDeque<Option<String>>,
+    transcript: Vec<TranscriptEntry>,
+    args: Vec<String>,
+    env: HashMap<String, String>,
+    now: Duration,
+    rng: Rng,
+    files: BTreeMap<String, String>,
+    dirs: BTreeSet<String>,
+    responses: HashMap<(String, String), HttpResponse>,
+    requests: Vec<HttpRequest>,
+}
+
+impl TestContext {
+    pub fn new() -> TestContext {
+        TestContext {
+            outputs: vec![],
+            errors: vec![],
+            inputs: VecDeque::new(),
+            transcript: vec![],
+            args: vec![],
+            env: HashMap::new(),
+            now: Duration::ZERO,
+            rng: Rng::with_seed(0),
+            files: BTreeMap::new(),
+            dirs: BTreeSet::new(),
+            responses: HashMap::new(),
+            requests: vec![],
+        }
+    }
+
+    pub fn get_output(self: &mut Self) -> Vec<&str> {
+        self.outputs
+            .iter()
+            .map(|s| s as &str)
+            .collect::<Vec<&str>>()
+            .clone()
+    }
+
+    pub fn get_errors(self: &Self) -> Vec<&str> {
+        self.errors.iter().map(|s| s as &str).collect()
+    }
+
+    // Queues lines to be read in order. Once they run out, reads reach the end of input.
+    pub fn mock_inputs(self: &mut Self, inputs: Vec<&str>) {
+        self.inputs
+            .extend(inputs.iter().map(|s| Some(s.to_string())))
+    }
+
+    // Queues the end of input, which the next read reaches even if more lines are queued after it.
+    pub fn mock_eof(self: &mut Self) {
+        self.inputs.push_back(None)
+    }
+
+    // Everything printed and read so far, in order.
+    pub fn get_transcript(self: &Self) -> &[TranscriptEntry] {
+        &self.transcript
+    }
+
+    // The transcript with one entry per line, as used by golden tests.
+    pub fn get_transcript_text(self: &Self) -> String {
+        self.transcript
+            .iter()
+            .map(|entry| format!("{entry}\n"))
+            .collect()
+    }
+
+    pub fn mock_args(self: &mut Self, args: Vec<&str>) {
+        self.args = args.iter().map(|s| s.to_string()).collect()
+    }
+
+    pub fn mock_env(self: &mut Self, name: &str, value: &str) {
+        self.env.insert(name.to_owned(), value.to_owned());
+    }
+
+    // Sets the time since the Unix epoch, which otherwise stays at zero.
+    pub fn mock_time(self: &mut Self, now: Duration) {
+        self.now = now;
+    }
+
+    // Reseeds the random numbers, which are otherwise seeded with zero.
+    pub fn mock_seed(self: &mut Self, seed: u64) {
+        self.rng = Rng::with_seed(seed);
+    }
+
+    // Adds a file, along with any directories above it.
+    pub fn mock_file(self: &mut Self, path: &str, contents: &str) {
+        if let Some(parent) = parent(path) {
+            self.add_dirs(parent);
+        }
+
+        self.files.insert(path.to_owned(), contents.to_owned());
+    }
+
+    pub fn get_file(self: &Self, path: &str) -> Option<&str> {
+        self.files.get(path).map(|contents| contents as &str)
+    }
+
+    // Responds to requests with `method` to `url`. Requests for which no response is mocked fail as
+    // though the host couldn't be reached.
+    pub fn mock_http(self: &mut Self, method: &str, url: &str, response: HttpResponse) {
+        self.responses
+            .insert((method.to_owned(), url.to_owned()), response);
+    }
+
+    // The requests sent so far, in order.
+    pub fn get_requests(self: &Self) -> &[HttpRequest] {
+        &self.requests
+    }
+
+    fn add_dirs(self: &mut Self, path: &str) {
+        let mut current = Some(path);
+
+        while let Some(path) = current {
+            self.dirs.insert(path.to_owned());
+            current = parent(path);
+        }
+    }
+
+    fn is_dir(self: &Self, path: &str) -> bool {
+        path.is_empty() || path == "." || self.dirs.contains(path)
+    }
+
+    fn check_parent(self: &Self, path: &str) -> io::Result<()> {
+        match parent(path) {
+            Some(parent) if !self.is_dir(parent) => Err(not_found(parent)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl IoContext for TestContext {
+    fn print(self: &mut Self, message: &str) {
+        self.outputs.push(message.to_owned());
+        self.transcript
+            .push(TranscriptEntry::Print(message.to_owned()));
+    }
+
+    fn read(self: &mut Self) -> Option<String> {
+        let input = self.inputs.pop_front().flatten();
+
+        self.transcript.push(match &input {
+            Some(input) => TranscriptEntry::Read(input.clone()),
+            None => TranscriptEntry::Eof,
+        });
+
+        input
+    }
+
+    fn print_err(self: &mut Self, message: &str) {
+        self.errors.push(message.to_owned());
+        self.transcript
+            .push(TranscriptEntry::PrintErr(message.to_owned()));
+    }
+
+    fn args(self: &mut Self) -> Vec<String> {
+        self.args.clone()
+    }
+
+    fn env_var(self: &mut Self, name: &str) -> Option<String> {
+        self.env.get(name).cloned()
+    }
+
+    fn now(self: &mut Self) -> Duration {
+        self.now
+    }
+
+    fn random(self: &mut Self) -> f64 {
+        self.rng.f64()
+    }
+
+    fn read_file(self: &mut Self, path: &str) -> io::Result<String> {
+        self.files.get(path).cloned().ok_or_else(|| not_found(path))
+    }
+
+    fn write_file(self: &mut Self, path: &str, contents: &str) -> io::Result<()> {
+        self.check_parent(path)?;
+        self.files.insert(path.to_owned(), contents.to_owned());
+        Ok(())
+    }
+
+    fn append_file(self: &mut Self, path: &str, contents: &str) -> io::Result<()> {
+        self.check_parent(path)?;
+        self.files
+            .entry(path.to_owned())
+            .or_default()
+            .push_str(contents);
+        Ok(())
+    }
+
+    fn exists(self: &mut Self, path: &str) -> bool {
+        self.files.contains_key(path) || self.is_dir(path)
+    }
+
+    fn list_dir(self: &mut Self, path: &str) -> io::Result<Vec<String>> {
+        if !self.is_dir(path) {
+            return Err(not_found(path));
+        }
+
+        let children = self
+            .files
+            .keys()
+            .chain(self.dirs.iter())
+            .filter(|child| {
+                parent(child) == Some(path) || (path.is_empty() && parent(child).is_none())
+            })
+            .map(|child| child.rsplit('/').next().unwrap().to_owned())
+            .collect::<BTreeSet<String>>();
+
+        Ok(children.into_iter().collect())
+    }
+
+    fn remove_file(self: &mut Self, path: &str) -> io::Result<()> {
+        self.files
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn make_dir(self: &mut Self, path: &str) -> io::Result<()> {
+        if self.files.contains_key(path) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("`{path}` is a file."),
+            ));
+        }
+
+        self.add_dirs(path);
+        Ok(())
+    }
+
+    fn http(self: &mut Self, request: &HttpRequest) -> Result<HttpResponse, String> {
+        self.requests.push(request.clone());
+
+        self.responses
+            .get(&(request.method.clone(), request.url.clone()))
+            .cloned()
+            .ok_or_else(|| format!("Could not connect to `{}`.", request.url))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TranscriptEntry {
+    Print(String),
+    PrintErr(String),
+    Read(String),
+    Eof,
+}
+
+impl fmt::Display for TranscriptEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TranscriptEntry::Print(message) => write!(f, "{message}"),
+            TranscriptEntry::PrintErr(message) => write!(f, "! {message}"),
+            TranscriptEntry::Read(input) => write!(f, "> {input}"),
+            TranscriptEntry::Eof => write!(f, "> <EOF>"),
+        }
+    }
+}
+
+// Paths are `/` separated. Top-level entries have no parent.
+fn parent(path: &str) -> Option<&str> {
+    path.rsplit_once('/').map(|(parent, _)| parent)
+}
+
+fn not_found(path: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("`{path}` does not exist."))
+}
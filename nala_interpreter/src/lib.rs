@@ -4,52 +4,358 @@ extern crate reqwest;
 extern crate serde;
 extern crate serde_json;
 
-use std::fs;
+use std::{fs, path::Path};
 
 pub mod ast;
 mod builtin_types;
 mod builtins;
+pub mod capabilities;
 pub mod errors;
+pub mod evaluator;
+pub mod formatter;
 pub mod interpreter;
 pub mod io_context;
 #[allow(dead_code)]
 mod lexer;
+pub mod limits;
+pub mod linter;
 pub mod parser;
+pub mod repl;
 pub mod resolved;
+pub mod resolver;
 pub mod scopes;
 pub mod types;
 pub mod utils;
+pub mod vm;
 
-use interpreter::*;
-use io_context::ConsoleContext;
+use formatter::format_code;
+use interpreter::{testing::eval_tests_with_limits, *};
+use io_context::{ConsoleContext, IoContext};
+use limits::{CancelHandle, Limits};
+use linter::{lint_code, Finding, LintConfig, Severity};
 use parser::*;
+use resolver::resolve;
 
-pub fn main(path: &str) -> () {
-    let code = fs::read_to_string(path);
-    let mut ctx = ConsoleContext {};
+// The engines scripts can run on: the tree-walking interpreter, or the bytecode compiler and VM.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Engine {
+    #[default]
+    Tree,
+    Vm,
+}
+
+// Parses and runs a script within `limits`, reporting any errors to stderr.
+// `source` names where the code came from for error messages. Returns whether the script ran to
+// completion.
+pub fn run(
+    code: String,
+    source: &str,
+    args: Vec<String>,
+    quiet: bool,
+    engine: Engine,
+    limits: Limits,
+) -> bool {
+    let mut ctx = ConsoleContext::with_args(args);
+
+    let mut program = match parse_code(code) {
+        Ok(program) => program,
+        Err(message) => {
+            eprintln!("Nala Parse Error:\n  file:\n    {source} \n  message:\n    {message}");
+            return false;
+        }
+    };
+
+    // Resolved ahead of running, as the engines would, so that these errors are reported as static
+    // ones rather than as happening at runtime.
+    if let Err(e) = resolve(&mut program) {
+        eprintln!(
+            "Nala Resolution Error:\n  file:\n    {source} \n  message:\n    {}",
+            e.message
+        );
+        return false;
+    }
+
+    let result = match engine {
+        Engine::Tree => eval_program_with_limits(program, &mut ctx, limits, CancelHandle::new()),
+        Engine::Vm => vm::run_program_with_limits(program, &mut ctx, limits, CancelHandle::new()),
+    };
+
+    match result {
+        Ok(_) => {
+            if !quiet {
+                println!("Execution completed.");
+            }
 
-    let code = match code {
-        Ok(code) => code,
+            true
+        }
+        Err(e) => {
+            ctx.print_err(&format!("Nala Runtime Error: {}", e.message));
+            false
+        }
+    }
+}
+
+// Checks that every Nala file in `paths` parses and resolves, recursing into directories. Returns
+// whether every file is valid.
+pub fn check(paths: &[String]) -> bool {
+    let files = match find_all_nala_files(paths) {
+        Ok(files) => files,
         Err(err) => {
-            println!("Error loading nala file: {}", err);
-            return;
+            eprintln!("Error loading nala file: {}", err);
+            return false;
         }
     };
 
-    let parse_result = parse_code(code);
+    let mut failed = 0;
 
-    if parse_result.is_err() {
-        let message = parse_result.unwrap_err();
-        println!(
-            "{}",
-            format!("Nala Parse Error:\n  file:\n    {path} \n  message:\n    {message}")
-        );
+    for path in files.iter() {
+        let result = fs::read_to_string(path)
+            .map_err(|err| format!("Error loading nala file: {}", err))
+            .and_then(parse_code);
+
+        let error = match result {
+            Ok(mut program) => resolve(&mut program)
+                .err()
+                .map(|e| ("Resolution", e.message)),
+            Err(message) => Some(("Parse", message)),
+        };
+
+        if let Some((kind, message)) = error {
+            eprintln!("Nala {kind} Error:\n  file:\n    {path} \n  message:\n    {message}");
+            failed += 1;
+        }
+    }
+
+    if failed == 0 {
+        println!("Checked {} files, no errors found.", files.len());
+    } else {
+        eprintln!("Checked {} files, {failed} had errors.", files.len());
+    }
+
+    failed == 0
+}
+
+// Formats every Nala file in `paths` in place, recursing into directories. With `check`, files are
+// only reported rather than rewritten. Returns whether every file was already formatted (when
+// checking) or was formatted successfully.
+pub fn fmt(paths: &[String], check: bool) -> bool {
+    let files = match find_all_nala_files(paths) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("Error loading nala file: {}", err);
+            return false;
+        }
+    };
+
+    let mut success = true;
+
+    for path in files.iter() {
+        let code = match fs::read_to_string(path) {
+            Ok(code) => code,
+            Err(err) => {
+                eprintln!("Error loading nala file: {}", err);
+                success = false;
+                continue;
+            }
+        };
+
+        let formatted = match format_code(&code) {
+            Ok(formatted) => formatted,
+            Err(message) => {
+                eprintln!("Nala Parse Error:\n  file:\n    {path} \n  message:\n    {message}");
+                success = false;
+                continue;
+            }
+        };
+
+        if formatted == code {
+            continue;
+        }
+
+        if check {
+            println!("Unformatted file: {path}");
+            success = false;
+        } else if let Err(err) = fs::write(path, formatted) {
+            eprintln!("Error writing nala file: {}", err);
+            success = false;
+        } else {
+            println!("Formatted {path}");
+        }
+    }
+
+    success
+}
+
+// Lints every Nala file in `paths`, recursing into directories. Findings are printed one per line,
+// or with `json` as a single JSON array of objects. Returns whether no file failed to parse and no
+// finding had error severity.
+pub fn lint(paths: &[String], config: &LintConfig, json: bool) -> bool {
+    #[derive(serde::Serialize)]
+    struct FileFinding<'a> {
+        file: &'a str,
+        #[serde(flatten)]
+        finding: Finding,
+    }
+
+    let files = match find_all_nala_files(paths) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("Error loading nala file: {}", err);
+            return false;
+        }
+    };
+
+    let mut success = true;
+    let mut all_findings = Vec::<FileFinding>::new();
+
+    for path in files.iter() {
+        let result = fs::read_to_string(path)
+            .map_err(|err| format!("Error loading nala file: {}", err))
+            .and_then(|code| lint_code(&code, config));
+
+        match result {
+            Ok(findings) => all_findings.extend(findings.into_iter().map(|finding| FileFinding {
+                file: path,
+                finding,
+            })),
+            Err(message) => {
+                eprintln!("Nala Parse Error:\n  file:\n    {path} \n  message:\n    {message}");
+                success = false;
+            }
+        }
+    }
+
+    if all_findings
+        .iter()
+        .any(|f| f.finding.severity == Severity::Error)
+    {
+        success = false;
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&all_findings).unwrap());
+    } else {
+        for FileFinding { file, finding } in all_findings.iter() {
+            let severity = match finding.severity {
+                Severity::Error => "error",
+                _ => "warning",
+            };
+
+            println!(
+                "{file}:{}: {severity}[{}]: {}",
+                finding.line, finding.rule, finding.message
+            );
+        }
+    }
+
+    success
+}
+
+// Discovers and runs the `test` blocks of every Nala file in `paths`, recursing into directories,
+// each within `limits`. Returns whether every test passed.
+pub fn test(paths: &[String], limits: Limits) -> bool {
+    let files = match find_all_nala_files(paths) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("Error loading nala file: {}", err);
+            return false;
+        }
+    };
+
+    let mut ctx = ConsoleContext::new();
+    let mut failures = Vec::<(String, String)>::new();
+    let mut passed = 0;
+
+    for path in files.iter() {
+        let code = match fs::read_to_string(path) {
+            Ok(code) => code,
+            Err(err) => {
+                failures.push((path.clone(), format!("Error loading nala file: {}", err)));
+                continue;
+            }
+        };
+
+        let program = match parse_code(code) {
+            Ok(program) => program,
+            Err(message) => {
+                failures.push((path.clone(), format!("Nala Parse Error:\n  {message}")));
+                continue;
+            }
+        };
+
+        let results = eval_tests_with_limits(program, &mut ctx, limits.clone());
 
-        return;
+        if results.is_empty() {
+            continue;
+        }
+
+        println!("\nrunning {} tests from {path}", results.len());
+
+        for result in results {
+            let name = format!("{path} :: {}", result.name);
+
+            match result.outcome {
+                Ok(()) => {
+                    println!("test {} ... ok", result.name);
+                    passed += 1;
+                }
+                Err(e) => {
+                    println!("test {} ... FAILED", result.name);
+                    failures.push((name, format!("{}: {}", e.kind, e.message)));
+                }
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+
+        for (name, message) in failures.iter() {
+            println!("\n---- {name} ----\n{message}");
+        }
+    }
+
+    let status = if failures.is_empty() { "ok" } else { "FAILED" };
+
+    println!(
+        "\ntest result: {status}. {passed} passed; {} failed",
+        failures.len()
+    );
+
+    failures.is_empty()
+}
+
+fn find_all_nala_files(paths: &[String]) -> std::io::Result<Vec<String>> {
+    let mut files = Vec::<String>::new();
+
+    for path in paths {
+        find_nala_files(Path::new(path), &mut files)?;
     }
 
-    match eval_program(parse_result.unwrap(), &mut ctx) {
-        Ok(_) => println!("Execution completed."),
-        Err(e) => println!("Nala Runtime Error: {}", e.message),
+    Ok(files)
+}
+
+fn find_nala_files(path: &Path, files: &mut Vec<String>) -> std::io::Result<()> {
+    if path.is_dir() {
+        let mut entries = fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        entries.sort();
+
+        for entry in entries {
+            if entry.is_dir() || entry.extension().map_or(false, |ext| ext == "nl") {
+                find_nala_files(&entry, files)?;
+            }
+        }
+    } else if path.exists() {
+        files.push(path.to_string_lossy().to_string());
+    } else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} does not exist", path.display()),
+        ));
     }
+
+    Ok(())
 }
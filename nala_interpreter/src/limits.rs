@@ -0,0 +1,159 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    capabilities::Capabilities,
+    errors::{ErrorKind, RuntimeError},
+};
+
+// Bounds on the resources a program may use, for hosts running code they don't trust. Each is
+// unlimited when `None`. Exceeding one raises an error of kind `ErrorKind::Limit`, which scripts
+// can't catch.
+#[derive(Clone, Debug, Default)]
+pub struct Limits {
+    // Steps are loop iterations and function calls, the only way a program can run for long.
+    pub max_steps: Option<u64>,
+    pub max_call_depth: Option<usize>,
+    // The most items an array, or fields an object, may hold.
+    pub max_collection_len: Option<usize>,
+    pub max_string_len: Option<usize>,
+    // How long a single evaluation may run for.
+    pub timeout: Option<Duration>,
+    // What the program's builtins may reach outside of the interpreter.
+    pub capabilities: Capabilities,
+}
+
+// Lets a host stop a running program from another thread. Once cancelled, the program raises an
+// error at its next step, as does every evaluation after it.
+#[derive(Clone, Debug, Default)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// Tracks a program's use of the resources bounded by its `Limits`.
+#[derive(Debug, Default)]
+pub struct Budget {
+    limits: Limits,
+    cancel: CancelHandle,
+    steps: u64,
+    call_depth: usize,
+    deadline: Option<Instant>,
+    // Shared with the builtins needing a capability, which check it when called.
+    capabilities: Rc<Cell<Capabilities>>,
+}
+
+impl Budget {
+    pub fn new(limits: Limits, cancel: CancelHandle) -> Self {
+        Budget {
+            capabilities: Rc::new(Cell::new(limits.capabilities)),
+            limits,
+            cancel,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.capabilities.set(limits.capabilities);
+        self.limits = limits;
+    }
+
+    pub(crate) fn capabilities(&self) -> Rc<Cell<Capabilities>> {
+        self.capabilities.clone()
+    }
+
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    // Resets the step count and deadline, which apply to each evaluation separately.
+    pub fn start(&mut self) {
+        self.steps = 0;
+        self.call_depth = 0;
+        self.deadline = self.limits.timeout.map(|timeout| Instant::now() + timeout);
+    }
+
+    pub fn step(&mut self) -> Result<(), RuntimeError> {
+        if self.cancel.is_cancelled() {
+            return Err(limit_error("Execution was cancelled."));
+        }
+
+        self.steps += 1;
+
+        if let Some(max_steps) = self.limits.max_steps {
+            if self.steps > max_steps {
+                return Err(limit_error(&format!(
+                    "Execution exceeded the limit of {max_steps} steps."
+                )));
+            }
+        }
+
+        if let Some(deadline) = self.deadline {
+            if Instant::now() > deadline {
+                return Err(limit_error("Execution timed out."));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn enter_call(&mut self) -> Result<(), RuntimeError> {
+        self.check_call_depth(self.call_depth)?;
+        self.call_depth += 1;
+        Ok(())
+    }
+
+    pub fn exit_call(&mut self) {
+        self.call_depth -= 1;
+    }
+
+    // Checks that a call can be made from `depth` calls deep, for callers tracking depth themselves.
+    pub fn check_call_depth(&self, depth: usize) -> Result<(), RuntimeError> {
+        match self.limits.max_call_depth {
+            Some(max) if depth >= max => Err(limit_error(&format!(
+                "Execution exceeded the maximum call depth of {max}."
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_collection_len(&self, len: usize) -> Result<(), RuntimeError> {
+        match self.limits.max_collection_len {
+            Some(max) if len > max => Err(limit_error(&format!(
+                "Collection of {len} items exceeded the limit of {max}."
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_string_len(&self, len: usize) -> Result<(), RuntimeError> {
+        match self.limits.max_string_len {
+            Some(max) if len > max => Err(limit_error(&format!(
+                "String of length {len} exceeded the limit of {max}."
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn limit_error(message: &str) -> RuntimeError {
+    RuntimeError::with_kind(ErrorKind::Limit, message)
+}
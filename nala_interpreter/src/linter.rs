@@ -0,0 +1,763 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::{
+    ast::{
+        branching::{IfElseChain, Match, MatchCase, TryCatch},
+        funcs::Call,
+        math::{Addition, Multiplication},
+        patterns::Pattern,
+        terms::Term,
+        *,
+    },
+    parser::parse_code,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Allow,
+    Warning,
+    Error,
+}
+
+pub struct Rule {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default_severity: Severity,
+}
+
+pub const UNUSED_CONST: &str = "unused-const";
+pub const SHADOWED_BINDING: &str = "shadowed-binding";
+pub const UNNECESSARY_MUT: &str = "unnecessary-mut";
+pub const UNREACHABLE_CODE: &str = "unreachable-code";
+pub const EMPTY_MATCH_ARM: &str = "empty-match-arm";
+
+pub const RULES: [Rule; 5] = [
+    Rule {
+        name: UNUSED_CONST,
+        description: "A `const` binding is never read.",
+        default_severity: Severity::Warning,
+    },
+    Rule {
+        name: SHADOWED_BINDING,
+        description: "A binding hides another of the same name from an enclosing scope.",
+        default_severity: Severity::Warning,
+    },
+    Rule {
+        name: UNNECESSARY_MUT,
+        description: "A `mut` binding is never reassigned, so it could be `const`.",
+        default_severity: Severity::Warning,
+    },
+    Rule {
+        name: UNREACHABLE_CODE,
+        description: "Code follows a `break` in the same block, so it never runs.",
+        default_severity: Severity::Warning,
+    },
+    Rule {
+        name: EMPTY_MATCH_ARM,
+        description: "A match arm has an empty block.",
+        default_severity: Severity::Warning,
+    },
+];
+
+// The severity of each rule. Rules set to `Allow` aren't reported.
+#[derive(Clone, Debug)]
+pub struct LintConfig {
+    severities: HashMap<&'static str, Severity>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            severities: RULES
+                .iter()
+                .map(|rule| (rule.name, rule.default_severity))
+                .collect(),
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn set(&mut self, rule: &str, severity: Severity) -> Result<(), String> {
+        match RULES.iter().find(|r| r.name == rule) {
+            Some(rule) => {
+                self.severities.insert(rule.name, severity);
+                Ok(())
+            }
+            None => Err(format!("Unknown lint rule `{rule}`.")),
+        }
+    }
+
+    pub fn severity(&self, rule: &str) -> Severity {
+        self.severities
+            .get(rule)
+            .copied()
+            .unwrap_or(Severity::Allow)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Finding {
+    pub rule: &'static str,
+    pub severity: Severity,
+    // 1-based.
+    pub line: usize,
+    pub message: String,
+}
+
+// Lints a script, returning its findings ordered by line. Findings can be suppressed with a
+// `// nala-ignore: rule-a, rule-b` comment, either trailing the offending line or on the line
+// before it. A bare `// nala-ignore` suppresses every rule.
+pub fn lint_code(code: &str, config: &LintConfig) -> Result<Vec<Finding>, String> {
+    let program = parse_code(code.to_owned())?;
+    let source = Source::new(code);
+
+    let lines = match &program {
+        Program::Block(lines) => lines,
+        Program::Lines(lines) => lines,
+    };
+
+    let mut locator = Locator {
+        source: &source,
+        cursor: 0,
+        lines: HashMap::new(),
+        cases: HashMap::new(),
+        catches: HashMap::new(),
+        after_break: HashMap::new(),
+    };
+
+    locator.block(lines);
+
+    let mut linter = Linter {
+        locator: &locator,
+        scopes: vec![],
+        findings: vec![],
+    };
+
+    linter.block(lines, vec![]);
+
+    let suppressions = source.suppressions();
+
+    let mut findings: Vec<Finding> = linter
+        .findings
+        .into_iter()
+        .filter_map(|(rule, line, message)| {
+            let severity = config.severity(rule);
+
+            let suppressed = suppressions.iter().any(|(suppressed_line, rules)| {
+                *suppressed_line == line && (rules.is_empty() || rules.iter().any(|r| r == rule))
+            });
+
+            if severity == Severity::Allow || suppressed {
+                None
+            } else {
+                Some(Finding {
+                    rule,
+                    severity,
+                    line,
+                    message,
+                })
+            }
+        })
+        .collect();
+
+    findings.sort_by_key(|finding| finding.line);
+    Ok(findings)
+}
+
+// The source with string contents and comments blanked out, so that keywords and identifiers can
+// be searched for without matching inside them.
+struct Source<'a> {
+    code: &'a str,
+    masked: String,
+    line_starts: Vec<usize>,
+    // The byte ranges of every comment.
+    comments: Vec<(usize, usize)>,
+}
+
+impl<'a> Source<'a> {
+    fn new(code: &'a str) -> Self {
+        let bytes = code.as_bytes();
+        let mut masked = bytes.to_vec();
+        let mut comments = vec![];
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let (start, end, is_comment) = match (bytes[i], bytes.get(i + 1)) {
+                // Keep the quotes, so that a string's position can still be matched.
+                (b'\'', _) => (
+                    i + 1,
+                    find_from(code, i + 1, "'").unwrap_or(code.len()),
+                    false,
+                ),
+                (b'/', Some(b'/')) => (i, find_from(code, i, "\n").unwrap_or(code.len()), true),
+                (b'/', Some(b'*')) => {
+                    let end = find_from(code, i + 2, "*/").map_or(code.len(), |end| end + 2);
+                    (i, end, true)
+                }
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+
+            for byte in masked[start..end].iter_mut() {
+                if *byte != b'\n' {
+                    *byte = b' ';
+                }
+            }
+
+            if is_comment {
+                comments.push((start, end));
+                i = end;
+            } else {
+                // Skip the closing quote.
+                i = end + 1;
+            }
+        }
+
+        let line_starts = std::iter::once(0)
+            .chain(code.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self {
+            code,
+            masked: String::from_utf8(masked).unwrap(),
+            line_starts,
+            comments,
+        }
+    }
+
+    fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset)
+    }
+
+    fn has_code(&self, line: usize) -> bool {
+        let start = self.line_starts[line - 1];
+        let end = self
+            .line_starts
+            .get(line)
+            .copied()
+            .unwrap_or(self.masked.len());
+
+        !self.masked[start..end].trim().is_empty()
+    }
+
+    // The lines suppressed by `nala-ignore` comments, and the rules suppressed on each. An empty
+    // list of rules suppresses them all.
+    fn suppressions(&self) -> Vec<(usize, Vec<String>)> {
+        let directive = Regex::new(r"^(//|/\*)\s*nala-ignore\b(:(.*?))?\s*(\*/)?$").unwrap();
+        let mut suppressions = vec![];
+
+        for (start, end) in self.comments.iter() {
+            let captures = match directive.captures(self.code[*start..*end].trim()) {
+                Some(captures) => captures,
+                None => continue,
+            };
+
+            let rules = captures
+                .get(3)
+                .map(|rules| {
+                    rules
+                        .as_str()
+                        .split(',')
+                        .map(|rule| rule.trim().to_owned())
+                        .filter(|rule| !rule.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let mut line = self.line(*start);
+
+            // A comment on a line of its own applies to the next line with code.
+            if self.masked[self.line_starts[line - 1]..*start]
+                .trim()
+                .is_empty()
+            {
+                line += 1;
+
+                while line < self.line_starts.len() && !self.has_code(line) {
+                    line += 1;
+                }
+            }
+
+            suppressions.push((line, rules));
+        }
+
+        suppressions
+    }
+}
+
+fn find_from(code: &str, from: usize, pattern: &str) -> Option<usize> {
+    code.get(from..)?.find(pattern).map(|i| from + i)
+}
+
+// The AST doesn't record where its nodes came from, so lines are found by searching the source for
+// each construct's keyword, in the order the constructs appear. Only constructs with a keyword can
+// be located this way, which covers everything the rules report on.
+struct Locator<'a> {
+    source: &'a Source<'a>,
+    cursor: usize,
+    lines: HashMap<*const Line, usize>,
+    cases: HashMap<*const MatchCase, usize>,
+    catches: HashMap<*const TryCatch, usize>,
+    // The line of the first code after each `break`.
+    after_break: HashMap<*const Line, usize>,
+}
+
+impl Locator<'_> {
+    fn block(&mut self, lines: &[Line]) {
+        for line in lines {
+            self.line(line);
+        }
+    }
+
+    fn line(&mut self, line: &Line) {
+        let pattern = match line {
+            Line::Break(_) => String::from(r"\bbreak\b"),
+            Line::Declare(ident, _, _, true, _) => format!(r"\bmut\s+{ident}\b"),
+            Line::Declare(ident, _, _, false, _) => format!(r"\bconst\s+{ident}\b"),
+            Line::Enum(ident, ..) => format!(r"\benum\s+{ident}\b"),
+            Line::For(ident, ..) => format!(r"\bfor\s+{ident}\b"),
+            Line::Func(func) => format!(r"\bfunc\s+{}\b", func.ident),
+            Line::IfElseChain(_) => String::from(r"\bif\b"),
+            Line::Match(_) => String::from(r"\bmatch\b"),
+            Line::Struct(ident, ..) => format!(r"\bstruct\s+{ident}\b"),
+            Line::Test(..) => String::from(r"\btest\s*'"),
+            Line::TryCatch(_) => String::from(r"\btry\b"),
+            Line::Wiles(..) => String::from(r"\bwiles\b"),
+            Line::Assign(..) | Line::Blank | Line::Comment(..) | Line::Expr(_) => return,
+        };
+
+        let found = self.find(&pattern);
+        self.lines.insert(line, found);
+
+        match line {
+            Line::Break(_) => {
+                // A `break` ends at its semicolon, since expressions can't contain one.
+                if let Some(end) = find_from(&self.source.masked, self.cursor, ";") {
+                    let next = self.source.masked[end + 1..]
+                        .find(|c: char| !c.is_whitespace())
+                        .map_or(self.source.masked.len(), |i| end + 1 + i);
+
+                    self.after_break.insert(line, self.source.line(next));
+                }
+            }
+            Line::For(_, _, block) | Line::Wiles(_, block) | Line::Test(_, block) => {
+                self.block(block)
+            }
+            Line::Func(func) => {
+                if let FuncVariant::Nala(block) = &*func.block {
+                    self.block(block);
+                }
+            }
+            Line::IfElseChain(chain) => {
+                self.block(&chain.block);
+
+                for else_if in chain.else_ifs.iter() {
+                    self.block(&else_if.block);
+                }
+
+                if let Some(else_block) = &chain.else_block {
+                    self.block(&else_block.block);
+                }
+            }
+            Line::Match(Match { cases, .. }) => {
+                for case in cases {
+                    let found = self.find("=>");
+                    self.cases.insert(case, found);
+                    self.block(&case.block);
+                }
+            }
+            Line::TryCatch(try_catch) => {
+                self.block(&try_catch.block);
+
+                let found = self.find(&format!(r"\bcatch\s*\(\s*{}\b", try_catch.ident));
+                self.catches.insert(try_catch, found);
+                self.block(&try_catch.catch_block);
+            }
+            _ => (),
+        }
+    }
+
+    // Finds the line of the next match of `pattern` and moves past it.
+    fn find(&mut self, pattern: &str) -> usize {
+        let regex = Regex::new(pattern).unwrap();
+
+        match regex.find_at(&self.source.masked, self.cursor) {
+            Some(found) => {
+                self.cursor = found.end();
+                self.source.line(found.start())
+            }
+            None => self.source.line(self.cursor),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum BindingKind {
+    Const,
+    Mut,
+    Other,
+}
+
+struct Binding {
+    ident: String,
+    kind: BindingKind,
+    line: usize,
+    used: bool,
+    reassigned: bool,
+}
+
+struct Linter<'a> {
+    locator: &'a Locator<'a>,
+    scopes: Vec<Vec<Binding>>,
+    findings: Vec<(&'static str, usize, String)>,
+}
+
+impl Linter<'_> {
+    // Lints a block in a new scope holding `bindings`. Function bodies are linted at the end of the
+    // block which declares them, since they can refer to bindings declared after them.
+    fn block(&mut self, lines: &[Line], bindings: Vec<Binding>) {
+        self.scopes.push(vec![]);
+
+        for binding in bindings {
+            self.declare(binding);
+        }
+
+        let mut funcs = vec![];
+        let mut after_break = None;
+
+        for line in lines {
+            if let Line::Blank | Line::Comment(..) = line {
+                continue;
+            }
+
+            if let Some(unreachable) = after_break.take() {
+                self.report(
+                    UNREACHABLE_CODE,
+                    unreachable,
+                    String::from("Unreachable code after `break`."),
+                );
+            }
+
+            if let Line::Break(_) = line {
+                after_break = self
+                    .locator
+                    .after_break
+                    .get(&(line as *const Line))
+                    .copied();
+            }
+
+            match line {
+                Line::Func(func) => {
+                    let line = self.line_of(line);
+                    self.declare(binding(&func.ident, BindingKind::Other, line));
+                    funcs.push((func, line));
+                }
+                line => self.line(line),
+            }
+        }
+
+        for (func, line) in funcs {
+            if let FuncVariant::Nala(block) = &*func.block {
+                let params = func
+                    .params
+                    .iter()
+                    .map(|param| binding(&param.ident, BindingKind::Other, line))
+                    .collect();
+
+                self.block(block, params);
+            }
+        }
+
+        for binding in self.scopes.pop().unwrap() {
+            if binding.ident.starts_with('_') {
+                continue;
+            }
+
+            match binding.kind {
+                BindingKind::Const if !binding.used => self.report(
+                    UNUSED_CONST,
+                    binding.line,
+                    format!("Constant `{}` is declared but never used.", binding.ident),
+                ),
+                BindingKind::Mut if !binding.reassigned => self.report(
+                    UNNECESSARY_MUT,
+                    binding.line,
+                    format!(
+                        "`{}` is declared `mut` but never reassigned.",
+                        binding.ident
+                    ),
+                ),
+                _ => (),
+            }
+        }
+    }
+
+    fn line(&mut self, line: &Line) {
+        match line {
+            Line::Assign(place, expr) => {
+                match place {
+                    PlaceExpression::Identifier(ident, _) => {
+                        if let Some(binding) = self.resolve(ident) {
+                            binding.reassigned = true;
+                        }
+                    }
+                    place => self.place(place),
+                }
+
+                self.expr(expr);
+            }
+            Line::Break(expr) | Line::Expr(expr) => self.expr(expr),
+            Line::Declare(ident, expr, _, is_mutable, _) => {
+                self.expr(expr);
+
+                let kind = if *is_mutable {
+                    BindingKind::Mut
+                } else {
+                    BindingKind::Const
+                };
+
+                let line = self.line_of(line);
+                self.declare(binding(ident, kind, line));
+            }
+            Line::For(ident, expr, block) => {
+                self.expr(expr);
+                let line = self.line_of(line);
+                self.block(block, vec![binding(ident, BindingKind::Other, line)]);
+            }
+            Line::IfElseChain(chain) => self.if_else_chain(chain),
+            Line::Match(Match { expr, cases }) => {
+                self.expr(expr);
+
+                for case in cases {
+                    self.match_case(case);
+                }
+            }
+            Line::Test(_, block) => self.block(block, vec![]),
+            Line::Wiles(expr, block) => {
+                self.expr(expr);
+                self.block(block, vec![]);
+            }
+            Line::TryCatch(try_catch) => {
+                self.block(&try_catch.block, vec![]);
+
+                let line = self
+                    .locator
+                    .catches
+                    .get(&(try_catch as *const TryCatch))
+                    .copied()
+                    .unwrap_or_default();
+
+                let ident = binding(&try_catch.ident, BindingKind::Other, line);
+                self.block(&try_catch.catch_block, vec![ident]);
+            }
+            Line::Blank | Line::Comment(..) | Line::Enum(..) | Line::Func(_) | Line::Struct(..) => {
+            }
+        }
+    }
+
+    fn if_else_chain(&mut self, chain: &IfElseChain) {
+        self.expr(&chain.cond);
+        self.block(&chain.block, vec![]);
+
+        for else_if in chain.else_ifs.iter() {
+            self.expr(&else_if.cond);
+            self.block(&else_if.block, vec![]);
+        }
+
+        if let Some(else_block) = &chain.else_block {
+            self.block(&else_block.block, vec![]);
+        }
+    }
+
+    fn match_case(&mut self, case: &MatchCase) {
+        let line = self
+            .locator
+            .cases
+            .get(&(case as *const MatchCase))
+            .copied()
+            .unwrap_or_default();
+
+        let Pattern::Variant(enum_ident, variant_ident, ident) = &case.pattern;
+
+        let is_empty = case
+            .block
+            .iter()
+            .all(|line| matches!(line, Line::Blank | Line::Comment(..)));
+
+        if is_empty {
+            self.report(
+                EMPTY_MATCH_ARM,
+                line,
+                format!("Match arm `{enum_ident}::{variant_ident}` has an empty block."),
+            );
+        }
+
+        let bindings = ident
+            .iter()
+            .map(|ident| binding(ident, BindingKind::Other, line))
+            .collect();
+
+        self.block(&case.block, bindings);
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Addition(addition) => self.addition(addition),
+            Expr::Eq(left, right) | Expr::Gt(left, right) | Expr::Lt(left, right) => {
+                self.expr(left);
+                self.addition(right);
+            }
+        }
+    }
+
+    fn addition(&mut self, addition: &Addition) {
+        match addition {
+            Addition::Add(left, right) | Addition::Sub(left, right) => {
+                self.addition(left);
+                self.multiplication(right);
+            }
+            Addition::Multiplication(multiplication) => self.multiplication(multiplication),
+        }
+    }
+
+    fn multiplication(&mut self, multiplication: &Multiplication) {
+        match multiplication {
+            Multiplication::Mult(left, right) | Multiplication::Div(left, right) => {
+                self.multiplication(left);
+                self.term(right);
+            }
+            Multiplication::Unary(Unary::Primary(primary)) => self.primary(primary),
+        }
+    }
+
+    fn term(&mut self, term: &Term) {
+        if let Term::Identifier(ident, _) = term {
+            self.reference(ident);
+        }
+    }
+
+    fn primary(&mut self, primary: &Primary) {
+        match primary {
+            Primary::Call(Call::Call(place, _, args)) => {
+                self.place(place);
+
+                for arg in args {
+                    self.expr(arg);
+                }
+            }
+            Primary::Call(Call::PlaceExpression(place)) => self.place(place),
+            Primary::Literal(_) => (),
+            Primary::Array(array) => {
+                for elem in array.elems.iter() {
+                    self.expr(elem);
+                }
+            }
+            Primary::Object(object) => {
+                for entry in object.entries.iter() {
+                    self.expr(&entry.value);
+                }
+            }
+            Primary::Map(map) => {
+                for entry in map.entries.iter() {
+                    self.expr(&entry.key);
+                    self.expr(&entry.value);
+                }
+            }
+            Primary::Set(set) => {
+                for elem in set.elems.iter() {
+                    self.expr(elem);
+                }
+            }
+            Primary::EnumVariant(_, _, data) => {
+                if let Some(data) = data {
+                    self.expr(data);
+                }
+            }
+        }
+    }
+
+    fn place(&mut self, place: &PlaceExpression) {
+        match place {
+            PlaceExpression::Identifier(ident, _) => self.reference(ident),
+            PlaceExpression::Index(place, index) => {
+                self.place(place);
+                self.expr(index);
+            }
+            PlaceExpression::MemberAccess(place, _) => self.place(place),
+        }
+    }
+
+    fn declare(&mut self, binding: Binding) {
+        // A binding already declared in the same scope, such as a function's parameter, is a
+        // duplicate which the resolver rejects rather than something shadowed.
+        let duplicate = self
+            .scopes
+            .last()
+            .unwrap()
+            .iter()
+            .any(|b| b.ident == binding.ident);
+
+        let shadowed = self.scopes[..self.scopes.len() - 1]
+            .iter()
+            .rev()
+            .find_map(|scope| scope.iter().rev().find(|b| b.ident == binding.ident))
+            .map(|shadowed| shadowed.line)
+            .filter(|_| !duplicate);
+
+        if let Some(shadowed) = shadowed {
+            if !binding.ident.starts_with('_') {
+                self.report(
+                    SHADOWED_BINDING,
+                    binding.line,
+                    format!(
+                        "`{}` shadows a binding declared on line {shadowed}.",
+                        binding.ident
+                    ),
+                );
+            }
+        }
+
+        self.scopes.last_mut().unwrap().push(binding);
+    }
+
+    fn reference(&mut self, ident: &str) {
+        if let Some(binding) = self.resolve(ident) {
+            binding.used = true;
+        }
+    }
+
+    fn resolve(&mut self, ident: &str) -> Option<&mut Binding> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.iter_mut().rev().find(|b| b.ident == ident))
+    }
+
+    fn line_of(&self, line: &Line) -> usize {
+        self.locator
+            .lines
+            .get(&(line as *const Line))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn report(&mut self, rule: &'static str, line: usize, message: String) {
+        self.findings.push((rule, line, message));
+    }
+}
+
+fn binding(ident: &str, kind: BindingKind, line: usize) -> Binding {
+    Binding {
+        ident: ident.to_owned(),
+        kind,
+        line,
+        used: false,
+        reassigned: false,
+    }
+}
@@ -1,33 +1,82 @@
 lalrpop_mod!(pub grammar);
 
-use grammar::ProgramParser;
-use lalrpop_util::{lalrpop_mod, ParseError};
+use grammar::{LinesParser, ProgramParser, TypeVariantParser};
+use lalrpop_util::{lalrpop_mod, lexer::Token, ParseError};
 
-use crate::ast::*;
+use crate::ast::{types::type_literal_variant::TypeVariantLiteral, *};
+
+pub enum PartialParseError {
+    // The input could become valid once more is appended to it, such as an unclosed block.
+    Incomplete,
+    Invalid(String),
+}
+
+// A parse error along with the byte range of the code it applies to.
+pub struct SyntaxError {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+}
 
 // TODO: Get error line numbers working properly.
 pub fn parse_code(code: String) -> Result<Program, String> {
-    match ProgramParser::new().parse(&code) {
-        Ok(parsed) => Ok(parsed),
-        Err(error) => match error {
-            ParseError::InvalidToken { location } => {
-                // NOTE: `location` is a single usize ignoring lines.
-                let snippet: String = code.chars().skip(location).collect();
-                Err(format!(
-                    "Invalid token at location {}:\n\n{}",
-                    location, snippet
-                ))
-            }
-            ParseError::UnrecognizedEOF { location, expected } => Err(format!(
-                "Unrecognized EOF at location {}. Expected one of: {:?}",
-                location, expected
-            )),
-            ParseError::UnrecognizedToken { token, expected } => Err(format!(
-                "Unrecognized token {:?}. Expected one of: {:?}",
-                token, expected
-            )),
-            _ => todo!("Unprocessed ParseError: {}", error.to_string()),
-        },
+    parse_program(&code).map_err(|error| error.message)
+}
+
+pub fn parse_program(code: &str) -> Result<Program, SyntaxError> {
+    ProgramParser::new().parse(code).map_err(|error| {
+        let (start, end) = match &error {
+            ParseError::InvalidToken { location } => (*location, *location + 1),
+            ParseError::UnrecognizedEOF { location, .. } => (*location, *location),
+            ParseError::UnrecognizedToken {
+                token: (start, _, end),
+                ..
+            } => (*start, *end),
+            ParseError::ExtraToken {
+                token: (start, _, end),
+            } => (*start, *end),
+            ParseError::User { .. } => (0, code.len()),
+        };
+
+        SyntaxError {
+            message: format_error(code, error),
+            start,
+            end,
+        }
+    })
+}
+
+// Parses a type annotation on its own, such as `Array<Number>`.
+pub fn parse_type(code: &str) -> Option<TypeVariantLiteral> {
+    TypeVariantParser::new().parse(code).ok()
+}
+
+// Parses a sequence of lines, distinguishing input which is merely incomplete from input which can
+// never be valid. Used to parse input a piece at a time, as in the REPL.
+pub fn parse_lines(code: &str) -> Result<Vec<Line>, PartialParseError> {
+    LinesParser::new().parse(code).map_err(|error| match error {
+        ParseError::UnrecognizedEOF { .. } => PartialParseError::Incomplete,
+        error => PartialParseError::Invalid(format_error(code, error)),
+    })
+}
+
+fn format_error(code: &str, error: ParseError<usize, Token<'_>, &str>) -> String {
+    match error {
+        ParseError::InvalidToken { location } => {
+            // NOTE: `location` is a single usize ignoring lines.
+            let snippet: String = code.chars().skip(location).collect();
+            format!("Invalid token at location {}:\n\n{}", location, snippet)
+        }
+        ParseError::UnrecognizedEOF { location, expected } => format!(
+            "Unrecognized EOF at location {}. Expected one of: {:?}",
+            location, expected
+        ),
+        ParseError::UnrecognizedToken { token, expected } => format!(
+            "Unrecognized token {:?}. Expected one of: {:?}",
+            token, expected
+        ),
+        ParseError::ExtraToken { token } => format!("Unexpected extra token {:?}.", token),
+        ParseError::User { error } => error.to_owned(),
     }
 }
 
@@ -0,0 +1,170 @@
+use crate::{
+    ast::{Line, Program},
+    errors::RuntimeError,
+    interpreter::Interpreter,
+    io_context::IoContext,
+    limits::Limits,
+    parser::{parse_lines, PartialParseError},
+};
+
+const HELP: &str = "\
+Enter Nala code to evaluate it. Blocks may span several lines.
+
+Commands:
+  :type <expr>  Evaluate an expression and show its type
+  :scope        Show every binding in scope
+  :help         Show this message
+  :quit         Exit the REPL";
+
+pub enum ReplResponse {
+    // Nothing to show, such as after a declaration.
+    Empty,
+    // The input so far is not yet a complete line, and more is expected.
+    Incomplete,
+    Output(String),
+    Error(String),
+    Exit,
+}
+
+// Evaluates input a line at a time against a single interpreter, so bindings persist between
+// inputs. Input which does not yet parse is buffered until it does, which allows blocks to span
+// several lines.
+pub struct Repl<C: IoContext> {
+    interpreter: Interpreter<C>,
+    buffer: String,
+}
+
+impl<C: IoContext> Repl<C> {
+    pub fn new(ctx: C) -> Result<Self, RuntimeError> {
+        Ok(Self {
+            interpreter: Interpreter::new(ctx)?,
+            buffer: String::new(),
+        })
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.interpreter.set_limits(limits);
+    }
+
+    pub fn eval_line(&mut self, input: &str) -> ReplResponse {
+        if self.buffer.is_empty() {
+            let trimmed = input.trim();
+
+            if trimmed.is_empty() {
+                return ReplResponse::Empty;
+            }
+
+            if let Some(command) = trimmed.strip_prefix(':') {
+                return self.eval_command(command);
+            }
+        }
+
+        self.buffer.push_str(input);
+        self.buffer.push('\n');
+
+        let lines = match self.parse_buffer() {
+            Ok(lines) => lines,
+            Err(PartialParseError::Incomplete) => return ReplResponse::Incomplete,
+            Err(PartialParseError::Invalid(message)) => {
+                self.buffer.clear();
+                return ReplResponse::Error(format!("ParseError: {message}"));
+            }
+        };
+
+        self.buffer.clear();
+        self.eval_parsed(lines)
+    }
+
+    // Discards any partially entered input, such as when the user interrupts a multi-line block.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+
+    pub fn is_incomplete(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+
+    pub fn interpreter(&mut self) -> &mut Interpreter<C> {
+        &mut self.interpreter
+    }
+
+    // A trailing semicolon is optional at the prompt, so `2 + 2` is accepted as an expression.
+    fn parse_buffer(&self) -> Result<Vec<Line>, PartialParseError> {
+        match parse_lines(&self.buffer) {
+            Err(PartialParseError::Incomplete) => {
+                parse_lines(&format!("{};", self.buffer.trim_end()))
+                    .map_err(|_| PartialParseError::Incomplete)
+            }
+            result => result,
+        }
+    }
+
+    fn eval_parsed(&mut self, lines: Vec<Line>) -> ReplResponse {
+        let mut output = Vec::<String>::new();
+
+        // Lines are evaluated one at a time, rather than with `eval_lines`, so that evaluation
+        // doesn't stop at the first line producing a value.
+        for line in lines {
+            match self.interpreter.eval_program(Program::Lines(vec![line])) {
+                Ok(value) if value.is_void() => (),
+                Ok(value) => output.push(value.to_string()),
+                Err(e) => {
+                    output.push(format!("{}: {}", e.kind, e.message));
+                    return ReplResponse::Error(output.join("\n"));
+                }
+            }
+        }
+
+        if output.is_empty() {
+            ReplResponse::Empty
+        } else {
+            ReplResponse::Output(output.join("\n"))
+        }
+    }
+
+    fn eval_command(&mut self, command: &str) -> ReplResponse {
+        let (name, rest) = match command.split_once(char::is_whitespace) {
+            Some((name, rest)) => (name, rest.trim()),
+            None => (command, ""),
+        };
+
+        match name {
+            "type" | "t" => self.eval_type_command(rest),
+            "scope" | "s" => ReplResponse::Output(
+                format!("{:?}", self.interpreter.scopes())
+                    .trim_end()
+                    .to_owned(),
+            ),
+            "help" | "h" => ReplResponse::Output(HELP.to_owned()),
+            "quit" | "q" => ReplResponse::Exit,
+            _ => ReplResponse::Error(format!(
+                "Unknown command `:{name}`. Enter `:help` for a list of commands."
+            )),
+        }
+    }
+
+    // Note that the expression is evaluated, including any side effects, in order to infer its type.
+    fn eval_type_command(&mut self, expr: &str) -> ReplResponse {
+        let code = format!("{};", expr.trim_end_matches(';'));
+
+        let lines = match parse_lines(&code) {
+            Ok(lines) => lines,
+            Err(_) => return ReplResponse::Error(String::from("Usage: `:type <expr>`")),
+        };
+
+        let expr = match lines.as_slice() {
+            [Line::Expr(expr)] => expr.clone(),
+            _ => return ReplResponse::Error(String::from("Usage: `:type <expr>`")),
+        };
+
+        let result = self
+            .interpreter
+            .eval_program(Program::Lines(vec![Line::Expr(expr)]))
+            .and_then(|value| self.interpreter.infer_type(&value));
+
+        match result {
+            Ok(value_type) => ReplResponse::Output(value_type.to_string()),
+            Err(e) => ReplResponse::Error(format!("{}: {}", e.kind, e.message)),
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use std::{fmt::Display, vec};
+
+use serde::{
+    de::{self, DeserializeOwned, IntoDeserializer, Visitor},
+    forward_to_deserialize_any,
+};
+
+use crate::errors::RuntimeError;
+
+use super::value::{EnumVariantValue, Value};
+
+// Converts a Nala value back into any deserializable Rust value. This is the inverse of `to_value`:
+// arrays and sets become sequences, objects and maps become structs or maps and the builtin
+// `Option` enum maps onto `Option`. Numbers with no fractional part may be read into integer types.
+pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, RuntimeError> {
+    T::deserialize(value)
+}
+
+impl de::Error for RuntimeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        RuntimeError::new(&msg.to_string())
+    }
+}
+
+fn is_option(variant: &EnumVariantValue) -> bool {
+    variant.enum_ident == "Option"
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = RuntimeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RuntimeError> {
+        match self {
+            Value::Array(items) => {
+                let items = items.borrow().clone();
+                visitor.visit_seq(SeqDeserializer::new(items))
+            }
+            Value::Bool(b) => visitor.visit_bool(b),
+            Value::Num(n) => {
+                if n.fract() == 0.0 && n.abs() < i64::MAX as f32 {
+                    visitor.visit_i64(n as i64)
+                } else {
+                    visitor.visit_f64(n as f64)
+                }
+            }
+            Value::Map(map) => {
+                let entries = map
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| (key.to_value(), value.clone()))
+                    .collect();
+
+                visitor.visit_map(MapDeserializer::new(entries))
+            }
+            Value::Object(fields) => {
+                let fields = fields
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| (Value::String(key.as_str().into()), value.clone()))
+                    .collect();
+
+                visitor.visit_map(MapDeserializer::new(fields))
+            }
+            Value::Set(set) => {
+                let elems = set.borrow().iter().map(|elem| elem.to_value()).collect();
+                visitor.visit_seq(SeqDeserializer::new(elems))
+            }
+            Value::String(s) => visitor.visit_str(&s),
+            Value::Variant(variant) if is_option(&variant) => match variant.data {
+                Some(data) => visitor.visit_some(*data),
+                None => visitor.visit_none(),
+            },
+            // Without a target enum type, variants are described the way most self-describing
+            // formats describe them: by name alone, or as a single entry map from name to data.
+            Value::Variant(EnumVariantValue {
+                variant_ident,
+                data,
+                ..
+            }) => match data {
+                Some(data) => visitor.visit_map(MapDeserializer::new(vec![(
+                    Value::String(variant_ident.into()),
+                    *data,
+                )])),
+                None => visitor.visit_string(variant_ident),
+            },
+            Value::Void => visitor.visit_unit(),
+            value => Err(RuntimeError::new(&format!(
+                "Cannot deserialize value `{value}`."
+            ))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RuntimeError> {
+        match self {
+            Value::Variant(variant) if is_option(&variant) => match variant.data {
+                Some(data) => visitor.visit_some(*data),
+                None => visitor.visit_none(),
+            },
+            Value::Void => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RuntimeError> {
+        match self {
+            Value::Variant(variant) if is_option(&variant) && variant.data.is_none() => {
+                visitor.visit_unit()
+            }
+            Value::Void => visitor.visit_unit(),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, RuntimeError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, RuntimeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, RuntimeError> {
+        match self {
+            Value::Variant(EnumVariantValue {
+                variant_ident,
+                data,
+                ..
+            }) => visitor.visit_enum(VariantDeserializer {
+                variant_ident,
+                data: data.map(|data| *data),
+            }),
+            Value::String(variant_ident) => visitor.visit_enum(VariantDeserializer {
+                variant_ident: variant_ident.to_string(),
+                data: None,
+            }),
+            value => Err(RuntimeError::new(&format!(
+                "Expected an enum variant, found `{value}`."
+            ))),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RuntimeError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RuntimeError> {
+        self.deserialize_any(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct SeqDeserializer {
+    items: vec::IntoIter<Value>,
+}
+
+impl SeqDeserializer {
+    fn new(items: Vec<Value>) -> Self {
+        Self {
+            items: items.into_iter(),
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqDeserializer {
+    type Error = RuntimeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, RuntimeError> {
+        match self.items.next() {
+            Some(item) => seed.deserialize(item).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MapDeserializer {
+    fields: vec::IntoIter<(Value, Value)>,
+    next_value: Option<Value>,
+}
+
+impl MapDeserializer {
+    fn new(fields: Vec<(Value, Value)>) -> Self {
+        Self {
+            fields: fields.into_iter(),
+            next_value: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapDeserializer {
+    type Error = RuntimeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, RuntimeError> {
+        match self.fields.next() {
+            Some((key, value)) => {
+                self.next_value = Some(value);
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, RuntimeError> {
+        let value = self
+            .next_value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
+
+struct VariantDeserializer {
+    variant_ident: String,
+    data: Option<Value>,
+}
+
+impl<'de> de::EnumAccess<'de> for VariantDeserializer {
+    type Error = RuntimeError;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), RuntimeError> {
+        let variant = seed.deserialize(self.variant_ident.clone().into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = RuntimeError;
+
+    fn unit_variant(self) -> Result<(), RuntimeError> {
+        match self.data {
+            None => Ok(()),
+            Some(data) => Err(RuntimeError::new(&format!(
+                "Variant `{0}` has unexpected data `{data}`.",
+                self.variant_ident
+            ))),
+        }
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, RuntimeError> {
+        seed.deserialize(self.expect_data()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, RuntimeError> {
+        de::Deserializer::deserialize_seq(self.expect_data()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, RuntimeError> {
+        de::Deserializer::deserialize_map(self.expect_data()?, visitor)
+    }
+}
+
+impl VariantDeserializer {
+    fn expect_data(self) -> Result<Value, RuntimeError> {
+        let VariantDeserializer {
+            variant_ident,
+            data,
+        } = self;
+
+        data.ok_or_else(|| {
+            RuntimeError::new(&format!("Expected variant `{variant_ident}` to have data."))
+        })
+    }
+}
@@ -1,14 +1,17 @@
+use std::rc::Rc;
+
 use crate::ast::FuncVariant;
+use crate::scopes::ScopeRef;
 
 use crate::types::type_variant::TypeVariant;
 
 #[derive(Debug, Clone)]
 pub struct FuncValue {
-    pub block: Box<FuncVariant>,
+    pub block: Rc<FuncVariant>,
     pub params: Vec<Param>,
     pub return_type: TypeVariant,
     pub type_param: Option<String>,
-    pub closure_scope: usize,
+    pub closure_scope: ScopeRef,
 }
 
 #[derive(Debug, Clone)]
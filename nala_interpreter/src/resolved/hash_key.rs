@@ -0,0 +1,94 @@
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+
+use crate::errors::RuntimeError;
+
+use super::value::{EnumVariantValue, Value};
+
+// A value used as the key of a map or an element of a set. Keys are copies of the values they were
+// made from, rather than references to them, as they must be hashable and must not change while in
+// the collection. Arrays and objects are therefore compared by their contents when used as keys, and
+// changing one afterwards leaves the key as it was.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum HashKey {
+    Array(Vec<HashKey>),
+    Bool(bool),
+    // Stored as bits, as floats are not hashable. Zero is normalized so that `0` and `-0` are the
+    // same key.
+    Num(u32),
+    // Fields are sorted by name, so that objects with the same fields are the same key.
+    Object(Vec<(String, HashKey)>),
+    String(Rc<str>),
+    Variant(String, String, Option<Box<HashKey>>),
+}
+
+impl HashKey {
+    pub fn from_value(value: &Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::Array(items) => Ok(Self::Array(
+                items
+                    .borrow()
+                    .iter()
+                    .map(Self::from_value)
+                    .collect::<Result<_, _>>()?,
+            )),
+            Value::Bool(b) => Ok(Self::Bool(*b)),
+            Value::Num(n) => Ok(Self::Num(if *n == 0.0 { 0.0f32 } else { *n }.to_bits())),
+            Value::Object(fields) => {
+                let mut fields = fields
+                    .borrow()
+                    .iter()
+                    .map(|(ident, value)| Ok((ident.clone(), Self::from_value(value)?)))
+                    .collect::<Result<Vec<_>, RuntimeError>>()?;
+
+                fields.sort_by(|a, b| a.0.cmp(&b.0));
+                Ok(Self::Object(fields))
+            }
+            Value::String(s) => Ok(Self::String(Rc::clone(s))),
+            Value::Variant(EnumVariantValue {
+                enum_ident,
+                variant_ident,
+                data,
+            }) => Ok(Self::Variant(
+                enum_ident.clone(),
+                variant_ident.clone(),
+                match data {
+                    Some(data) => Some(Box::new(Self::from_value(data)?)),
+                    None => None,
+                },
+            )),
+            value => Err(RuntimeError::new(&format!(
+                "Cannot use `{value:?}` as a map key or set element. Functions, maps and sets cannot be hashed."
+            ))),
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            Self::Array(items) => Value::Array(Rc::new(RefCell::new(
+                items.iter().map(Self::to_value).collect(),
+            ))),
+            Self::Bool(b) => Value::Bool(*b),
+            Self::Num(bits) => Value::Num(f32::from_bits(*bits)),
+            Self::Object(fields) => {
+                let fields: HashMap<String, Value> = fields
+                    .iter()
+                    .map(|(ident, key)| (ident.clone(), key.to_value()))
+                    .collect();
+
+                Value::Object(Rc::new(RefCell::new(fields)))
+            }
+            Self::String(s) => Value::String(Rc::clone(s)),
+            Self::Variant(enum_ident, variant_ident, data) => Value::Variant(EnumVariantValue {
+                enum_ident: enum_ident.clone(),
+                variant_ident: variant_ident.clone(),
+                data: data.as_ref().map(|data| Box::new(data.to_value())),
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for HashKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.to_value())
+    }
+}
@@ -1,4 +1,7 @@
+pub mod de;
 pub mod enum_variants;
 pub mod func_value;
+pub mod hash_key;
+pub mod ser;
 pub mod struct_field;
 pub mod value;
@@ -0,0 +1,401 @@
+use std::{cell::RefCell, collections::HashMap, fmt::Display, rc::Rc};
+
+use serde::{ser, Serialize};
+
+use crate::{
+    builtins::util::{build_none, build_some},
+    errors::RuntimeError,
+};
+
+use super::value::{EnumVariantValue, Value};
+
+// Converts any serializable Rust value into a Nala value. Sequences become arrays, structs and maps
+// become objects, and `Option` maps onto the builtin `Option` enum. Other Rust enums become variants
+// of a Nala enum with the same name, which must be declared before the value can be used in a script.
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, RuntimeError> {
+    value.serialize(ValueSerializer)
+}
+
+impl ser::Error for RuntimeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        RuntimeError::new(&msg.to_string())
+    }
+}
+
+fn build_variant(enum_ident: &str, variant_ident: &str, data: Option<Value>) -> Value {
+    Value::Variant(EnumVariantValue {
+        enum_ident: enum_ident.to_owned(),
+        variant_ident: variant_ident.to_owned(),
+        data: data.map(Box::new),
+    })
+}
+
+fn build_array(items: Vec<Value>) -> Value {
+    Value::Array(Rc::new(RefCell::new(items)))
+}
+
+fn build_object(fields: HashMap<String, Value>) -> Value {
+    Value::Object(Rc::new(RefCell::new(fields)))
+}
+
+// A `Number` is an `f32`, so integers past 2^24 would otherwise be silently rounded.
+fn build_integer(v: i128) -> Result<Value, RuntimeError> {
+    let num = v as f32;
+
+    if num as i128 == v {
+        Ok(Value::Num(num))
+    } else {
+        Err(RuntimeError::new(&format!(
+            "Cannot convert `{v}` to a `Number` without losing precision."
+        )))
+    }
+}
+
+pub struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = RuntimeError;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeArray;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeObject;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, RuntimeError> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, RuntimeError> {
+        Ok(Value::Num(v as f32))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, RuntimeError> {
+        Ok(Value::Num(v as f32))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, RuntimeError> {
+        build_integer(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, RuntimeError> {
+        build_integer(v.into())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, RuntimeError> {
+        Ok(Value::Num(v as f32))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, RuntimeError> {
+        Ok(Value::Num(v as f32))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, RuntimeError> {
+        build_integer(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, RuntimeError> {
+        build_integer(v.into())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, RuntimeError> {
+        Ok(Value::Num(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, RuntimeError> {
+        let num = v as f32;
+
+        // Doubles past the range of a `Number` would otherwise become infinite.
+        if num.is_finite() {
+            Ok(Value::Num(num))
+        } else {
+            Err(RuntimeError::new(&format!(
+                "Cannot convert `{v:e}` to a `Number`, as it's out of range."
+            )))
+        }
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, RuntimeError> {
+        Ok(Value::String(v.to_string().into()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, RuntimeError> {
+        Ok(Value::String(v.into()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, RuntimeError> {
+        Ok(build_array(
+            v.iter().map(|byte| Value::Num(*byte as f32)).collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Value, RuntimeError> {
+        Ok(build_none())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, RuntimeError> {
+        Ok(build_some(value.serialize(self)?))
+    }
+
+    // Nala has no unit value which can be stored, so this is treated as an absent value (which is
+    // also how JSON `null` arrives here).
+    fn serialize_unit(self) -> Result<Value, RuntimeError> {
+        Ok(build_none())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, RuntimeError> {
+        Ok(build_object(HashMap::new()))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, RuntimeError> {
+        Ok(build_variant(name, variant, None))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, RuntimeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, RuntimeError> {
+        Ok(build_variant(name, variant, Some(value.serialize(self)?)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, RuntimeError> {
+        Ok(SerializeArray {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, RuntimeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, RuntimeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, RuntimeError> {
+        Ok(SerializeArray {
+            variant: Some((name, variant)),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject, RuntimeError> {
+        Ok(SerializeObject {
+            variant: None,
+            fields: HashMap::new(),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeObject, RuntimeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeObject, RuntimeError> {
+        Ok(SerializeObject {
+            variant: Some((name, variant)),
+            fields: HashMap::new(),
+            next_key: None,
+        })
+    }
+}
+
+pub struct SerializeArray {
+    variant: Option<(&'static str, &'static str)>,
+    items: Vec<Value>,
+}
+
+impl SerializeArray {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RuntimeError> {
+        self.items.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value, RuntimeError> {
+        let array = build_array(self.items);
+
+        match self.variant {
+            Some((name, variant)) => Ok(build_variant(name, variant, Some(array))),
+            None => Ok(array),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Value;
+    type Error = RuntimeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RuntimeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, RuntimeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Value;
+    type Error = RuntimeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RuntimeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, RuntimeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Value;
+    type Error = RuntimeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RuntimeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, RuntimeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeArray {
+    type Ok = Value;
+    type Error = RuntimeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RuntimeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, RuntimeError> {
+        self.finish()
+    }
+}
+
+pub struct SerializeObject {
+    variant: Option<(&'static str, &'static str)>,
+    fields: HashMap<String, Value>,
+    next_key: Option<String>,
+}
+
+impl SerializeObject {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), RuntimeError> {
+        self.fields
+            .insert(key.to_owned(), value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value, RuntimeError> {
+        let object = build_object(self.fields);
+
+        match self.variant {
+            Some((name, variant)) => Ok(build_variant(name, variant, Some(object))),
+            None => Ok(object),
+        }
+    }
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = Value;
+    type Error = RuntimeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), RuntimeError> {
+        match key.serialize(ValueSerializer)? {
+            Value::String(key) => {
+                self.next_key = Some(key.to_string());
+                Ok(())
+            }
+            key => Err(RuntimeError::new(&format!(
+                "Object keys must be strings, found `{key:?}`."
+            ))),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RuntimeError> {
+        let key = self
+            .next_key
+            .take()
+            .expect("serialize_value called before serialize_key");
+
+        self.insert(&key, value)
+    }
+
+    fn end(self) -> Result<Value, RuntimeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = Value;
+    type Error = RuntimeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), RuntimeError> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value, RuntimeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeObject {
+    type Ok = Value;
+    type Error = RuntimeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), RuntimeError> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value, RuntimeError> {
+        self.finish()
+    }
+}
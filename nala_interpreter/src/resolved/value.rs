@@ -1,12 +1,10 @@
-use std::{
-    collections::HashMap,
-    fmt,
-    sync::{Arc, Mutex},
-};
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+
+use indexmap::{IndexMap, IndexSet};
 
 use crate::{ast::terms::Literal, errors::RuntimeError};
 
-use super::func_value::FuncValue;
+use super::{func_value::FuncValue, hash_key::HashKey};
 
 #[derive(Debug, Clone)]
 pub struct EnumVariantValue {
@@ -17,13 +15,17 @@ pub struct EnumVariantValue {
 
 #[derive(Clone)]
 pub enum Value {
-    Array(Arc<Mutex<Vec<Value>>>),
+    Array(Rc<RefCell<Vec<Value>>>),
     Bool(bool),
-    Func(FuncValue),
+    Func(Rc<FuncValue>),
     Variant(EnumVariantValue),
+    // Entries are kept in the order they were first inserted.
+    Map(Rc<RefCell<IndexMap<HashKey, Value>>>),
     Num(f32),
-    Object(Arc<Mutex<HashMap<String, Value>>>),
-    String(String),
+    Object(Rc<RefCell<HashMap<String, Value>>>),
+    // Elements are kept in the order they were first inserted.
+    Set(Rc<RefCell<IndexSet<HashKey>>>),
+    String(Rc<str>),
     Break(Box<Value>),
     Void,
 }
@@ -63,7 +65,7 @@ impl Value {
 
     pub fn as_string(&self) -> Option<String> {
         if let Value::String(string) = self {
-            Some(string.to_owned())
+            Some(string.to_string())
         } else {
             None
         }
@@ -91,19 +93,20 @@ impl fmt::Debug for Value {
             Value::Array(a) => {
                 let items = a
                     .clone()
-                    .lock()
-                    .unwrap()
+                    .borrow()
                     .iter()
                     .fold(String::new(), |acc, curr| format!("{acc}{0:?}, ", &curr));
 
                 write!(f, "[{items}]")
             }
             Value::Bool(b) => write!(f, "{}", b),
-            Value::Func(FuncValue {
-                params,
-                return_type,
-                ..
-            }) => {
+            Value::Func(func) => {
+                let FuncValue {
+                    params,
+                    return_type,
+                    ..
+                } = func.as_ref();
+
                 let mut params: Vec<String> = params
                     .to_vec()
                     .iter()
@@ -114,9 +117,10 @@ impl fmt::Debug for Value {
 
                 write!(f, "Func<{}>", params.join(", "))
             }
+            Value::Map(map) => write_map(f, map),
             Value::Num(n) => write!(f, "{}", n),
             Value::Object(fields) => {
-                let fields = fields.lock().unwrap();
+                let fields = fields.borrow();
                 let mut fields: Vec<(&String, &Value)> = fields.iter().collect();
                 fields.sort_by(|a, b| a.0.cmp(&b.0));
 
@@ -133,6 +137,7 @@ impl fmt::Debug for Value {
 
                 write!(f, "}}")
             }
+            Value::Set(set) => write_set(f, set),
             Value::String(s) => write!(f, "'{}'", s),
             Value::Variant(EnumVariantValue {
                 variant_ident,
@@ -159,8 +164,7 @@ impl fmt::Display for Value {
             Value::Array(a) => {
                 let items = a
                     .clone()
-                    .lock()
-                    .unwrap()
+                    .borrow()
                     .iter()
                     .fold(String::new(), |acc, curr| format!("{acc}{0:?}, ", &curr));
 
@@ -168,11 +172,13 @@ impl fmt::Display for Value {
             }
             Value::Bool(b) => write!(f, "{}", b),
             Value::Break(_) => write!(f, "<Break>"),
-            Value::Func(FuncValue {
-                params,
-                return_type,
-                ..
-            }) => {
+            Value::Func(func) => {
+                let FuncValue {
+                    params,
+                    return_type,
+                    ..
+                } = func.as_ref();
+
                 let mut params: Vec<String> = params
                     .to_vec()
                     .iter()
@@ -183,17 +189,18 @@ impl fmt::Display for Value {
 
                 write!(f, "Func<{}>", params.join(", "))
             }
+            Value::Map(map) => write_map(f, map),
             Value::Num(n) => write!(f, "{}", n),
             Value::Object(fields) => {
                 let fields = fields
-                    .lock()
-                    .unwrap()
+                    .borrow()
                     .iter()
                     .map(|(key, value)| format!("{}: {}", key, value))
                     .fold(String::new(), |a, b| a + &b + ", ");
 
                 write!(f, "{{ {fields} }}")
             }
+            Value::Set(set) => write_set(f, set),
             Value::String(t) => write!(f, "{}", t),
             Value::Variant(EnumVariantValue {
                 variant_ident,
@@ -213,6 +220,28 @@ impl fmt::Display for Value {
     }
 }
 
+// Maps and sets are written the same way whether displayed or debugged, with keys and elements
+// quoted as in literals.
+fn write_map(f: &mut fmt::Formatter, map: &RefCell<IndexMap<HashKey, Value>>) -> fmt::Result {
+    let entries = map
+        .borrow()
+        .iter()
+        .fold(String::new(), |acc, (key, value)| {
+            format!("{acc}{key:?}: {value:?}, ")
+        });
+
+    write!(f, "Map {{ {entries}}}")
+}
+
+fn write_set(f: &mut fmt::Formatter, set: &RefCell<IndexSet<HashKey>>) -> fmt::Result {
+    let elems = set
+        .borrow()
+        .iter()
+        .fold(String::new(), |acc, elem| format!("{acc}{elem:?}, "));
+
+    write!(f, "Set {{ {elems}}}")
+}
+
 impl PartialEq for Value {
     fn eq(&self, right: &Value) -> bool {
         match self {
@@ -237,11 +266,69 @@ impl PartialEq for Value {
                     false
                 }
             }
-            _ => todo!(),
-        }
-    }
+            Value::Array(left) => {
+                if let Value::Array(right) = right {
+                    if Rc::ptr_eq(left, right) {
+                        return true;
+                    }
+
+                    *left.borrow() == *right.borrow()
+                } else {
+                    false
+                }
+            }
+            Value::Map(left) => {
+                if let Value::Map(right) = right {
+                    if Rc::ptr_eq(left, right) {
+                        return true;
+                    }
+
+                    *left.borrow() == *right.borrow()
+                } else {
+                    false
+                }
+            }
+            Value::Object(left) => {
+                if let Value::Object(right) = right {
+                    if Rc::ptr_eq(left, right) {
+                        return true;
+                    }
 
-    fn ne(&self, _right: &Value) -> bool {
-        todo!()
+                    *left.borrow() == *right.borrow()
+                } else {
+                    false
+                }
+            }
+            Value::Set(left) => {
+                if let Value::Set(right) = right {
+                    if Rc::ptr_eq(left, right) {
+                        return true;
+                    }
+
+                    *left.borrow() == *right.borrow()
+                } else {
+                    false
+                }
+            }
+            Value::Variant(left) => {
+                if let Value::Variant(right) = right {
+                    left.enum_ident == right.enum_ident
+                        && left.variant_ident == right.variant_ident
+                        && left.data == right.data
+                } else {
+                    false
+                }
+            }
+            Value::Break(left) => {
+                if let Value::Break(right) = right {
+                    left == right
+                } else {
+                    false
+                }
+            }
+            Value::Void => right.is_void(),
+            // Functions have no meaningful notion of equality.
+            Value::Func(_) => false,
+        }
     }
 }
@@ -0,0 +1,327 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use crate::{
+    ast::{branching::*, funcs::*, math::*, terms::*, *},
+    errors::RuntimeError,
+    scopes::binding_exists_error,
+};
+
+// Resolves every identifier in `program` to the binding it refers to, so that the interpreter can
+// find bindings by slot rather than by name. Declaring a binding twice in the same scope, or using
+// one before it's declared, is reported here before the program runs.
+pub fn resolve(program: &mut Program) -> Result<(), RuntimeError> {
+    let lines = match program {
+        Program::Block(lines) => lines,
+        Program::Lines(lines) => lines,
+    };
+
+    let mut resolver = Resolver { scopes: vec![] };
+    resolver.block(lines, ScopeKind::Global, &[])
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ScopeKind {
+    Global,
+    Block,
+    // A scope whose code only runs once the scopes enclosing it have been evaluated, such as a
+    // function body or a test. Identifiers there can refer to bindings declared after them.
+    Deferred,
+}
+
+// Mirrors a scope the interpreter creates at runtime, so that depths found here match the scopes
+// walked there.
+struct Scope {
+    kind: ScopeKind,
+    // Every binding declared in the scope, to its slot. These are all assigned when the scope is
+    // opened, so that deferred code can refer to bindings declared later.
+    slots: HashMap<String, usize>,
+    // The bindings declared so far.
+    declared: HashSet<String>,
+}
+
+struct Resolver {
+    scopes: Vec<Scope>,
+}
+
+impl Resolver {
+    // Resolves `lines` in a new scope, in which `bound` are declared ahead of the lines themselves,
+    // as for parameters and loop variables.
+    fn block(
+        &mut self,
+        lines: &mut [Line],
+        kind: ScopeKind,
+        bound: &[String],
+    ) -> Result<(), RuntimeError> {
+        self.scopes.push(Scope {
+            kind,
+            slots: HashMap::new(),
+            declared: HashSet::new(),
+        });
+
+        for ident in bound {
+            self.declare(ident)?;
+        }
+
+        collect_declarations(lines, &mut self.scopes.last_mut().unwrap().slots);
+
+        for line in lines.iter_mut() {
+            self.line(line)?;
+        }
+
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn line(&mut self, line: &mut Line) -> Result<(), RuntimeError> {
+        match line {
+            Line::Assign(place, expr) => {
+                self.expr(expr)?;
+                self.place(place)?;
+            }
+            Line::Break(expr) | Line::Expr(expr) => self.expr(expr)?,
+            Line::Declare(ident, expr, _, _, resolution) => {
+                self.expr(expr)?;
+                *resolution = self.declare(ident)?;
+            }
+            Line::For(ident, expr, block) => {
+                self.expr(expr)?;
+                self.block(block, ScopeKind::Block, &[ident.clone()])?;
+            }
+            Line::Func(func) => self.func(func)?,
+            Line::IfElseChain(chain) => {
+                let IfElseChain {
+                    cond,
+                    block,
+                    else_ifs,
+                    else_block,
+                } = &mut **chain;
+
+                self.expr(cond)?;
+                self.block(block, ScopeKind::Block, &[])?;
+
+                for else_if in else_ifs.iter_mut() {
+                    self.expr(&mut else_if.cond)?;
+                    self.block(&mut else_if.block, ScopeKind::Block, &[])?;
+                }
+
+                if let Some(else_block) = else_block {
+                    self.block(&mut else_block.block, ScopeKind::Block, &[])?;
+                }
+            }
+            Line::Match(Match { expr, cases }) => {
+                self.expr(expr)?;
+
+                for MatchCase { pattern, block } in cases.iter_mut() {
+                    let patterns::Pattern::Variant(_, _, bound) = pattern;
+                    let bound: Vec<String> = bound.iter().cloned().collect();
+                    self.block(block, ScopeKind::Block, &bound)?;
+                }
+            }
+            Line::Test(_, block) => self.block(block, ScopeKind::Deferred, &[])?,
+            Line::TryCatch(TryCatch {
+                block,
+                ident,
+                catch_block,
+            }) => {
+                self.block(block, ScopeKind::Block, &[])?;
+                self.block(catch_block, ScopeKind::Block, &[ident.clone()])?;
+            }
+            // Wiles loops don't get a scope of their own, so their bodies declare into the
+            // enclosing scope.
+            Line::Wiles(expr, block) => {
+                self.expr(expr)?;
+
+                for line in block.iter_mut() {
+                    self.line(line)?;
+                }
+            }
+            Line::Blank | Line::Comment(..) | Line::Enum(..) | Line::Struct(..) => (),
+        }
+
+        Ok(())
+    }
+
+    // Functions get a scope holding their type parameter when declared, and another holding their
+    // parameters each time they're called.
+    fn func(&mut self, func: &mut FuncDeclare) -> Result<(), RuntimeError> {
+        func.resolution = self.declare(&func.ident)?;
+
+        if let FuncVariant::Nala(block) = Rc::make_mut(&mut func.block) {
+            let params: Vec<String> = func.params.iter().map(|p| p.ident.clone()).collect();
+
+            self.scopes.push(Scope {
+                kind: ScopeKind::Block,
+                slots: HashMap::new(),
+                declared: HashSet::new(),
+            });
+
+            self.block(block, ScopeKind::Deferred, &params)?;
+            self.scopes.pop();
+        }
+
+        Ok(())
+    }
+
+    fn expr(&mut self, expr: &mut Expr) -> Result<(), RuntimeError> {
+        match expr {
+            Expr::Addition(addition) => self.addition(addition),
+            Expr::Eq(left, right) | Expr::Gt(left, right) | Expr::Lt(left, right) => {
+                self.expr(left)?;
+                self.addition(right)
+            }
+        }
+    }
+
+    fn addition(&mut self, addition: &mut Addition) -> Result<(), RuntimeError> {
+        match addition {
+            Addition::Add(left, right) | Addition::Sub(left, right) => {
+                self.addition(left)?;
+                self.multiplication(right)
+            }
+            Addition::Multiplication(multiplication) => self.multiplication(multiplication),
+        }
+    }
+
+    fn multiplication(&mut self, multiplication: &mut Multiplication) -> Result<(), RuntimeError> {
+        match multiplication {
+            Multiplication::Mult(left, right) | Multiplication::Div(left, right) => {
+                self.multiplication(left)?;
+                self.term(right)
+            }
+            Multiplication::Unary(Unary::Primary(primary)) => self.primary(primary),
+        }
+    }
+
+    fn term(&mut self, term: &mut Term) -> Result<(), RuntimeError> {
+        if let Term::Identifier(ident, resolution) = term {
+            *resolution = self.lookup(ident)?;
+        }
+
+        Ok(())
+    }
+
+    fn primary(&mut self, primary: &mut Primary) -> Result<(), RuntimeError> {
+        match primary {
+            Primary::Call(Call::Call(place, _, args)) => {
+                self.place(place)?;
+
+                for arg in args.iter_mut() {
+                    self.expr(arg)?;
+                }
+            }
+            Primary::Call(Call::PlaceExpression(place)) => self.place(place)?,
+            Primary::Literal(_) => (),
+            Primary::Array(array) => {
+                for elem in array.elems.iter_mut() {
+                    self.expr(elem)?;
+                }
+            }
+            Primary::Object(object) => {
+                for entry in object.entries.iter_mut() {
+                    self.expr(&mut entry.value)?;
+                }
+            }
+            Primary::Map(map) => {
+                for entry in map.entries.iter_mut() {
+                    self.expr(&mut entry.key)?;
+                    self.expr(&mut entry.value)?;
+                }
+            }
+            Primary::Set(set) => {
+                for elem in set.elems.iter_mut() {
+                    self.expr(elem)?;
+                }
+            }
+            Primary::EnumVariant(_, _, data) => {
+                if let Some(data) = data {
+                    self.expr(data)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn place(&mut self, place: &mut PlaceExpression) -> Result<(), RuntimeError> {
+        match place {
+            PlaceExpression::Identifier(ident, resolution) => *resolution = self.lookup(ident)?,
+            PlaceExpression::Index(place, index) => {
+                self.place(place)?;
+                self.expr(index)?;
+            }
+            PlaceExpression::MemberAccess(place, _) => self.place(place)?,
+        }
+
+        Ok(())
+    }
+
+    fn declare(&mut self, ident: &str) -> Result<Resolution, RuntimeError> {
+        let scope = self.scopes.last_mut().unwrap();
+
+        if !scope.declared.insert(ident.to_owned()) {
+            return Err(binding_exists_error(ident));
+        }
+
+        let next_slot = scope.slots.len();
+        let slot = *scope.slots.entry(ident.to_owned()).or_insert(next_slot);
+
+        Ok(match scope.kind {
+            ScopeKind::Global => Resolution::Global,
+            _ => Resolution::Local { depth: 0, slot },
+        })
+    }
+
+    // Finds the innermost binding for `ident` that has been declared by the time it's used.
+    // Identifiers with no binding at all are left to be looked up by name, as they may be builtins,
+    // which live in a prelude scope above the program's, or globals added by the host. Declaring a
+    // global named after a builtin shadows it.
+    fn lookup(&self, ident: &str) -> Result<Resolution, RuntimeError> {
+        let mut deferred = false;
+        let mut declared_later = false;
+
+        for (depth, scope) in self.scopes.iter().rev().enumerate() {
+            if let Some(&slot) = scope.slots.get(ident) {
+                if deferred || scope.declared.contains(ident) {
+                    return Ok(match scope.kind {
+                        ScopeKind::Global => Resolution::Global,
+                        _ => Resolution::Local { depth, slot },
+                    });
+                }
+
+                declared_later = true;
+            }
+
+            deferred |= scope.kind == ScopeKind::Deferred;
+        }
+
+        if declared_later {
+            Err(RuntimeError::new(&format!(
+                "Identifier '{ident}' was used before its declaration."
+            )))
+        } else {
+            Ok(Resolution::Global)
+        }
+    }
+}
+
+// Collects the bindings declared directly in `lines`, including those in the bodies of wiles loops,
+// which share their enclosing scope.
+fn collect_declarations(lines: &[Line], slots: &mut HashMap<String, usize>) {
+    for line in lines {
+        let ident = match line {
+            Line::Declare(ident, ..) => ident,
+            Line::Func(func) => &func.ident,
+            Line::Wiles(_, block) => {
+                collect_declarations(block, slots);
+                continue;
+            }
+            _ => continue,
+        };
+
+        let next_slot = slots.len();
+        slots.entry(ident.clone()).or_insert(next_slot);
+    }
+}
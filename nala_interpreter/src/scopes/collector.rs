@@ -0,0 +1,139 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::{ast::FuncVariant, resolved::value::Value};
+
+use super::{scope::ScopeState, Scopes};
+
+impl Scopes {
+    // Frees released scopes that are only kept alive by each other, such as a scope holding a
+    // function that closes over it. References to scopes from the bindings of released scopes are
+    // counted, and any scope referenced from anywhere else (the bindings of active scopes, or values
+    // held by the interpreter itself) is kept, along with its parents and everything its bindings
+    // refer to. The rest can't be reached, and are freed.
+    pub(super) fn collect(&mut self) {
+        let scopes = &self.scopes;
+        self.retained
+            .retain(|scope| scopes[*scope].state == ScopeState::Released);
+
+        // A scope freed while retained may have been reused and retained again since.
+        self.retained.sort_unstable();
+        self.retained.dedup();
+
+        let mut internal = HashMap::<usize, usize>::new();
+
+        for scope in self.retained.iter() {
+            for value in self.scopes[*scope].values() {
+                visit_scope_refs(value, &mut |referenced| {
+                    *internal.entry(referenced).or_default() += 1;
+                });
+            }
+        }
+
+        let mut pending: Vec<usize> = self
+            .scopes
+            .iter()
+            .enumerate()
+            .filter(|(index, scope)| match scope.state {
+                ScopeState::Active => true,
+                ScopeState::Released => {
+                    let pins = Rc::strong_count(&scope.pin) - 1;
+                    pins > internal.get(index).copied().unwrap_or_default()
+                }
+                ScopeState::Free => false,
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut live = vec![false; self.scopes.len()];
+
+        while let Some(scope) = pending.pop() {
+            let mut current = Some(scope);
+
+            while let Some(index) = current {
+                if live[index] {
+                    break;
+                }
+
+                live[index] = true;
+
+                if self.scopes[index].state == ScopeState::Released {
+                    for value in self.scopes[index].values() {
+                        visit_scope_refs(value, &mut |referenced| pending.push(referenced));
+                    }
+                }
+
+                current = self.scopes[index].parent;
+            }
+        }
+
+        let (kept, garbage): (Vec<usize>, Vec<usize>) =
+            self.retained.iter().partition(|scope| live[**scope]);
+
+        // Contents are only dropped once every scope is freed, as dropping them unpins others.
+        let mut freed = Vec::with_capacity(garbage.len());
+
+        for scope in garbage {
+            let scope = self.free_scope(scope);
+
+            if let Some(parent) = scope.parent {
+                if live[parent] {
+                    self.scopes[parent].children -= 1;
+                }
+            }
+
+            freed.push(scope);
+        }
+
+        self.retained = kept;
+    }
+}
+
+// Calls `visit` with every scope referenced by a function within `value`. Arrays, maps, objects,
+// functions and the bindings captured by compiled functions are only looked into when `value` holds
+// the only reference to them, as any other reference could be held from outside of the scopes collected.
+// The scopes referenced from shared values are left alive.
+fn visit_scope_refs(value: &Value, visit: &mut impl FnMut(usize)) {
+    match value {
+        Value::Array(array) if Rc::strong_count(array) == 1 => {
+            for value in array.borrow().iter() {
+                visit_scope_refs(value, visit);
+            }
+        }
+        Value::Map(map) if Rc::strong_count(map) == 1 => {
+            for value in map.borrow().values() {
+                visit_scope_refs(value, visit);
+            }
+        }
+        Value::Object(object) if Rc::strong_count(object) == 1 => {
+            for value in object.borrow().values() {
+                visit_scope_refs(value, visit);
+            }
+        }
+        Value::Func(func) if Rc::strong_count(func) == 1 => {
+            if func.closure_scope.pin.is_some() {
+                visit(func.closure_scope.id);
+            }
+
+            if let FuncVariant::Compiled(closure) = func.block.as_ref() {
+                if Rc::strong_count(&closure.captures) > 1 {
+                    return;
+                }
+
+                for cell in closure.captures.iter() {
+                    if Rc::strong_count(cell) == 1 {
+                        if let Some(binding) = cell.borrow().as_ref() {
+                            visit_scope_refs(&binding.value, visit);
+                        }
+                    }
+                }
+            }
+        }
+        Value::Variant(variant) => {
+            if let Some(data) = &variant.data {
+                visit_scope_refs(data, visit);
+            }
+        }
+        Value::Break(value) => visit_scope_refs(value, visit),
+        _ => (),
+    }
+}
@@ -1,32 +1,152 @@
+mod collector;
 mod scope;
 pub mod value_binding;
 
-use std::fmt;
+use std::{fmt, rc::Rc};
 
-use crate::{errors::*, resolved::value::Value, types::type_variant::TypeVariant};
+use crate::{
+    ast::Resolution, errors::*, limits::Budget, resolved::value::Value,
+    types::type_variant::TypeVariant,
+};
 
-use self::{scope::Scope, value_binding::ValueBinding};
+use self::{
+    scope::{Scope, ScopeState},
+    value_binding::ValueBinding,
+};
 
+// The fewest released scopes still referenced at which they're checked for cycles, see `collect`.
+const MIN_COLLECTION_THRESHOLD: usize = 256;
+
+// Scopes are kept in an arena and referred to by index. Each is released by the code that created
+// it once that code is done with it, and freed for reuse as soon as no closure or child scope still
+// refers to it.
 pub struct Scopes {
     scopes: Vec<Scope>,
+    free: Vec<usize>,
+    // Scopes released while still referenced, which are freed once they no longer are.
+    retained: Vec<usize>,
+    collection_threshold: usize,
+    // Kept alongside the scopes as both are needed wherever code is evaluated.
+    pub(crate) budget: Budget,
+}
+
+// A reference to a scope held by a function closing over it, which keeps the scope and its parents
+// alive for as long as the function is. The default refers to no scope in particular, for builtins
+// yet to be declared.
+#[derive(Debug, Clone, Default)]
+pub struct ScopeRef {
+    id: usize,
+    pin: Option<Rc<()>>,
+}
+
+impl ScopeRef {
+    pub fn id(&self) -> usize {
+        self.id
+    }
 }
 
 impl Scopes {
     pub fn new() -> Scopes {
-        Scopes { scopes: vec![] }
+        Scopes {
+            scopes: vec![],
+            free: vec![],
+            retained: vec![],
+            collection_threshold: MIN_COLLECTION_THRESHOLD,
+            budget: Budget::default(),
+        }
     }
 
     pub fn new_scope(&mut self, parent: Option<usize>) -> usize {
-        let next_index = self.scopes.len();
-        self.scopes.push(Scope::new(parent));
-        next_index
+        if self.retained.len() >= self.collection_threshold {
+            self.collect();
+            self.collection_threshold = MIN_COLLECTION_THRESHOLD.max(self.retained.len() * 2);
+        }
+
+        if let Some(parent) = parent {
+            self.scopes[parent].children += 1;
+        }
+
+        match self.free.pop() {
+            Some(index) => {
+                self.scopes[index] = Scope::new(parent);
+                index
+            }
+            None => {
+                self.scopes.push(Scope::new(parent));
+                self.scopes.len() - 1
+            }
+        }
+    }
+
+    // Marks a scope as no longer needed by the code that created it, freeing it unless a closure or
+    // child scope still refers to it.
+    pub fn release(&mut self, scope: usize) {
+        self.scopes[scope].state = ScopeState::Released;
+
+        if !self.free_if_unreferenced(scope) {
+            self.retained.push(scope);
+        }
+    }
+
+    // Runs `f` in a new scope, released once it returns.
+    pub fn in_new_scope<T>(
+        &mut self,
+        parent: Option<usize>,
+        f: impl FnOnce(&mut Scopes, usize) -> T,
+    ) -> T {
+        let scope = self.new_scope(parent);
+        let result = f(self, scope);
+        self.release(scope);
+        result
+    }
+
+    pub fn pin(&self, scope: usize) -> ScopeRef {
+        ScopeRef {
+            id: scope,
+            pin: Some(Rc::clone(&self.scopes[scope].pin)),
+        }
+    }
+
+    // The number of scopes allocated, including those free to be reused.
+    pub fn allocated(&self) -> usize {
+        self.scopes.len()
+    }
+
+    fn free_if_unreferenced(&mut self, scope: usize) -> bool {
+        let candidate = &self.scopes[scope];
+
+        if candidate.state != ScopeState::Released
+            || candidate.children > 0
+            || candidate.is_pinned()
+        {
+            return false;
+        }
+
+        let freed = self.free_scope(scope);
+
+        if let Some(parent) = freed.parent {
+            self.scopes[parent].children -= 1;
+            self.free_if_unreferenced(parent);
+        }
+
+        true
+    }
+
+    // Takes a scope's contents, leaving it free for reuse. Dropping its bindings may unpin other
+    // scopes, which are freed when next collected.
+    fn free_scope(&mut self, scope: usize) -> Scope {
+        let mut freed = Scope::new(None);
+        freed.state = ScopeState::Free;
+
+        self.free.push(scope);
+        std::mem::replace(&mut self.scopes[scope], freed)
     }
 
     fn get_maybe_value(self: &Self, ident: &str, current_scope: usize) -> Option<Value> {
         let scope = self.scopes.get(current_scope).unwrap();
 
         match scope.get_binding(&ident) {
-            Some(ValueBinding { value, .. }) => Some(value),
+            Some(ValueBinding { value, .. }) => Some(value.clone()),
             None => match scope.parent {
                 Some(parent_scope) => self.get_maybe_value(ident, parent_scope),
                 None => None,
@@ -55,6 +175,22 @@ impl Scopes {
         }
     }
 
+    // Gets the value of a binding found by the resolver.
+    pub fn get_resolved_value(
+        self: &Self,
+        ident: &str,
+        resolution: Resolution,
+        current_scope: usize,
+    ) -> Result<Value, RuntimeError> {
+        match resolution {
+            Resolution::Global => self.get_value(ident, current_scope),
+            Resolution::Local { depth, slot } => self
+                .get_slot(depth, slot, current_scope)
+                .map(|binding| binding.value.clone())
+                .ok_or_else(|| not_found_in_scope_error(ident)),
+        }
+    }
+
     pub fn get_type(
         self: &Self,
         ident: &str,
@@ -66,6 +202,26 @@ impl Scopes {
         }
     }
 
+    // Lists the identifiers of every value and type visible from `current_scope`, innermost first.
+    pub fn get_binding_idents(self: &Self, current_scope: usize) -> Vec<String> {
+        let mut idents = Vec::<String>::new();
+        let mut scope = Some(current_scope);
+
+        while let Some(index) = scope {
+            let current = self.scopes.get(index).unwrap();
+
+            for ident in current.binding_idents() {
+                if !idents.contains(ident) {
+                    idents.push(ident.clone());
+                }
+            }
+
+            scope = current.parent;
+        }
+
+        idents
+    }
+
     fn find_scope_with_binding(
         self: &mut Self,
         ident: &str,
@@ -94,7 +250,7 @@ impl Scopes {
 
         if let Some(scope) = scope {
             let ValueBinding { is_mutable, .. } = scope.get_binding(ident).unwrap();
-            if is_mutable {
+            if *is_mutable {
                 scope.add_binding(ident, new_value, None, true)
             } else {
                 return Err(assign_immutable_binding_error(ident));
@@ -106,6 +262,30 @@ impl Scopes {
         Ok(Value::Void)
     }
 
+    pub fn mutate_resolved_value(
+        self: &mut Self,
+        ident: &str,
+        resolution: Resolution,
+        current_scope: usize,
+        new_value: Value,
+    ) -> Result<Value, RuntimeError> {
+        let (depth, slot) = match resolution {
+            Resolution::Global => return self.mutate_value(ident, current_scope, new_value),
+            Resolution::Local { depth, slot } => (depth, slot),
+        };
+
+        let scope = self.ancestor(current_scope, depth);
+
+        match self.scopes[scope].get_slot_mut(slot) {
+            Some(binding) if binding.is_mutable => {
+                binding.value = new_value;
+                Ok(Value::Void)
+            }
+            Some(_) => Err(assign_immutable_binding_error(ident)),
+            None => Err(not_found_in_scope_error(ident)),
+        }
+    }
+
     pub fn add_binding(
         self: &mut Self,
         ident: &str,
@@ -115,9 +295,7 @@ impl Scopes {
         is_mutable: bool,
     ) -> Result<Value, RuntimeError> {
         if self.binding_exists_local(ident, current_scope) {
-            Err(RuntimeError::new(&format!(
-                "Binding for {ident} already exists in local scope."
-            )))
+            Err(binding_exists_error(ident))
         } else {
             let scope = self.scopes.get_mut(current_scope).unwrap();
             scope.add_binding(ident, value, declared_type, is_mutable);
@@ -125,6 +303,42 @@ impl Scopes {
         }
     }
 
+    // Declares a binding where the resolver placed it. Locals are always declared in the current
+    // scope, at depth 0.
+    pub fn add_resolved_binding(
+        self: &mut Self,
+        ident: &str,
+        resolution: Resolution,
+        value: Value,
+        declared_type: Option<TypeVariant>,
+        current_scope: usize,
+        is_mutable: bool,
+    ) -> Result<Value, RuntimeError> {
+        let slot = match resolution {
+            Resolution::Global => {
+                return self.add_binding(ident, value, declared_type, current_scope, is_mutable)
+            }
+            Resolution::Local { slot, .. } => slot,
+        };
+
+        let scope = self.scopes.get_mut(current_scope).unwrap();
+
+        if scope.get_slot(slot).is_some() {
+            Err(binding_exists_error(ident))
+        } else {
+            scope.add_slot_binding(
+                slot,
+                ValueBinding {
+                    value,
+                    declared_type,
+                    is_mutable,
+                },
+            );
+
+            Ok(Value::Void)
+        }
+    }
+
     pub fn add_type_binding(
         self: &mut Self,
         current_scope: usize,
@@ -163,6 +377,40 @@ impl Scopes {
         self.get_maybe_value(ident, current_scope).is_some()
     }
 
+    pub fn resolved_binding_exists(
+        self: &Self,
+        ident: &str,
+        resolution: Resolution,
+        current_scope: usize,
+    ) -> bool {
+        match resolution {
+            Resolution::Global => self.binding_exists(ident, current_scope),
+            Resolution::Local { depth, slot } => {
+                self.get_slot(depth, slot, current_scope).is_some()
+            }
+        }
+    }
+
+    fn get_slot(
+        self: &Self,
+        depth: usize,
+        slot: usize,
+        current_scope: usize,
+    ) -> Option<&ValueBinding> {
+        self.scopes[self.ancestor(current_scope, depth)].get_slot(slot)
+    }
+
+    // The scope `depth` scopes up from `current_scope`.
+    fn ancestor(self: &Self, current_scope: usize, depth: usize) -> usize {
+        let mut scope = current_scope;
+
+        for _ in 0..depth {
+            scope = self.scopes[scope].parent.unwrap();
+        }
+
+        scope
+    }
+
     fn type_binding_exists_local(self: &Self, ident: &str, current_scope: usize) -> bool {
         self.scopes
             .get(current_scope)
@@ -171,7 +419,7 @@ impl Scopes {
             .is_some()
     }
 
-    fn binding_exists_local(self: &Self, ident: &str, current_scope: usize) -> bool {
+    pub fn binding_exists_local(self: &Self, ident: &str, current_scope: usize) -> bool {
         self.scopes
             .get(current_scope)
             .unwrap()
@@ -183,6 +431,10 @@ impl Scopes {
 impl fmt::Debug for Scopes {
     fn fmt(self: &Self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         for (i, scope) in self.scopes.iter().enumerate() {
+            if scope.state == ScopeState::Free {
+                continue;
+            }
+
             writeln!(f, "Scope {i}: {scope:?}")?;
         }
 
@@ -190,12 +442,18 @@ impl fmt::Debug for Scopes {
     }
 }
 
-fn not_found_in_scope_error(ident: &str) -> RuntimeError {
+pub(crate) fn not_found_in_scope_error(ident: &str) -> RuntimeError {
     RuntimeError::new(&format!(
         "Identifier '{ident}' was not found in this scope."
     ))
 }
 
-fn assign_immutable_binding_error(ident: &str) -> RuntimeError {
+pub(crate) fn assign_immutable_binding_error(ident: &str) -> RuntimeError {
     RuntimeError::new(&format!("Cannot re-assign to immutable binding `{ident}`."))
 }
+
+pub(crate) fn binding_exists_error(ident: &str) -> RuntimeError {
+    RuntimeError::new(&format!(
+        "Binding for {ident} already exists in local scope."
+    ))
+}
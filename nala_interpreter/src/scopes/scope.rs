@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, rc::Rc};
 
 use crate::{resolved::value::Value, types::type_variant::TypeVariant};
 
@@ -8,7 +8,26 @@ use super::value_binding::ValueBinding;
 pub struct Scope {
     pub parent: Option<usize>,
     bindings: HashMap<String, ValueBinding>,
+    // Bindings resolved ahead of time, see `resolver`. Only globals are kept by name.
+    slots: Vec<Option<ValueBinding>>,
     type_bindings: HashMap<String, TypeVariant>,
+    // How many scopes have this one as their parent and haven't been freed.
+    pub children: usize,
+    pub state: ScopeState,
+    // Cloned into every `ScopeRef` to the scope, so that its strong count tells how many functions
+    // are closing over it.
+    pub pin: Rc<()>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeState {
+    // In use by the code that created it.
+    Active,
+    // No longer needed by the code that created it, but possibly still referenced by closures or
+    // child scopes.
+    Released,
+    // Waiting to be reused.
+    Free,
 }
 
 impl Scope {
@@ -16,10 +35,25 @@ impl Scope {
         Scope {
             parent,
             bindings: HashMap::new(),
+            slots: Vec::new(),
             type_bindings: HashMap::new(),
+            children: 0,
+            state: ScopeState::Active,
+            pin: Rc::new(()),
         }
     }
 
+    pub fn is_pinned(self: &Self) -> bool {
+        Rc::strong_count(&self.pin) > 1
+    }
+
+    pub fn values(self: &Self) -> impl Iterator<Item = &Value> {
+        self.bindings
+            .values()
+            .chain(self.slots.iter().flatten())
+            .map(|binding| &binding.value)
+    }
+
     pub fn add_binding(
         self: &mut Self,
         ident: &str,
@@ -37,16 +71,32 @@ impl Scope {
         );
     }
 
+    pub fn add_slot_binding(self: &mut Self, slot: usize, binding: ValueBinding) {
+        if slot >= self.slots.len() {
+            self.slots.resize(slot + 1, None);
+        }
+
+        self.slots[slot] = Some(binding);
+    }
+
     pub fn add_type_binding(self: &mut Self, ident: &str, value: TypeVariant) {
         self.type_bindings.insert(ident.to_owned(), value);
     }
 
-    pub fn get_binding(self: &Self, ident: &str) -> Option<ValueBinding> {
-        if let Some(binding) = self.bindings.get(ident) {
-            Some(binding.clone())
-        } else {
-            None
-        }
+    pub fn get_binding(self: &Self, ident: &str) -> Option<&ValueBinding> {
+        self.bindings.get(ident)
+    }
+
+    pub fn get_slot(self: &Self, slot: usize) -> Option<&ValueBinding> {
+        self.slots.get(slot).and_then(Option::as_ref)
+    }
+
+    pub fn get_slot_mut(self: &mut Self, slot: usize) -> Option<&mut ValueBinding> {
+        self.slots.get_mut(slot).and_then(Option::as_mut)
+    }
+
+    pub fn binding_idents(self: &Self) -> impl Iterator<Item = &String> {
+        self.bindings.keys().chain(self.type_bindings.keys())
     }
 
     pub fn get_type_binding(self: &Self, ident: &str) -> Option<&TypeVariant> {
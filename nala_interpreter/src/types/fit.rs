@@ -1,7 +1,7 @@
 use crate::{
     ast::types::primitive_type::PrimitiveType,
     errors::RuntimeError,
-    resolved::{func_value::FuncValue, struct_field::StructField, value::Value},
+    resolved::{enum_variants::EnumVariant, struct_field::StructField, value::Value},
     scopes::Scopes,
 };
 
@@ -27,6 +27,12 @@ pub fn fits_type(
             }
             NalaType::PrimitiveType(PrimitiveType::Break) => todo!(),
             NalaType::PrimitiveType(PrimitiveType::Func) => fits_func(inner, value),
+            NalaType::PrimitiveType(PrimitiveType::Map) => {
+                fits_map(inner, value, scopes, current_scope)
+            }
+            NalaType::PrimitiveType(PrimitiveType::Set) => {
+                fits_set(inner, value, scopes, current_scope)
+            }
             NalaType::Enum(_, _) => fits_enum(value, type_variant, inner, scopes, current_scope),
             NalaType::Struct(fields) => fits_struct(fields, value, scopes, current_scope),
             NalaType::Generic(_) => todo!(),
@@ -62,7 +68,7 @@ fn fits_array(
 ) -> Result<bool, RuntimeError> {
     if let Value::Array(items) = value {
         let items = items.clone();
-        let items = items.lock().unwrap();
+        let items = items.borrow();
         let first = items.first();
 
         if let Some(first) = first {
@@ -76,9 +82,57 @@ fn fits_array(
     }
 }
 
+fn fits_map(
+    inner: &Vec<TypeVariant>,
+    value: &Value,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<bool, RuntimeError> {
+    if let Value::Map(map) = value {
+        let map = map.clone();
+        let map = map.borrow();
+
+        if let Some((key, value)) = map.first() {
+            Ok(
+                fits_type(&key.to_value(), &inner[0], scopes, current_scope)?
+                    && fits_type(value, &inner[1], scopes, current_scope)?,
+            )
+        } else {
+            // Empty map, fits any type.
+            Ok(true)
+        }
+    } else {
+        Ok(false)
+    }
+}
+
+fn fits_set(
+    inner: &Vec<TypeVariant>,
+    value: &Value,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<bool, RuntimeError> {
+    if let Value::Set(set) = value {
+        let first = set.borrow().first().map(|elem| elem.to_value());
+
+        if let Some(first) = first {
+            Ok(fits_type(&first, &inner[0], scopes, current_scope)?)
+        } else {
+            // Empty set, fits any type.
+            Ok(true)
+        }
+    } else {
+        Ok(false)
+    }
+}
+
 fn fits_func(inner: &Vec<TypeVariant>, value: &Value) -> Result<bool, RuntimeError> {
-    if let Value::Func(FuncValue { return_type, .. }) = value {
-        Ok(return_type == &inner.last().unwrap().clone())
+    if let Value::Func(func) = value {
+        match inner.last().unwrap() {
+            // Builtins taking functions which may return anything, such as `map`, expect these.
+            TypeVariant::Type(NalaType::Generic(_)) => Ok(true),
+            return_type => Ok(&func.return_type == return_type),
+        }
     } else {
         Ok(false)
     }
@@ -92,29 +146,36 @@ fn fits_enum(
     current_scope: usize,
 ) -> Result<bool, RuntimeError> {
     if let Value::Variant(variant) = value {
-        let value_type = infer_type(value, scopes, current_scope)?;
-
         match enum_type {
             TypeVariant::Composite(enum_type) => {
-                if let TypeVariant::Composite(value_type) = value_type {
-                    let outer_fits = enum_outer_fits(&enum_type.outer, &value_type.outer)?;
-
-                    let generic_params_match = value_type.generic_type_param.is_some()
-                        || value_type.generic_type_param == enum_type.generic_type_param;
-
-                    let data_fits = enum_data_fits(
-                        &expected_data_types[0],
-                        &variant.data,
-                        scopes,
-                        current_scope,
-                    )?;
-
-                    Ok(outer_fits && generic_params_match && data_fits)
-                } else {
-                    Ok(false)
-                }
+                let outer_fits = enum_outer_fits(
+                    &enum_type.outer,
+                    &NalaType::Enum(variant.enum_ident.clone(), vec![]),
+                )?;
+
+                // Only a generic expected type needs the value's own type, which is left uninferred
+                // otherwise, as data such as an empty array has no type to infer.
+                let generic_params_match = enum_type.generic_type_param.is_none()
+                    || matches!(
+                        infer_type(value, scopes, current_scope)?,
+                        TypeVariant::Composite(CompositeType {
+                            generic_type_param: Some(_),
+                            ..
+                        })
+                    );
+
+                // Variants whose data isn't of the generic type are checked against their own
+                // declared type.
+                let expected_data_type =
+                    declared_data_type(&enum_type.outer, &variant.variant_ident)
+                        .unwrap_or(&expected_data_types[0]);
+
+                let data_fits =
+                    enum_data_fits(expected_data_type, &variant.data, scopes, current_scope)?;
+
+                Ok(outer_fits && generic_params_match && data_fits)
             }
-            enum_type => Ok(enum_type == &value_type),
+            enum_type => Ok(enum_type == &infer_type(value, scopes, current_scope)?),
         }
     } else {
         Ok(false)
@@ -129,15 +190,19 @@ fn fits_struct(
 ) -> Result<bool, RuntimeError> {
     if let Value::Object(fields) = value {
         let fields = fields.clone();
-        let fields = fields.lock().unwrap();
+        let fields = fields.borrow();
 
-        for (ident, value) in fields.iter() {
-            if let Some(expected_field) = expected_fields.iter().find(|f| &f.ident == ident) {
-                if !fits_type(value, &expected_field.value_type, scopes, current_scope)? {
-                    return Ok(false);
+        // Fields the struct doesn't declare are ignored, and declared ones may only be left out if
+        // they're optional.
+        for expected_field in expected_fields.iter() {
+            match fields.get(&expected_field.ident) {
+                Some(value) => {
+                    if !fits_type(value, &expected_field.value_type, scopes, current_scope)? {
+                        return Ok(false);
+                    }
                 }
-            } else {
-                return Ok(true);
+                None if is_option(&expected_field.value_type) => continue,
+                None => return Ok(false),
             }
         }
 
@@ -147,6 +212,15 @@ fn fits_struct(
     }
 }
 
+pub(crate) fn is_option(type_variant: &TypeVariant) -> bool {
+    let outer = match type_variant {
+        TypeVariant::Composite(CompositeType { outer, .. }) => outer,
+        TypeVariant::Type(outer) => outer,
+    };
+
+    matches!(outer, NalaType::Enum(ident, _) if ident == "Option")
+}
+
 fn enum_data_fits(
     expected_type: &TypeVariant,
     data: &Option<Box<Value>>,
@@ -160,6 +234,20 @@ fn enum_data_fits(
     }
 }
 
+fn declared_data_type<'a>(
+    enum_outer: &'a NalaType,
+    variant_ident: &str,
+) -> Option<&'a TypeVariant> {
+    if let NalaType::Enum(_, variants) = enum_outer {
+        variants.iter().find_map(|variant| match variant {
+            EnumVariant::Data(ident, data_type) if ident == variant_ident => Some(data_type),
+            _ => None,
+        })
+    } else {
+        None
+    }
+}
+
 fn enum_outer_fits(enum_outer: &NalaType, value_outer: &NalaType) -> Result<bool, RuntimeError> {
     match enum_outer {
         NalaType::Enum(outer_ident, _) => {
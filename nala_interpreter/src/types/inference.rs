@@ -1,4 +1,6 @@
-use std::sync::{Arc, Mutex};
+use std::{cell::RefCell, rc::Rc};
+
+use indexmap::{IndexMap, IndexSet};
 
 use crate::{
     ast::types::primitive_type::PrimitiveType,
@@ -7,6 +9,7 @@ use crate::{
     resolved::{
         enum_variants::EnumVariant,
         func_value::FuncValue,
+        hash_key::HashKey,
         struct_field::StructField,
         value::{EnumVariantValue, Value},
     },
@@ -31,11 +34,13 @@ pub fn infer_type(
         Value::Array(items) => infer_array(items, scopes, current_scope)?,
         Value::Bool(_) => TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Bool)),
         Value::Break(_) => TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Break)),
-        Value::Func(FuncValue {
-            params,
-            return_type,
-            ..
-        }) => {
+        Value::Func(func) => {
+            let FuncValue {
+                params,
+                return_type,
+                ..
+            } = func.as_ref();
+
             let mut param_types: Vec<TypeVariant> =
                 params.into_iter().map(|p| p.clone().param_type).collect();
 
@@ -47,21 +52,24 @@ pub fn infer_type(
                 generic_type_param: None,
             })
         }
+        Value::Map(map) => infer_map(map, scopes, current_scope)?,
         Value::Num(_) => TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Number)),
         Value::Object(fields) => {
             let fields = fields
-                .lock()
-                .unwrap()
+                .borrow()
                 .clone()
                 .iter()
-                .map(|(ident, v)| StructField {
-                    ident: ident.clone(),
-                    value_type: infer_type(v, scopes, current_scope).unwrap(),
+                .map(|(ident, v)| {
+                    Ok(StructField {
+                        ident: ident.clone(),
+                        value_type: infer_type(v, scopes, current_scope)?,
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<StructField>, RuntimeError>>()?;
 
             TypeVariant::Type(NalaType::Struct(fields))
         }
+        Value::Set(set) => infer_set(set, scopes, current_scope)?,
         Value::String(_) => TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::String)),
         Value::Variant(variant) => infer_variant(variant, scopes, current_scope)?,
         Value::Void => TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Void)),
@@ -71,12 +79,12 @@ pub fn infer_type(
 }
 
 fn infer_array(
-    items: &Arc<Mutex<Vec<Value>>>,
+    items: &Rc<RefCell<Vec<Value>>>,
     scopes: &mut Scopes,
     current_scope: usize,
 ) -> Result<TypeVariant, RuntimeError> {
-    let items = Arc::clone(&items);
-    let items = items.lock().unwrap();
+    let items = Rc::clone(&items);
+    let items = items.borrow();
 
     let elem_type = if items.len() > 0 {
         // TODO: Inferring the type based on the first element isn't the right idea. Instead, we
@@ -100,6 +108,44 @@ fn infer_array(
     }))
 }
 
+// As with arrays, the types of maps and sets are inferred from their first entry.
+fn infer_map(
+    map: &Rc<RefCell<IndexMap<HashKey, Value>>>,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<TypeVariant, RuntimeError> {
+    let (key, value) = match map.borrow().first() {
+        Some((key, value)) => (key.to_value(), value.clone()),
+        None => Err(RuntimeError::new("Cannot infer type of an empty map."))?,
+    };
+
+    Ok(TypeVariant::Composite(CompositeType {
+        outer: NalaType::PrimitiveType(PrimitiveType::Map),
+        inner: vec![
+            infer_type(&key, scopes, current_scope)?,
+            infer_type(&value, scopes, current_scope)?,
+        ],
+        generic_type_param: None,
+    }))
+}
+
+fn infer_set(
+    set: &Rc<RefCell<IndexSet<HashKey>>>,
+    scopes: &mut Scopes,
+    current_scope: usize,
+) -> Result<TypeVariant, RuntimeError> {
+    let first = match set.borrow().first() {
+        Some(first) => first.to_value(),
+        None => Err(RuntimeError::new("Cannot infer type of an empty set."))?,
+    };
+
+    Ok(TypeVariant::Composite(CompositeType {
+        outer: NalaType::PrimitiveType(PrimitiveType::Set),
+        inner: vec![infer_type(&first, scopes, current_scope)?],
+        generic_type_param: None,
+    }))
+}
+
 pub fn infer_variant(
     variant: &EnumVariantValue,
     scopes: &mut Scopes,
@@ -89,6 +89,34 @@ impl TypeVariant {
         }
     }
 
+    fn map(inner: Vec<TypeVariant>) -> Result<Self, RuntimeError> {
+        if inner.len() != 2 {
+            return Err(RuntimeError::new(
+                "Type `Map` takes two type arguments, for its keys and its values, such as `Map<String, Number>`.",
+            ));
+        }
+
+        Ok(TypeVariant::Composite(CompositeType {
+            outer: NalaType::PrimitiveType(PrimitiveType::Map),
+            inner,
+            generic_type_param: None,
+        }))
+    }
+
+    fn set(inner: Vec<TypeVariant>) -> Result<Self, RuntimeError> {
+        if inner.len() != 1 {
+            return Err(RuntimeError::new(
+                "Type `Set` takes one type argument, for its elements, such as `Set<Number>`.",
+            ));
+        }
+
+        Ok(TypeVariant::Composite(CompositeType {
+            outer: NalaType::PrimitiveType(PrimitiveType::Set),
+            inner,
+            generic_type_param: None,
+        }))
+    }
+
     /**
      * TODO: This is really bad. This will error if called on a type that does not support composite
      * This has to be done because our TypeLiteral representing enum outer cannot be easily processed
@@ -122,6 +150,8 @@ impl TypeVariant {
                     inner,
                     generic_type_param: None,
                 }),
+                PrimitiveType::Map => Self::map(inner)?,
+                PrimitiveType::Set => Self::set(inner)?,
                 _ => Err(RuntimeError::new(&format!(
                     "Type `{literal}` does not support type arguments. Type `{literal}<{}>` is invalid.",
                     inner[0]
@@ -155,9 +185,11 @@ impl TypeVariant {
                     inner,
                     generic_type_param: None,
                 }),
+                PrimitiveType::Map => Self::map(inner)?,
                 PrimitiveType::Number => {
                     TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::Number))
                 }
+                PrimitiveType::Set => Self::set(inner)?,
                 PrimitiveType::String => {
                     TypeVariant::Type(NalaType::PrimitiveType(PrimitiveType::String))
                 }
@@ -183,6 +215,12 @@ impl TypeVariant {
                         .collect(),
                 )?;
 
+                // Primitive types take their type arguments as they are, while user defined types
+                // are made concrete by them below.
+                if let TypeLiteral::PrimitiveType(_) = outer {
+                    return Self::from_outer_literal_type(outer, inner, scopes, current_scope);
+                }
+
                 let outer =
                     Self::from_outer_literal_type(outer, inner.clone(), scopes, current_scope)?;
 
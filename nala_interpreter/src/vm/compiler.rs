@@ -0,0 +1,711 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use crate::{
+    ast::{
+        arrays::Array,
+        branching::{IfElseChain, Match, TryCatch},
+        funcs::{Call, FuncDeclare},
+        maps::Map,
+        math::{Addition, Multiplication},
+        objects::Object,
+        patterns::Pattern,
+        sets::Set,
+        terms::{Literal, Term},
+        *,
+    },
+    resolved::value::Value,
+};
+
+use super::ops::*;
+
+// Compiles a program to the function the VM runs first. `globals` are bound before the program
+// runs, taking the first slots in place of parameters. They're bound in a scope enclosing the
+// program's own, so that the program can declare globals of the same names, shadowing them.
+pub fn compile(program: &Program, globals: &[String]) -> Function {
+    let lines = match program {
+        Program::Block(lines) => lines,
+        Program::Lines(lines) => lines,
+    };
+
+    let mut compiler = Compiler { builders: vec![] };
+    compiler.program(globals, lines)
+}
+
+struct Builder {
+    function: Function,
+    // The scopes enclosing the code being compiled, outermost first.
+    active: Vec<usize>,
+    scope_slots: Vec<HashMap<String, usize>>,
+    // The identifiers of each scope declared so far in the code compiled.
+    declared: Vec<HashSet<String>>,
+    // Where each of the function's captures comes from in the function enclosing it.
+    captures: Vec<Location>,
+}
+
+// Functions are compiled as their declarations are reached, so the builders form a stack from the
+// program down to the innermost function being compiled.
+struct Compiler {
+    builders: Vec<Builder>,
+}
+
+impl Compiler {
+    fn function(&mut self, params: &[String], lines: &Vec<Line>) -> (Function, Vec<Location>) {
+        self.builders.push(Builder {
+            function: Function::default(),
+            active: vec![],
+            scope_slots: vec![],
+            declared: vec![],
+            captures: vec![],
+        });
+
+        self.open_scope(params, lines);
+        let params = params.iter().map(|p| self.slot(p)).collect();
+        self.builder().function.params = params;
+
+        self.block(lines);
+        self.emit(Op::Return);
+
+        let builder = self.builders.pop().unwrap();
+        (builder.function, builder.captures)
+    }
+
+    fn program(&mut self, globals: &[String], lines: &Vec<Line>) -> Function {
+        self.builders.push(Builder {
+            function: Function::default(),
+            active: vec![],
+            scope_slots: vec![],
+            declared: vec![],
+            captures: vec![],
+        });
+
+        self.open_scope(globals, &vec![]);
+        let params = globals.iter().map(|g| self.slot(g)).collect();
+        self.builder().function.params = params;
+
+        self.scoped_block(None, lines);
+        self.emit(Op::Return);
+
+        self.builders.pop().unwrap().function
+    }
+
+    fn builder(&mut self) -> &mut Builder {
+        self.builders.last_mut().unwrap()
+    }
+
+    fn emit(&mut self, op: Op) -> usize {
+        let code = &mut self.builder().function.code;
+        code.push(op);
+        code.len() - 1
+    }
+
+    fn here(&mut self) -> usize {
+        self.builder().function.code.len()
+    }
+
+    // Points the jump at `at` to the next instruction to be emitted.
+    fn patch(&mut self, at: usize) {
+        let target = self.here();
+
+        match &mut self.builder().function.code[at] {
+            Op::Jump(to)
+            | Op::JumpIfFalse(to, _)
+            | Op::JumpIfUnbound(_, to)
+            | Op::ExitIfValue(to)
+            | Op::IterNext(to)
+            | Op::ForResult(to)
+            | Op::WilesResult(to)
+            | Op::Match(_, to)
+            | Op::Try(to) => *to = target,
+            op => unreachable!("Tried to patch non-jump instruction {:?}.", op),
+        }
+    }
+
+    // Gives every identifier bound or declared directly in a block a slot of its own. Bodies of
+    // `wiles` loops don't get their own scope, so their declarations belong to the enclosing one.
+    fn open_scope(&mut self, bound: &[String], lines: &Vec<Line>) -> usize {
+        let mut idents = bound.to_vec();
+        let mut declares_types = false;
+        collect_declarations(lines, &mut idents, &mut declares_types);
+
+        let builder = self.builder();
+        let mut info = ScopeInfo {
+            slots: vec![],
+            declares_types,
+        };
+        let mut slots = HashMap::<String, usize>::new();
+
+        for ident in idents {
+            if !slots.contains_key(&ident) {
+                let slot = builder.function.slots.len();
+
+                builder.function.slots.push(SlotInfo {
+                    ident: ident.clone(),
+                    captured: false,
+                });
+
+                info.slots.push(slot);
+                slots.insert(ident, slot);
+            }
+        }
+
+        let scope = builder.function.scopes.len();
+        builder.function.scopes.push(info);
+        builder.scope_slots.push(slots);
+        builder.declared.push(bound.iter().cloned().collect());
+        builder.active.push(scope);
+
+        scope
+    }
+
+    // The slot of an identifier declared in the innermost scope.
+    fn slot(&mut self, ident: &str) -> usize {
+        let builder = self.builder();
+        let scope = *builder.active.last().unwrap();
+        builder.scope_slots[scope][ident]
+    }
+
+    // Marks an identifier of the innermost scope as declared, making it visible to the code after
+    // it, and returns its slot.
+    fn declare(&mut self, ident: &str) -> usize {
+        let builder = self.builder();
+        let scope = *builder.active.last().unwrap();
+        builder.declared[scope].insert(ident.to_owned());
+        builder.scope_slots[scope][ident]
+    }
+
+    fn lookup(&mut self, ident: &str) -> usize {
+        let location = self.resolve(self.builders.len() - 1, ident, false);
+        let lookups = &mut self.builder().function.lookups;
+
+        lookups.push(Lookup {
+            ident: ident.to_owned(),
+            location,
+        });
+
+        lookups.len() - 1
+    }
+
+    // Finds the binding `ident` refers to from the function at `level`, following the same rules
+    // as `resolver::resolve`: the innermost binding declared by the time it's used, or by the time
+    // the function is called for bindings of enclosing functions. These are captured, which moves
+    // their slots into cells.
+    fn resolve(&mut self, level: usize, ident: &str, deferred: bool) -> Option<Location> {
+        let builder = &self.builders[level];
+
+        let local = builder.active.iter().rev().find_map(|scope| {
+            let slot = builder.scope_slots[*scope].get(ident)?;
+
+            if deferred || builder.declared[*scope].contains(ident) {
+                Some(Location::Local(*slot))
+            } else {
+                None
+            }
+        });
+
+        if local.is_some() || level == 0 {
+            return local;
+        }
+
+        let source = self.resolve(level - 1, ident, true)?;
+
+        if let Location::Local(slot) = source {
+            self.builders[level - 1].function.slots[slot].captured = true;
+        }
+
+        let captures = &mut self.builders[level].captures;
+
+        let capture = match captures.iter().position(|c| *c == source) {
+            Some(capture) => capture,
+            None => {
+                captures.push(source);
+                captures.len() - 1
+            }
+        };
+
+        Some(Location::Capture(capture))
+    }
+
+    fn constant(&mut self, value: Value) {
+        let constants = &mut self.builder().function.constants;
+        constants.push(value);
+        let index = constants.len() - 1;
+
+        self.emit(Op::Constant(index));
+    }
+
+    fn name(&mut self, name: &str) -> usize {
+        let names = &mut self.builder().function.names;
+
+        match names.iter().position(|n| n == name) {
+            Some(index) => index,
+            None => {
+                names.push(name.to_owned());
+                names.len() - 1
+            }
+        }
+    }
+
+    // Every block leaves a single value on the stack: that of its first statement to produce one,
+    // or Void.
+    fn block(&mut self, lines: &Vec<Line>) {
+        let mut exits = vec![];
+
+        for line in lines.iter() {
+            if self.line(line) {
+                exits.push(self.emit(Op::ExitIfValue(0)));
+            }
+        }
+
+        self.emit(Op::Void);
+
+        for exit in exits {
+            self.patch(exit);
+        }
+    }
+
+    // Compiles a block in a scope of its own, binding the value on top of the stack to `bound`.
+    fn scoped_block(&mut self, bound: Option<&String>, lines: &Vec<Line>) {
+        let bound: Vec<String> = bound.into_iter().cloned().collect();
+        let scope = self.open_scope(&bound, lines);
+
+        self.emit(Op::EnterScope(scope));
+
+        for ident in bound.iter() {
+            let slot = self.slot(ident);
+            self.emit(Op::Bind(slot));
+        }
+
+        self.block(lines);
+        self.emit(Op::ExitScope(scope));
+
+        self.builder().active.pop();
+    }
+
+    // Compiles a statement, returning whether it leaves a value on the stack.
+    fn line(&mut self, line: &Line) -> bool {
+        match line {
+            Line::Assign(place, expr) => {
+                self.expr(expr);
+                self.assign(place);
+                false
+            }
+            // Test blocks only run under the test runner, see `eval_tests`.
+            Line::Blank | Line::Comment(_, _) | Line::Test(_, _) => false,
+            Line::Break(expr) => {
+                self.expr(expr);
+                self.emit(Op::Break);
+                true
+            }
+            Line::Declare(ident, expr, declared_type, is_mutable, _) => {
+                self.expr(expr);
+
+                let slot = self.declare(ident);
+                let declarations = &mut self.builder().function.declarations;
+
+                declarations.push(Declaration {
+                    slot,
+                    declared_type: declared_type.clone(),
+                    is_mutable: *is_mutable,
+                });
+
+                let index = declarations.len() - 1;
+                self.emit(Op::Declare(index));
+                false
+            }
+            Line::Enum(ident, type_param, variants) => {
+                self.type_declaration(TypeDeclaration::Enum(
+                    ident.clone(),
+                    type_param.clone(),
+                    variants.clone(),
+                ));
+                false
+            }
+            Line::Struct(ident, type_param, fields) => {
+                self.type_declaration(TypeDeclaration::Struct(
+                    ident.clone(),
+                    type_param.clone(),
+                    fields.clone(),
+                ));
+                false
+            }
+            Line::Expr(expr) => {
+                self.expr(expr);
+                true
+            }
+            Line::For(ident, expr, block) => {
+                self.for_loop(ident, expr, block);
+                true
+            }
+            Line::Func(func) => {
+                self.func_declare(func);
+                false
+            }
+            Line::IfElseChain(chain) => {
+                self.if_else_chain(chain);
+                true
+            }
+            Line::Match(the_match) => {
+                self.the_match(the_match);
+                true
+            }
+            Line::TryCatch(try_catch) => {
+                self.try_catch(try_catch);
+                true
+            }
+            Line::Wiles(cond, block) => {
+                self.wiles(cond, block);
+                true
+            }
+        }
+    }
+
+    fn type_declaration(&mut self, declaration: TypeDeclaration) {
+        let types = &mut self.builder().function.types;
+        types.push(declaration);
+        let index = types.len() - 1;
+
+        self.emit(Op::Type(index));
+    }
+
+    fn assign(&mut self, place: &PlaceExpression) {
+        match place {
+            PlaceExpression::Identifier(ident, _) => {
+                let lookup = self.lookup(ident);
+                self.emit(Op::Assign(lookup));
+            }
+            PlaceExpression::Index(array, index) => {
+                if let PlaceExpression::Identifier(ident, _) = &**array {
+                    // As in the tree-walking interpreter, assigning into an unbound array does
+                    // nothing.
+                    let lookup = self.lookup(ident);
+                    let unbound = self.emit(Op::JumpIfUnbound(lookup, 0));
+
+                    self.emit(Op::Load(lookup));
+                    self.expr(index);
+                    self.emit(Op::SetIndex);
+                    let end = self.emit(Op::Jump(0));
+
+                    self.patch(unbound);
+                    self.emit(Op::Pop);
+                    self.patch(end);
+                } else {
+                    self.place(array);
+                    self.expr(index);
+                    self.emit(Op::SetIndex);
+                }
+            }
+            PlaceExpression::MemberAccess(object, member) => {
+                self.place(object);
+                let member = self.name(member);
+                self.emit(Op::SetMember(member));
+            }
+        }
+    }
+
+    fn for_loop(&mut self, ident: &String, expr: &Expr, block: &Vec<Line>) {
+        self.expr(expr);
+        self.emit(Op::IterStart);
+        self.emit(Op::Void);
+
+        let top = self.here();
+        let next = self.emit(Op::IterNext(0));
+
+        self.scoped_block(Some(ident), block);
+
+        let result = self.emit(Op::ForResult(0));
+        self.emit(Op::Jump(top));
+
+        self.patch(next);
+        self.patch(result);
+        self.emit(Op::IterEnd);
+    }
+
+    fn wiles(&mut self, cond: &Expr, block: &Vec<Line>) {
+        let top = self.here();
+        self.expr(cond);
+        let done = self.emit(Op::JumpIfFalse(0, Condition::Wiles));
+
+        self.block(block);
+
+        let result = self.emit(Op::WilesResult(0));
+        self.emit(Op::Jump(top));
+
+        self.patch(done);
+        self.emit(Op::Void);
+        self.patch(result);
+    }
+
+    fn if_else_chain(&mut self, chain: &IfElseChain) {
+        let mut ends = vec![];
+
+        let branches = std::iter::once((&chain.cond, &chain.block))
+            .chain(chain.else_ifs.iter().map(|e| (&e.cond, &e.block)));
+
+        for (cond, block) in branches {
+            self.expr(cond);
+            let next = self.emit(Op::JumpIfFalse(0, Condition::If));
+
+            self.scoped_block(None, block);
+            ends.push(self.emit(Op::Jump(0)));
+
+            self.patch(next);
+        }
+
+        if let Some(else_block) = &chain.else_block {
+            self.scoped_block(None, &else_block.block);
+        } else {
+            self.emit(Op::Void);
+        }
+
+        for end in ends {
+            self.patch(end);
+        }
+    }
+
+    fn the_match(&mut self, the_match: &Match) {
+        self.expr(&the_match.expr);
+
+        let mut ends = vec![];
+
+        for case in the_match.cases.iter() {
+            let patterns = &mut self.builder().function.patterns;
+            patterns.push(case.pattern.clone());
+            let pattern = patterns.len() - 1;
+
+            let next = self.emit(Op::Match(pattern, 0));
+
+            let Pattern::Variant(_, _, binding) = &case.pattern;
+            self.scoped_block(binding.as_ref(), &case.block);
+            ends.push(self.emit(Op::Jump(0)));
+
+            self.patch(next);
+        }
+
+        self.emit(Op::Pop);
+        self.emit(Op::Void);
+
+        for end in ends {
+            self.patch(end);
+        }
+    }
+
+    fn try_catch(&mut self, try_catch: &TryCatch) {
+        let catch = self.emit(Op::Try(0));
+
+        self.scoped_block(None, &try_catch.block);
+        self.emit(Op::EndTry);
+        let end = self.emit(Op::Jump(0));
+
+        self.patch(catch);
+        self.scoped_block(Some(&try_catch.ident), &try_catch.catch_block);
+        self.patch(end);
+    }
+
+    fn func_declare(&mut self, func: &FuncDeclare) {
+        let FuncDeclare {
+            ident,
+            params,
+            return_type,
+            type_param,
+            block,
+            ..
+        } = func;
+
+        let lines = match block.as_ref() {
+            FuncVariant::Nala(lines) => lines,
+            _ => unreachable!("Only parsed functions can be compiled."),
+        };
+
+        let slot = self.declare(ident);
+
+        let param_idents: Vec<String> = params.iter().map(|p| p.ident.clone()).collect();
+        let (function, captures) = self.function(&param_idents, lines);
+
+        let protos = &mut self.builder().function.protos;
+
+        protos.push(FuncProto {
+            params: params.clone(),
+            return_type: return_type.clone(),
+            type_param: type_param.clone(),
+            function: Rc::new(function),
+            captures,
+        });
+
+        let proto = protos.len() - 1;
+        self.emit(Op::Closure(proto));
+        self.emit(Op::Bind(slot));
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Addition(addition) => self.addition(addition),
+            Expr::Eq(left, right) => {
+                self.expr(left);
+                self.addition(right);
+                self.emit(Op::Equals);
+            }
+            Expr::Gt(left, right) => {
+                self.expr(left);
+                self.addition(right);
+                self.emit(Op::Gt);
+            }
+            Expr::Lt(left, right) => {
+                self.expr(left);
+                self.addition(right);
+                self.emit(Op::Lt);
+            }
+        }
+    }
+
+    fn addition(&mut self, addition: &Addition) {
+        match addition {
+            Addition::Add(left, right) => {
+                self.addition(left);
+                self.multiplication(right);
+                self.emit(Op::Add);
+            }
+            Addition::Sub(left, right) => {
+                self.addition(left);
+                self.multiplication(right);
+                self.emit(Op::Subtract);
+            }
+            Addition::Multiplication(multiplication) => self.multiplication(multiplication),
+        }
+    }
+
+    fn multiplication(&mut self, multiplication: &Multiplication) {
+        match multiplication {
+            Multiplication::Mult(left, right) => {
+                self.multiplication(left);
+                self.term(right);
+                self.emit(Op::Multiply);
+            }
+            Multiplication::Div(left, right) => {
+                self.multiplication(left);
+                self.term(right);
+                self.emit(Op::Divide);
+            }
+            Multiplication::Unary(Unary::Primary(primary)) => self.primary(primary),
+        }
+    }
+
+    fn term(&mut self, term: &Term) {
+        match term {
+            Term::Identifier(ident, _) => {
+                let lookup = self.lookup(ident);
+                self.emit(Op::Load(lookup));
+            }
+            Term::Literal(literal) => self.literal(literal),
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal) {
+        match literal {
+            Literal::Number(num) => self.constant(Value::Num(*num)),
+            Literal::String(string) => self.constant(Value::String(string.clone())),
+        }
+    }
+
+    fn primary(&mut self, primary: &Primary) {
+        match primary {
+            Primary::Call(Call::Call(place, type_args, args)) => {
+                self.place(place);
+                self.emit(Op::Callee);
+
+                for arg in args.iter() {
+                    self.expr(arg);
+                }
+
+                let type_args = type_args.as_ref().map(|type_args| {
+                    let pool = &mut self.builder().function.type_args;
+                    pool.push(type_args.clone());
+                    pool.len() - 1
+                });
+
+                self.emit(Op::Call(args.len(), type_args));
+            }
+            Primary::Call(Call::PlaceExpression(place)) => self.place(place),
+            Primary::Literal(literal) => self.literal(literal),
+            Primary::Array(Array { elems }) => {
+                for elem in elems.iter() {
+                    self.expr(elem);
+                }
+
+                self.emit(Op::Array(elems.len()));
+            }
+            Primary::Object(Object { entries }) => {
+                for entry in entries.iter() {
+                    self.expr(&entry.value);
+                }
+
+                let shapes = &mut self.builder().function.shapes;
+                shapes.push(entries.iter().map(|e| e.key.clone()).collect());
+                let shape = shapes.len() - 1;
+
+                self.emit(Op::Object(shape));
+            }
+            Primary::Map(Map { entries }) => {
+                for entry in entries.iter() {
+                    self.expr(&entry.key);
+                    self.expr(&entry.value);
+                }
+
+                self.emit(Op::Map(entries.len()));
+            }
+            Primary::Set(Set { elems }) => {
+                for elem in elems.iter() {
+                    self.expr(elem);
+                }
+
+                self.emit(Op::Set(elems.len()));
+            }
+            Primary::EnumVariant(enum_ident, variant_ident, data) => {
+                let enum_ident = self.name(enum_ident);
+                let variant_ident = self.name(variant_ident);
+
+                // The variant is looked up before its data is evaluated, so that errors for unknown
+                // variants take precedence.
+                if let Some(data) = data {
+                    self.emit(Op::CheckVariant(enum_ident, variant_ident));
+                    self.expr(data);
+                }
+
+                self.emit(Op::Variant(enum_ident, variant_ident, data.is_some()));
+            }
+        }
+    }
+
+    fn place(&mut self, place: &PlaceExpression) {
+        match place {
+            PlaceExpression::Identifier(ident, _) => {
+                let lookup = self.lookup(ident);
+                self.emit(Op::Load(lookup));
+            }
+            PlaceExpression::Index(array, index) => {
+                self.place(array);
+                self.expr(index);
+                self.emit(Op::Index);
+            }
+            PlaceExpression::MemberAccess(object, member) => {
+                self.place(object);
+                let member = self.name(member);
+                self.emit(Op::Member(member));
+            }
+        }
+    }
+}
+
+fn collect_declarations(lines: &Vec<Line>, idents: &mut Vec<String>, declares_types: &mut bool) {
+    for line in lines.iter() {
+        match line {
+            Line::Declare(ident, ..) => idents.push(ident.clone()),
+            Line::Func(func) => idents.push(func.ident.clone()),
+            Line::Enum(_, _, _) | Line::Struct(_, _, _) => *declares_types = true,
+            Line::Wiles(_, block) => collect_declarations(block, idents, declares_types),
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,1010 @@
+use std::{cell::RefCell, rc::Rc};
+
+use indexmap::IndexSet;
+
+use crate::{
+    ast::FuncVariant,
+    errors::RuntimeError,
+    evaluator::Evaluator,
+    interpreter::{
+        arrays::{build_array, check_element, index_value, set_index},
+        branching::{non_array_for_error, non_bool_if_cond_error, non_bool_wiles_cond_error},
+        enums::{build_variant, lookup_variant},
+        functions::{
+            check_arg, check_arg_count, check_return, handle_type_args, params_from_declares,
+        },
+        maps::{build_map, map_entries},
+        objects::{build_object, eval_member_access},
+        operations::{arithmatic::*, equals::eval_equals, gt::eval_gt, lt::eval_lt},
+        sets::{build_set, check_set_element, set_elements},
+        types::{eval_enum, eval_struct},
+        variables::{check_assign, check_declare},
+    },
+    io_context::IoContext,
+    resolved::{
+        func_value::{FuncValue, Param},
+        hash_key::HashKey,
+        value::Value,
+    },
+    scopes::{
+        assign_immutable_binding_error, binding_exists_error, not_found_in_scope_error,
+        value_binding::ValueBinding, ScopeRef, Scopes,
+    },
+    types::{fit::fits_type, type_variant::TypeVariant},
+};
+
+use super::ops::*;
+
+// A binding shared between the frame that declared it and the closures which capture it.
+pub type Cell = Rc<RefCell<Option<ValueBinding>>>;
+
+// A compiled function together with the bindings it captured when its declaration ran.
+#[derive(Clone)]
+pub struct Closure {
+    pub(crate) function: Rc<Function>,
+    pub(crate) captures: Rc<Vec<Cell>>,
+}
+
+enum Slot {
+    Direct(Option<ValueBinding>),
+    Cell(Cell),
+}
+
+impl Slot {
+    fn new(captured: bool) -> Self {
+        if captured {
+            Slot::Cell(Rc::new(RefCell::new(None)))
+        } else {
+            Slot::Direct(None)
+        }
+    }
+}
+
+struct Frame {
+    function: Rc<Function>,
+    ip: usize,
+    slots: Vec<Slot>,
+    captures: Rc<Vec<Cell>>,
+    stack_base: usize,
+    type_scopes_base: usize,
+    // What the frame's return value is checked against. The program's own frame has no checks.
+    returns: Option<Returns>,
+}
+
+struct Returns {
+    func: Rc<FuncValue>,
+    call_scope: usize,
+    caller_scope: usize,
+}
+
+impl Frame {
+    fn new(
+        function: Rc<Function>,
+        captures: Rc<Vec<Cell>>,
+        stack_base: usize,
+        type_scopes_base: usize,
+        returns: Option<Returns>,
+    ) -> Self {
+        let slots = function
+            .slots
+            .iter()
+            .map(|s| Slot::new(s.captured))
+            .collect();
+
+        Frame {
+            function,
+            ip: 0,
+            slots,
+            captures,
+            stack_base,
+            type_scopes_base,
+            returns,
+        }
+    }
+
+    fn read<R>(&self, location: Location, read: impl FnOnce(&ValueBinding) -> R) -> Option<R> {
+        match location {
+            Location::Local(slot) => match &self.slots[slot] {
+                Slot::Direct(binding) => binding.as_ref().map(read),
+                Slot::Cell(cell) => cell.borrow().as_ref().map(read),
+            },
+            Location::Capture(capture) => self.captures[capture].borrow().as_ref().map(read),
+        }
+    }
+
+    fn write(&mut self, location: Location, write: impl FnOnce(&mut Option<ValueBinding>)) {
+        match location {
+            Location::Local(slot) => match &mut self.slots[slot] {
+                Slot::Direct(binding) => write(binding),
+                Slot::Cell(cell) => write(&mut cell.borrow_mut()),
+            },
+            Location::Capture(capture) => write(&mut self.captures[capture].borrow_mut()),
+        }
+    }
+
+    fn find(&self, lookup: &Lookup) -> Option<Location> {
+        lookup
+            .location
+            .filter(|location| self.read(*location, |_| ()).is_some())
+    }
+
+    fn bind(&mut self, slot: usize, binding: ValueBinding) -> Result<(), RuntimeError> {
+        let location = Location::Local(slot);
+
+        if self.read(location, |_| ()).is_some() {
+            return Err(binding_exists_error(&self.function.slots[slot].ident));
+        }
+
+        self.write(location, |b| *b = Some(binding));
+        Ok(())
+    }
+
+    fn cell(&self, location: Location) -> Cell {
+        match location {
+            Location::Local(slot) => match &self.slots[slot] {
+                Slot::Cell(cell) => Rc::clone(cell),
+                Slot::Direct(_) => unreachable!("Captured slots are always kept in cells."),
+            },
+            Location::Capture(capture) => Rc::clone(&self.captures[capture]),
+        }
+    }
+}
+
+// A scope types are declared into. Scopes a frame creates are released once it's done with them,
+// while those it shares, such as its closure's, are kept alive until then.
+enum TypeScope {
+    Owned(usize),
+    Shared(ScopeRef),
+}
+
+impl TypeScope {
+    fn id(&self) -> usize {
+        match self {
+            TypeScope::Owned(scope) => *scope,
+            TypeScope::Shared(scope) => scope.id(),
+        }
+    }
+}
+
+// Where to resume when an error is raised inside a `try` block, and how much state to unwind.
+struct Handler {
+    frame: usize,
+    stack_len: usize,
+    type_scopes_len: usize,
+    iters_len: usize,
+    catch_ip: usize,
+}
+
+// Runs compiled functions on a value stack. Values are only ever bound in slots, so `scopes` holds
+// nothing but types, and `type_scopes` tracks which of its scopes each frame is declaring into.
+pub struct Machine<'a> {
+    scopes: Scopes,
+    stack: Vec<Value>,
+    frames: Vec<Frame>,
+    type_scopes: Vec<TypeScope>,
+    iters: Vec<(Rc<RefCell<Vec<Value>>>, usize)>,
+    handlers: Vec<Handler>,
+    // The frames below this belong to an outer run, which a builtin calling back into the script
+    // returns to.
+    base: usize,
+    ctx: &'a mut dyn IoContext,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(scopes: Scopes, ctx: &'a mut dyn IoContext) -> Self {
+        Machine {
+            scopes,
+            stack: vec![],
+            frames: vec![],
+            type_scopes: vec![],
+            iters: vec![],
+            handlers: vec![],
+            base: 0,
+            ctx,
+        }
+    }
+
+    // Runs a compiled program, binding `globals` to the slots it reserved for them.
+    pub fn run(
+        &mut self,
+        function: Rc<Function>,
+        globals: Vec<Value>,
+        top_scope: usize,
+    ) -> Result<Value, RuntimeError> {
+        let mut frame = Frame::new(function, Rc::new(vec![]), 0, 0, None);
+
+        for (slot, value) in frame.function.params.clone().into_iter().zip(globals) {
+            frame.bind(slot, immutable(value))?;
+        }
+
+        let top_scope = self.scopes.pin(top_scope);
+        self.type_scopes.push(TypeScope::Shared(top_scope));
+        self.frames.push(frame);
+        self.resume()
+    }
+
+    // Runs until the frames above `base` have returned, catching what errors it can on the way.
+    fn resume(&mut self) -> Result<Value, RuntimeError> {
+        loop {
+            match self.execute() {
+                Ok(value) => return Ok(value),
+                Err(error) => self.catch(error)?,
+            }
+        }
+    }
+
+    // Runs the frame just pushed for a call made by a builtin until it returns. Errors the call
+    // doesn't catch are passed back to the builtin, unwinding everything the call left behind.
+    fn run_call(&mut self) -> Result<Value, RuntimeError> {
+        let base = self.frames.len() - 1;
+        let stack_len = self.frames[base].stack_base;
+        let type_scopes_len = self.frames[base].type_scopes_base;
+        let iters_len = self.iters.len();
+        let handlers_len = self.handlers.len();
+
+        let outer_base = std::mem::replace(&mut self.base, base);
+        let result = self.resume();
+        self.base = outer_base;
+
+        if result.is_err() {
+            self.frames.truncate(base);
+            self.stack.truncate(stack_len);
+            self.truncate_type_scopes(type_scopes_len);
+            self.iters.truncate(iters_len);
+            self.handlers.truncate(handlers_len);
+        }
+
+        result
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().unwrap()
+    }
+
+    fn type_scope(&self) -> usize {
+        self.type_scopes.last().unwrap().id()
+    }
+
+    fn truncate_type_scopes(&mut self, len: usize) {
+        for scope in self.type_scopes.split_off(len) {
+            if let TypeScope::Owned(scope) = scope {
+                self.scopes.release(scope);
+            }
+        }
+    }
+
+    fn push(&mut self, value: Value) {
+        self.stack.push(value);
+    }
+
+    fn pop(&mut self) -> Value {
+        self.stack.pop().unwrap()
+    }
+
+    fn execute(&mut self) -> Result<Value, RuntimeError> {
+        'frames: loop {
+            let function = Rc::clone(&self.frame().function);
+            let mut ip = self.frame().ip;
+
+            loop {
+                let op = function.code[ip];
+                ip += 1;
+
+                match op {
+                    Op::Constant(constant) => self.push(function.constants[constant].clone()),
+                    Op::Void => self.push(Value::Void),
+                    Op::Pop => {
+                        self.pop();
+                    }
+
+                    Op::Load(lookup) => {
+                        let value = self.load(&function.lookups[lookup])?;
+                        self.push(value);
+                    }
+                    Op::Declare(declaration) => {
+                        let value = self.pop();
+                        self.declare(&function, &function.declarations[declaration], value)?;
+                    }
+                    Op::Bind(slot) => {
+                        let value = self.pop();
+                        self.frame().bind(slot, immutable(value))?;
+                    }
+                    Op::Assign(lookup) => {
+                        let value = self.pop();
+                        self.assign(&function.lookups[lookup], value)?;
+                    }
+                    Op::SetIndex => self.set_index()?,
+                    Op::SetMember(member) => {
+                        let object = self.pop();
+                        let value = self.pop();
+
+                        if let Value::Object(object) = object {
+                            let mut object = object.borrow_mut();
+                            object.insert(function.names[member].clone(), value);
+                        } else {
+                            Err(RuntimeError::new(&format!(
+                                "Tried to access member `{}` of non-Object `{object}`.",
+                                function.names[member]
+                            )))?
+                        }
+                    }
+
+                    Op::Index => {
+                        let index = self.pop();
+                        let collection = self.pop();
+                        self.push(index_value(&collection, &index)?);
+                    }
+                    Op::Member(member) => {
+                        let object = self.pop();
+                        self.push(eval_member_access(&object, &function.names[member])?);
+                    }
+                    Op::Array(len) => {
+                        let values = self.stack.split_off(self.stack.len() - len);
+                        let scope = self.type_scope();
+                        let array = build_array(values, &mut self.scopes, scope)?;
+                        self.push(array);
+                    }
+                    Op::Map(len) => {
+                        let values = self.stack.split_off(self.stack.len() - len * 2);
+                        let entries = values
+                            .chunks(2)
+                            .map(|entry| (entry[0].clone(), entry[1].clone()))
+                            .collect();
+
+                        let scope = self.type_scope();
+                        let map = build_map(entries, &mut self.scopes, scope)?;
+                        self.push(map);
+                    }
+                    Op::Set(len) => {
+                        let values = self.stack.split_off(self.stack.len() - len);
+                        let scope = self.type_scope();
+                        let set = build_set(values, &mut self.scopes, scope)?;
+                        self.push(set);
+                    }
+                    Op::Object(shape) => {
+                        let keys = &function.shapes[shape];
+                        let values = self.stack.split_off(self.stack.len() - keys.len());
+                        let fields = keys.iter().cloned().zip(values).collect();
+                        let object = build_object(fields, &self.scopes)?;
+                        self.push(object);
+                    }
+                    Op::CheckVariant(enum_ident, variant_ident) => {
+                        let scope = self.type_scope();
+
+                        lookup_variant(
+                            &function.names[enum_ident],
+                            &function.names[variant_ident],
+                            &mut self.scopes,
+                            scope,
+                        )?;
+                    }
+                    Op::Variant(enum_ident, variant_ident, has_data) => {
+                        let data = if has_data { Some(self.pop()) } else { None };
+                        let enum_ident = &function.names[enum_ident];
+                        let variant_ident = &function.names[variant_ident];
+                        let scope = self.type_scope();
+
+                        let variant =
+                            lookup_variant(enum_ident, variant_ident, &mut self.scopes, scope)?;
+                        let value = build_variant(
+                            enum_ident,
+                            variant_ident,
+                            &variant,
+                            data,
+                            &mut self.scopes,
+                            scope,
+                        )?;
+
+                        self.push(value);
+                    }
+
+                    Op::Add => {
+                        let right = self.pop();
+                        let left = self.pop();
+
+                        let result = match (left, right) {
+                            (Value::Num(left), Value::Num(right)) => Value::Num(left + right),
+                            (Value::String(left), Value::String(right)) => {
+                                concat_strings(&left, &right, &self.scopes)?
+                            }
+                            (left, right) => {
+                                let scope = self.type_scope();
+                                do_add(left, right, &mut self.scopes, scope)?
+                            }
+                        };
+
+                        self.push(result);
+                    }
+                    Op::Subtract => {
+                        let right = self.pop();
+                        let left = self.pop();
+
+                        let result = match (left, right) {
+                            (Value::Num(left), Value::Num(right)) => Value::Num(left - right),
+                            (left, right) => {
+                                let scope = self.type_scope();
+                                do_subtract(left, right, &mut self.scopes, scope)?
+                            }
+                        };
+
+                        self.push(result);
+                    }
+                    Op::Multiply => {
+                        let right = self.pop();
+                        let left = self.pop();
+
+                        let result = match (left, right) {
+                            (Value::Num(left), Value::Num(right)) => Value::Num(left * right),
+                            (left, right) => {
+                                let scope = self.type_scope();
+                                do_multiply(left, right, &mut self.scopes, scope)?
+                            }
+                        };
+
+                        self.push(result);
+                    }
+                    Op::Divide => {
+                        let right = self.pop();
+                        let left = self.pop();
+                        let scope = self.type_scope();
+                        let result = do_divide(left, right, &mut self.scopes, scope)?;
+                        self.push(result);
+                    }
+                    Op::Equals => {
+                        let right = self.pop();
+                        let left = self.pop();
+
+                        let result = match (left, right) {
+                            (Value::Num(left), Value::Num(right)) => Value::Bool(left == right),
+                            (Value::String(left), Value::String(right)) => {
+                                Value::Bool(left == right)
+                            }
+                            (left, right) => {
+                                let scope = self.type_scope();
+                                eval_equals(left, right, &mut self.scopes, scope)?
+                            }
+                        };
+
+                        self.push(result);
+                    }
+                    Op::Gt => {
+                        let right = self.pop();
+                        let left = self.pop();
+                        let scope = self.type_scope();
+                        let result = eval_gt(left, right, &mut self.scopes, scope)?;
+                        self.push(result);
+                    }
+                    Op::Lt => {
+                        let right = self.pop();
+                        let left = self.pop();
+                        let scope = self.type_scope();
+                        let result = eval_lt(left, right, &mut self.scopes, scope)?;
+                        self.push(result);
+                    }
+
+                    Op::Callee => {
+                        if !matches!(self.stack.last(), Some(Value::Func(_))) {
+                            Err(RuntimeError::new("Cannot invoke a non-function."))?
+                        }
+                    }
+                    Op::Call(argc, type_args) => {
+                        self.scopes.budget.step()?;
+                        self.frame().ip = ip;
+                        let type_args = type_args.map(|t| function.type_args[t].clone());
+
+                        if self.call(argc, &type_args)? {
+                            continue 'frames;
+                        }
+                    }
+                    Op::Closure(proto) => {
+                        let closure = self.closure(&function.protos[proto])?;
+                        self.push(closure);
+                    }
+                    Op::Return => {
+                        let value = self.pop();
+                        let frame = self.frames.pop().unwrap();
+
+                        self.stack.truncate(frame.stack_base);
+
+                        let value = match frame.returns {
+                            Some(returns) => check_return(
+                                value,
+                                &returns.func.return_type,
+                                &returns.func.type_param,
+                                &mut self.scopes,
+                                returns.call_scope,
+                                returns.caller_scope,
+                            )?,
+                            None => value,
+                        };
+
+                        self.truncate_type_scopes(frame.type_scopes_base);
+
+                        if self.frames.len() == self.base {
+                            return Ok(value);
+                        }
+
+                        self.push(value);
+                        continue 'frames;
+                    }
+                    Op::Type(declaration) => {
+                        let scope = self.type_scope();
+
+                        match &function.types[declaration] {
+                            TypeDeclaration::Enum(ident, type_param, variants) => eval_enum(
+                                ident,
+                                type_param.clone(),
+                                variants.clone(),
+                                &mut self.scopes,
+                                scope,
+                            )?,
+                            TypeDeclaration::Struct(ident, type_param, fields) => eval_struct(
+                                ident,
+                                type_param.clone(),
+                                fields.clone(),
+                                &mut self.scopes,
+                                scope,
+                            )?,
+                        };
+                    }
+
+                    Op::Jump(target) => ip = target,
+                    Op::JumpIfFalse(target, condition) => match self.pop() {
+                        Value::Bool(true) if matches!(condition, Condition::Wiles) => {
+                            self.scopes.budget.step()?
+                        }
+                        Value::Bool(true) => (),
+                        Value::Bool(false) => ip = target,
+                        _ => match condition {
+                            Condition::If => Err(non_bool_if_cond_error())?,
+                            Condition::Wiles => Err(non_bool_wiles_cond_error())?,
+                        },
+                    },
+                    Op::JumpIfUnbound(lookup, target) => {
+                        if self.frame().find(&function.lookups[lookup]).is_none() {
+                            ip = target;
+                        }
+                    }
+                    Op::ExitIfValue(target) => {
+                        if self.stack.last().unwrap().is_void() {
+                            self.pop();
+                        } else {
+                            ip = target;
+                        }
+                    }
+                    Op::Break => {
+                        let value = self.pop();
+                        self.push(Value::Break(Box::new(value)));
+                    }
+
+                    Op::EnterScope(scope) => {
+                        let info = &function.scopes[scope];
+                        let frame = self.frames.last_mut().unwrap();
+
+                        for slot in info.slots.iter() {
+                            frame.slots[*slot] = Slot::new(function.slots[*slot].captured);
+                        }
+
+                        if info.declares_types {
+                            let scope = self.scopes.new_scope(Some(self.type_scope()));
+                            self.type_scopes.push(TypeScope::Owned(scope));
+                        }
+                    }
+                    Op::ExitScope(scope) => {
+                        if function.scopes[scope].declares_types {
+                            self.truncate_type_scopes(self.type_scopes.len() - 1);
+                        }
+                    }
+
+                    Op::IterStart => match self.pop() {
+                        Value::Array(array) => self.iters.push((array, 0)),
+                        Value::Map(map) => {
+                            let entries = map_entries(&map);
+                            self.iters.push((Rc::new(RefCell::new(entries)), 0))
+                        }
+                        Value::Set(set) => {
+                            let elems = set_elements(&set);
+                            self.iters.push((Rc::new(RefCell::new(elems)), 0))
+                        }
+                        value => {
+                            let scope = self.type_scope();
+                            Err(non_array_for_error(&value, &mut self.scopes, scope)?)?
+                        }
+                    },
+                    Op::IterNext(target) => {
+                        let (array, index) = self.iters.last_mut().unwrap();
+                        let item = array.borrow().get(*index).cloned();
+
+                        match item {
+                            Some(item) => {
+                                *index += 1;
+                                self.stack.push(item);
+                                self.scopes.budget.step()?;
+                            }
+                            None => ip = target,
+                        }
+                    }
+                    Op::IterEnd => {
+                        self.iters.pop();
+                    }
+                    // The loop's result sits below the value of the iteration that just ran.
+                    Op::ForResult(target) => {
+                        let value = self.pop();
+                        let result = self.stack.last_mut().unwrap();
+
+                        match value {
+                            Value::Break(value) => {
+                                *result = *value;
+                                ip = target;
+                            }
+                            value => *result = value,
+                        }
+                    }
+                    Op::WilesResult(target) => {
+                        if let Value::Break(value) = self.pop() {
+                            self.push(*value);
+                            ip = target;
+                        }
+                    }
+
+                    Op::Match(pattern, target) => {
+                        match function.patterns[pattern].matches(self.stack.last().unwrap()) {
+                            Some(bindings) => {
+                                self.pop();
+
+                                for (_, value) in bindings {
+                                    self.push(value);
+                                }
+                            }
+                            None => ip = target,
+                        }
+                    }
+                    Op::Try(catch_ip) => {
+                        let handler = Handler {
+                            frame: self.frames.len() - 1,
+                            stack_len: self.stack.len(),
+                            type_scopes_len: self.type_scopes.len(),
+                            iters_len: self.iters.len(),
+                            catch_ip,
+                        };
+
+                        self.handlers.push(handler);
+                    }
+                    Op::EndTry => {
+                        self.handlers.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    // Unwinds to the innermost `try` block, resuming at its `catch` block with the error bound.
+    fn catch(&mut self, error: RuntimeError) -> Result<(), RuntimeError> {
+        if !error.is_catchable() {
+            return Err(error);
+        }
+
+        // Handlers set up by an outer run are left for it to use once the error reaches it.
+        let handler = match self.handlers.last() {
+            Some(handler) if handler.frame >= self.base => self.handlers.pop().unwrap(),
+            _ => return Err(error),
+        };
+
+        self.frames.truncate(handler.frame + 1);
+        self.stack.truncate(handler.stack_len);
+        self.truncate_type_scopes(handler.type_scopes_len);
+        self.iters.truncate(handler.iters_len);
+
+        self.push(error.to_value());
+        self.frame().ip = handler.catch_ip;
+
+        Ok(())
+    }
+
+    fn load(&mut self, lookup: &Lookup) -> Result<Value, RuntimeError> {
+        let frame = self.frame();
+
+        lookup
+            .location
+            .and_then(|location| frame.read(location, |b| b.value.clone()))
+            .ok_or_else(|| not_found_in_scope_error(&lookup.ident))
+    }
+
+    fn declare(
+        &mut self,
+        function: &Function,
+        declaration: &Declaration,
+        value: Value,
+    ) -> Result<(), RuntimeError> {
+        let scope = self.type_scope();
+
+        let declared_type = check_declare(
+            &function.slots[declaration.slot].ident,
+            &value,
+            declaration.declared_type.clone(),
+            &mut self.scopes,
+            scope,
+        )?;
+
+        self.frame().bind(
+            declaration.slot,
+            ValueBinding {
+                value,
+                declared_type,
+                is_mutable: declaration.is_mutable,
+            },
+        )
+    }
+
+    fn assign(&mut self, lookup: &Lookup, value: Value) -> Result<(), RuntimeError> {
+        if let Value::Void = value {
+            Err(RuntimeError::new("Cannot assign a value of type Void."))?;
+        }
+
+        let frame = self.frame();
+
+        let location = frame
+            .find(lookup)
+            .ok_or_else(|| not_found_in_scope_error(&lookup.ident))?;
+
+        let (existing, is_mutable) = frame
+            .read(location, |b| (b.value.clone(), b.is_mutable))
+            .unwrap();
+
+        // Primitives of the same kind always share a type, so only other values need inferring.
+        let same_primitive = matches!(
+            (&existing, &value),
+            (Value::Num(_), Value::Num(_))
+                | (Value::String(_), Value::String(_))
+                | (Value::Bool(_), Value::Bool(_))
+        );
+
+        if !same_primitive {
+            let scope = self.type_scope();
+            check_assign(&existing, &value, &mut self.scopes, scope)?;
+        }
+
+        if !is_mutable {
+            return Err(assign_immutable_binding_error(&lookup.ident));
+        }
+
+        self.frame().write(location, |binding| {
+            if let Some(binding) = binding {
+                binding.value = value;
+            }
+        });
+
+        Ok(())
+    }
+
+    fn set_index(&mut self) -> Result<(), RuntimeError> {
+        let index = self.pop();
+        let collection = self.pop();
+        let value = self.pop();
+
+        let scope = self.type_scope();
+        set_index(collection, index, value, &mut self.scopes, scope)
+    }
+
+    // Calls the function below `argc` arguments on the stack. Builtins run immediately, leaving
+    // their result on the stack, while compiled functions push a frame, in which case this returns
+    // true.
+    fn call(
+        &mut self,
+        argc: usize,
+        type_args: &Option<crate::ast::types::type_literal_variant::TypeVariantLiteral>,
+    ) -> Result<bool, RuntimeError> {
+        let args = self.stack.split_off(self.stack.len() - argc);
+
+        let func = match self.pop() {
+            Value::Func(func) => func,
+            _ => unreachable!("Callees are checked before their arguments are evaluated."),
+        };
+
+        let FuncValue {
+            block,
+            params,
+            return_type,
+            type_param,
+            closure_scope,
+        } = func.as_ref();
+
+        let caller_scope = self.type_scope();
+
+        // Type bindings made by the call need a scope of their own, but most calls make none, and
+        // can share their closure's scope rather than allocating one.
+        let needs_scope = type_param.is_some()
+            || params
+                .iter()
+                .any(|p| p.param_type.find_generic_type_param().is_some())
+            || matches!(block.as_ref(), FuncVariant::Compiled(c) if c.function.declares_types());
+
+        // The call's scope is tracked from the start, so that it's released if the call fails.
+        let type_scopes_base = self.type_scopes.len();
+        let call_scope = if needs_scope {
+            TypeScope::Owned(self.scopes.new_scope(Some(closure_scope.id())))
+        } else {
+            TypeScope::Shared(closure_scope.clone())
+        };
+
+        let call_scope_id = call_scope.id();
+        self.type_scopes.push(call_scope);
+        let call_scope = call_scope_id;
+
+        handle_type_args(
+            type_args,
+            type_param,
+            &mut self.scopes,
+            call_scope,
+            caller_scope,
+        )?;
+        check_arg_count(params, &args)?;
+
+        match block.as_ref() {
+            FuncVariant::Builtin(func) => {
+                for (param, arg) in params.iter().zip(&args) {
+                    check_arg(arg, param, &mut self.scopes, call_scope, caller_scope)?;
+                }
+
+                let value = func(args, self)?;
+                let value = check_return(
+                    value,
+                    return_type,
+                    type_param,
+                    &mut self.scopes,
+                    call_scope,
+                    caller_scope,
+                )?;
+
+                self.truncate_type_scopes(type_scopes_base);
+                self.push(value);
+                Ok(false)
+            }
+            FuncVariant::Compiled(Closure { function, captures }) => {
+                // The program itself runs in the first frame, which isn't a call.
+                self.scopes.budget.check_call_depth(self.frames.len() - 1)?;
+
+                let mut frame = Frame::new(
+                    Rc::clone(function),
+                    Rc::clone(captures),
+                    self.stack.len(),
+                    type_scopes_base,
+                    Some(Returns {
+                        func: Rc::clone(&func),
+                        call_scope,
+                        caller_scope,
+                    }),
+                );
+
+                for ((param, arg), slot) in params.iter().zip(args).zip(function.params.iter()) {
+                    check_arg(&arg, param, &mut self.scopes, call_scope, caller_scope)?;
+                    frame.bind(*slot, immutable(arg))?;
+                }
+
+                self.frames.push(frame);
+                Ok(true)
+            }
+            FuncVariant::Nala(_) => Err(RuntimeError::new(
+                "Functions parsed for the tree-walking interpreter can only be called by it.",
+            )),
+        }
+    }
+
+    // Creates a function value the way `eval_func_declare` does, capturing the bindings its body
+    // refers to from the running frame.
+    fn closure(&mut self, proto: &FuncProto) -> Result<Value, RuntimeError> {
+        let current_scope = self.type_scope();
+
+        let (params, return_type, closure_scope) = match &proto.type_param {
+            Some(type_param) => {
+                self.scopes
+                    .in_new_scope(Some(current_scope), |scopes, closure_scope| {
+                        scopes.add_type_binding(
+                            closure_scope,
+                            type_param,
+                            TypeVariant::generic(type_param.clone()),
+                        )?;
+
+                        signature(proto, scopes, closure_scope)
+                    })?
+            }
+            None => signature(proto, &mut self.scopes, current_scope)?,
+        };
+
+        let frame = self.frame();
+        let captures = proto.captures.iter().map(|c| frame.cell(*c)).collect();
+
+        Ok(Value::Func(Rc::new(FuncValue {
+            block: Rc::new(FuncVariant::Compiled(Closure {
+                function: Rc::clone(&proto.function),
+                captures: Rc::new(captures),
+            })),
+            params,
+            return_type,
+            type_param: proto.type_param.clone(),
+            closure_scope,
+        })))
+    }
+}
+
+// Resolves a function's parameter and return types in `closure_scope`, which it then closes over.
+fn signature(
+    proto: &FuncProto,
+    scopes: &mut Scopes,
+    closure_scope: usize,
+) -> Result<(Vec<Param>, TypeVariant, ScopeRef), RuntimeError> {
+    let params = params_from_declares(&proto.params, scopes, closure_scope)?;
+    let return_type = TypeVariant::from_literal(proto.return_type.clone(), scopes, closure_scope)?;
+
+    Ok((params, return_type, scopes.pin(closure_scope)))
+}
+
+impl Evaluator for Machine<'_> {
+    fn ctx(&mut self) -> &mut dyn IoContext {
+        self.ctx
+    }
+
+    fn call(&mut self, func: Rc<FuncValue>, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        self.scopes.budget.step()?;
+
+        let argc = args.len();
+        self.push(Value::Func(func));
+        self.stack.extend(args);
+
+        if Machine::call(self, argc, &None)? {
+            self.run_call()
+        } else {
+            Ok(self.pop())
+        }
+    }
+
+    fn build_array(&mut self, values: Vec<Value>) -> Result<Value, RuntimeError> {
+        let scope = self.type_scope();
+        build_array(values, &mut self.scopes, scope)
+    }
+
+    fn check_collection_len(&mut self, len: usize) -> Result<(), RuntimeError> {
+        self.scopes.budget.check_collection_len(len)
+    }
+
+    fn check_string_len(&mut self, len: usize) -> Result<(), RuntimeError> {
+        self.scopes.budget.check_string_len(len)
+    }
+
+    fn check_element(&mut self, items: &[Value], value: &Value) -> Result<(), RuntimeError> {
+        let scope = self.type_scope();
+        check_element(items, value, &mut self.scopes, scope)
+    }
+
+    fn build_set(&mut self, values: Vec<Value>) -> Result<Value, RuntimeError> {
+        let scope = self.type_scope();
+        build_set(values, &mut self.scopes, scope)
+    }
+
+    fn check_set_element(
+        &mut self,
+        set: &IndexSet<HashKey>,
+        elem: &HashKey,
+    ) -> Result<(), RuntimeError> {
+        let scope = self.type_scope();
+        check_set_element(set, elem, &mut self.scopes, scope)
+    }
+
+    fn get_type(&mut self, ident: &str) -> Result<TypeVariant, RuntimeError> {
+        let scope = self.type_scope();
+        self.scopes.get_type(ident, scope)
+    }
+
+    fn fits_type(&mut self, value: &Value, expected: &TypeVariant) -> Result<bool, RuntimeError> {
+        let scope = self.type_scope();
+        fits_type(value, expected, &mut self.scopes, scope)
+    }
+}
+
+fn immutable(value: Value) -> ValueBinding {
+    ValueBinding {
+        value,
+        declared_type: None,
+        is_mutable: false,
+    }
+}
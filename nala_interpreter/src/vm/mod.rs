@@ -0,0 +1,58 @@
+mod compiler;
+mod machine;
+mod ops;
+
+use std::rc::Rc;
+
+use crate::{
+    ast::Program,
+    errors::RuntimeError,
+    interpreter::new_top_scope,
+    io_context::IoContext,
+    limits::{Budget, CancelHandle, Limits},
+    resolved::value::Value,
+    resolver::resolve,
+    scopes::Scopes,
+};
+
+use self::{compiler::compile, machine::Machine};
+
+pub use self::machine::Closure;
+
+// Compiles a program to bytecode and runs it on the VM. Scripts behave as they do under
+// `eval_program`, and are checked by the same resolver before they're compiled.
+pub fn run_program(program: Program, ctx: &mut impl IoContext) -> Result<Value, RuntimeError> {
+    run_program_with_limits(program, ctx, Limits::default(), CancelHandle::new())
+}
+
+// Runs a program on the VM, stopping it as `eval_program_with_limits` does.
+pub fn run_program_with_limits(
+    mut program: Program,
+    ctx: &mut impl IoContext,
+    limits: Limits,
+    cancel: CancelHandle,
+) -> Result<Value, RuntimeError> {
+    resolve(&mut program)?;
+
+    let mut scopes = Scopes::new();
+    scopes.budget = Budget::new(limits, cancel);
+    scopes.budget.start();
+
+    let top_scope = new_top_scope(&mut scopes)?;
+
+    let mut globals: Vec<(String, Value)> = scopes
+        .get_binding_idents(top_scope)
+        .into_iter()
+        .filter_map(|ident| {
+            let value = scopes.get_value(&ident, top_scope).ok()?;
+            Some((ident, value))
+        })
+        .collect();
+
+    globals.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let (idents, values): (Vec<String>, Vec<Value>) = globals.into_iter().unzip();
+    let function = compile(&program, &idents);
+
+    Machine::new(scopes, ctx).run(Rc::new(function), values, top_scope)
+}
@@ -0,0 +1,158 @@
+use std::rc::Rc;
+
+use crate::{
+    ast::{
+        funcs::ParamDeclare,
+        patterns::Pattern,
+        types::{
+            type_literal_variant::TypeVariantLiteral, variant_declare::VariantDeclare,
+            StructLiteralField,
+        },
+    },
+    resolved::value::Value,
+};
+
+// A single instruction. Operands are indices into the pools of the `Function` being run, or, for
+// jumps, into its code.
+#[derive(Debug, Clone, Copy)]
+pub enum Op {
+    Constant(usize),
+    Void,
+    Pop,
+
+    Load(usize),
+    Declare(usize),
+    // Binds the value on top of the stack to an immutable slot, as for functions and parameters.
+    Bind(usize),
+    Assign(usize),
+    SetIndex,
+    SetMember(usize),
+
+    Index,
+    Member(usize),
+    Array(usize),
+    // Builds a map from the given number of key and value pairs.
+    Map(usize),
+    Object(usize),
+    Set(usize),
+    CheckVariant(usize, usize),
+    Variant(usize, usize, bool),
+
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Equals,
+    Gt,
+    Lt,
+
+    Callee,
+    Call(usize, Option<usize>),
+    Closure(usize),
+    Return,
+    Type(usize),
+
+    Jump(usize),
+    JumpIfFalse(usize, Condition),
+    JumpIfUnbound(usize, usize),
+    // Ends the block early if the statement before it produced a value, leaving that value as the
+    // block's result. Otherwise the `Void` result is discarded.
+    ExitIfValue(usize),
+    Break,
+
+    EnterScope(usize),
+    ExitScope(usize),
+
+    IterStart,
+    IterNext(usize),
+    IterEnd,
+    ForResult(usize),
+    WilesResult(usize),
+
+    Match(usize, usize),
+    Try(usize),
+    EndTry,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Condition {
+    If,
+    Wiles,
+}
+
+// Where a binding lives: in a slot of the running frame, or in a cell captured by its closure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Location {
+    Local(usize),
+    Capture(usize),
+}
+
+// An identifier resolved to the binding it refers to, if it was declared anywhere.
+#[derive(Debug)]
+pub struct Lookup {
+    pub ident: String,
+    pub location: Option<Location>,
+}
+
+#[derive(Debug)]
+pub struct Declaration {
+    pub slot: usize,
+    pub declared_type: Option<TypeVariantLiteral>,
+    pub is_mutable: bool,
+}
+
+#[derive(Debug)]
+pub enum TypeDeclaration {
+    Enum(String, Option<String>, Vec<VariantDeclare>),
+    Struct(String, Option<String>, Vec<StructLiteralField>),
+}
+
+// Everything needed to create a closure over a nested function when its declaration runs.
+#[derive(Debug)]
+pub struct FuncProto {
+    pub params: Vec<ParamDeclare>,
+    pub return_type: TypeVariantLiteral,
+    pub type_param: Option<String>,
+    pub function: Rc<Function>,
+    pub captures: Vec<Location>,
+}
+
+#[derive(Debug)]
+pub struct SlotInfo {
+    pub ident: String,
+    pub captured: bool,
+}
+
+// A block that gets its own scope. Its slots are cleared each time it's entered, and if it declares
+// types they're bound in a type scope of their own.
+#[derive(Debug, Default)]
+pub struct ScopeInfo {
+    pub slots: Vec<usize>,
+    pub declares_types: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Function {
+    pub code: Vec<Op>,
+    pub params: Vec<usize>,
+    pub slots: Vec<SlotInfo>,
+    pub scopes: Vec<ScopeInfo>,
+
+    pub constants: Vec<Value>,
+    pub names: Vec<String>,
+    pub shapes: Vec<Vec<String>>,
+    pub lookups: Vec<Lookup>,
+    pub declarations: Vec<Declaration>,
+    pub types: Vec<TypeDeclaration>,
+    pub patterns: Vec<Pattern>,
+    pub type_args: Vec<TypeVariantLiteral>,
+    pub protos: Vec<FuncProto>,
+}
+
+impl Function {
+    // Whether the function's own scope declares types, in which case each call needs a fresh type
+    // scope to hold them.
+    pub fn declares_types(&self) -> bool {
+        self.scopes[0].declares_types
+    }
+}
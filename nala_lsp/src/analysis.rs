@@ -0,0 +1,482 @@
+use nala_interpreter::{
+    ast::{
+        funcs::Call, math::*, types::primitive_type::PrimitiveType, Expr, Line, PlaceExpression,
+        Primary, Program, Unary,
+    },
+    interpreter::Interpreter,
+    io_context::IoContext,
+    parser::{parse_program, parse_type},
+    resolved::struct_field::StructField,
+    types::{composite_type::CompositeType, nala_type::NalaType, type_variant::TypeVariant},
+};
+
+use crate::{
+    symbols::{Symbol, SymbolIndex, SymbolKind},
+    tokens::{tokenize, Token, KEYWORDS},
+};
+
+pub struct Diagnostic {
+    pub start: usize,
+    pub end: usize,
+    pub message: String,
+}
+
+pub struct Hover {
+    pub start: usize,
+    pub end: usize,
+    pub contents: String,
+}
+
+pub enum CompletionKind {
+    Enum,
+    Field,
+    Function,
+    Keyword,
+    Struct,
+    TypeParam,
+    Variable,
+}
+
+pub struct Completion {
+    pub label: String,
+    pub kind: CompletionKind,
+    pub detail: Option<String>,
+}
+
+// Scripts are never run for their side effects, so printing and reading do nothing.
+struct SilentContext;
+
+impl IoContext for SilentContext {
+    fn print(&mut self, _message: &str) {}
+
+    fn read(&mut self) -> Option<String> {
+        None
+    }
+}
+
+// What is known about a single version of a document. Types come from evaluating the declarations
+// which are safe to evaluate without running the script: structs, enums, functions and globals
+// initialized without calling anything.
+pub struct Analysis<'a> {
+    code: &'a str,
+    tokens: Vec<Token<'a>>,
+    symbols: SymbolIndex,
+    interpreter: Interpreter<SilentContext>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Analysis<'a> {
+    pub fn new(code: &'a str) -> Self {
+        let mut analysis = Analysis {
+            code,
+            tokens: tokenize(code),
+            symbols: SymbolIndex::build(code),
+            interpreter: Interpreter::new(SilentContext).expect("Builtins should always load."),
+            diagnostics: vec![],
+        };
+
+        match parse_program(code) {
+            Ok(program) => analysis.eval_declarations(program),
+            Err(error) => {
+                analysis.diagnostics.push(Diagnostic {
+                    start: error.start,
+                    end: error.end,
+                    message: error.message,
+                });
+
+                analysis.recover_types();
+            }
+        }
+
+        analysis
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn definition(&self, offset: usize) -> Option<(usize, usize)> {
+        let token = self.reference_at(offset)?;
+
+        self.symbols
+            .resolve(token.text, token.start)
+            .map(|symbol| (symbol.start, symbol.end))
+    }
+
+    pub fn hover(&mut self, offset: usize) -> Option<Hover> {
+        let (i, token) = self.ident_at(offset)?;
+        let (start, end) = (token.start, token.end);
+
+        let contents = if i > 0 && self.tokens[i - 1].is(".") {
+            let mut path = self.object_path(i - 1);
+            path.push(token.text.to_owned());
+
+            let field_type = self.path_type(&path, offset)?;
+            format!("{}: {field_type}", path.last().unwrap())
+        } else if let Some(symbol) = self
+            .symbols
+            .symbol_at(offset)
+            .or_else(|| self.symbols.resolve(token.text, token.start))
+            .cloned()
+        {
+            self.describe(&symbol)
+        } else {
+            let name = token.text.to_owned();
+
+            match self.interpreter.get_global(&name) {
+                Ok(value) => {
+                    let value_type = self.interpreter.infer_type(&value).ok()?;
+                    format!("{name}: {value_type}")
+                }
+                Err(_) => {
+                    self.interpreter.get_global_type(&name).ok()?;
+                    name
+                }
+            }
+        };
+
+        Some(Hover {
+            start,
+            end,
+            contents,
+        })
+    }
+
+    pub fn completions(&mut self, offset: usize) -> Vec<Completion> {
+        // The identifier being typed, if any, is replaced by the completion, so look at what
+        // precedes it.
+        let i = self
+            .tokens
+            .iter()
+            .position(|t| t.is_ident() && t.start < offset && offset <= t.end)
+            .unwrap_or_else(|| self.tokens.iter().filter(|t| t.end <= offset).count());
+
+        if i > 0 && self.tokens[i - 1].is(".") {
+            let path = self.object_path(i - 1);
+            return self.field_completions(&path, offset);
+        }
+
+        let mut completions: Vec<Completion> = self
+            .symbols
+            .visible(offset)
+            .into_iter()
+            .map(|symbol| Completion {
+                label: symbol.name.clone(),
+                kind: match symbol.kind {
+                    SymbolKind::Enum => CompletionKind::Enum,
+                    SymbolKind::Function => CompletionKind::Function,
+                    SymbolKind::Param | SymbolKind::Variable => CompletionKind::Variable,
+                    SymbolKind::Struct => CompletionKind::Struct,
+                    SymbolKind::TypeParam => CompletionKind::TypeParam,
+                },
+                detail: symbol
+                    .signature
+                    .clone()
+                    .or_else(|| symbol.declared_type.clone()),
+            })
+            .collect();
+
+        let mut globals = self.interpreter.global_idents();
+        globals.sort();
+
+        for ident in globals {
+            if completions.iter().any(|c| c.label == ident) {
+                continue;
+            }
+
+            let (kind, detail) = match self.interpreter.get_global(&ident) {
+                Ok(value) => {
+                    let value_type = self.interpreter.infer_type(&value).ok();
+                    let kind = match value_type {
+                        Some(TypeVariant::Composite(CompositeType {
+                            outer: NalaType::PrimitiveType(PrimitiveType::Func),
+                            ..
+                        })) => CompletionKind::Function,
+                        _ => CompletionKind::Variable,
+                    };
+
+                    (kind, value_type.map(|t| t.to_string()))
+                }
+                Err(_) => match self.interpreter.get_global_type(&ident) {
+                    Ok(TypeVariant::Type(NalaType::Struct(_))) => (CompletionKind::Struct, None),
+                    _ => (CompletionKind::Enum, None),
+                },
+            };
+
+            completions.push(Completion {
+                label: ident,
+                kind,
+                detail,
+            });
+        }
+
+        completions.extend(KEYWORDS.iter().map(|keyword| Completion {
+            label: keyword.to_string(),
+            kind: CompletionKind::Keyword,
+            detail: None,
+        }));
+
+        completions
+    }
+
+    fn eval_declarations(&mut self, program: Program) {
+        let lines = match program {
+            Program::Block(lines) => lines,
+            Program::Lines(lines) => lines,
+        };
+
+        for line in lines {
+            let ident = match &line {
+                Line::Enum(ident, ..) | Line::Struct(ident, ..) => ident.clone(),
+                Line::Func(func) => func.ident.clone(),
+                Line::Declare(_, expr, ..) if !has_calls(expr) => {
+                    // Globals may depend on others initialized with calls, which are skipped, so
+                    // their errors aren't reported.
+                    let _ = self.interpreter.eval_program(Program::Lines(vec![line]));
+                    continue;
+                }
+                _ => continue,
+            };
+
+            if let Err(error) = self.interpreter.eval_program(Program::Lines(vec![line])) {
+                let (start, end) = self
+                    .symbols
+                    .resolve(&ident, self.code.len())
+                    .map_or((0, 0), |symbol| (symbol.start, symbol.end));
+
+                self.diagnostics.push(Diagnostic {
+                    start,
+                    end,
+                    message: error.message,
+                });
+            }
+        }
+    }
+
+    // Code being edited often doesn't parse, but its types are still needed for hovers and
+    // completions. Each global struct and enum is parsed on its own in that case, and those which
+    // parse are evaluated.
+    fn recover_types(&mut self) {
+        let declarations: Vec<(usize, usize)> = self
+            .symbols
+            .globals()
+            .filter(|symbol| matches!(symbol.kind, SymbolKind::Struct | SymbolKind::Enum))
+            .filter_map(|symbol| self.declaration_span(symbol))
+            .collect();
+
+        for (start, end) in declarations {
+            if let Ok(program) = parse_program(&self.code[start..end]) {
+                let _ = self.interpreter.eval_program(program);
+            }
+        }
+    }
+
+    // The source of a declaration, from its keyword to the brace closing its body.
+    fn declaration_span(&self, symbol: &Symbol) -> Option<(usize, usize)> {
+        let i = self.tokens.iter().position(|t| t.start == symbol.start)?;
+        let keyword = self.tokens.get(i.checked_sub(1)?)?;
+        let mut depth = 0;
+
+        for token in self.tokens[i..].iter() {
+            if token.is("{") {
+                depth += 1;
+            } else if token.is("}") {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some((keyword.start, token.end));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn describe(&mut self, symbol: &Symbol) -> String {
+        let name = &symbol.name;
+
+        match symbol.kind {
+            SymbolKind::Enum => format!("enum {name}"),
+            SymbolKind::Function => symbol
+                .signature
+                .clone()
+                .unwrap_or_else(|| format!("func {name}")),
+            SymbolKind::Param | SymbolKind::Variable => {
+                let keyword = symbol
+                    .keyword
+                    .map(|keyword| format!("{keyword} "))
+                    .unwrap_or_default();
+
+                match self.symbol_type(symbol) {
+                    Some(symbol_type) => format!("{keyword}{name}: {symbol_type}"),
+                    None => format!("{keyword}{name}"),
+                }
+            }
+            SymbolKind::Struct => match self.interpreter.get_global_type(name) {
+                Ok(struct_type) if self.symbols.is_global(symbol) => {
+                    format!("struct {name} {struct_type}")
+                }
+                _ => format!("struct {name}"),
+            },
+            SymbolKind::TypeParam => format!("type parameter {name}"),
+        }
+    }
+
+    // The declared type of a symbol, or failing that the inferred type of a global.
+    fn symbol_type(&mut self, symbol: &Symbol) -> Option<TypeVariant> {
+        if let Some(declared_type) = &symbol.declared_type {
+            let literal = parse_type(declared_type)?;
+            return self.interpreter.resolve_type(literal).ok();
+        }
+
+        if self.symbols.is_global(symbol) {
+            let value = self.interpreter.get_global(&symbol.name).ok()?;
+            return self.interpreter.infer_type(&value).ok();
+        }
+
+        None
+    }
+
+    // The type of a chain of member accesses such as `person.address.city`, referenced at `offset`.
+    fn path_type(&mut self, path: &[String], offset: usize) -> Option<TypeVariant> {
+        let (first, members) = path.split_first()?;
+
+        let mut current = match self.symbols.resolve(first, offset).cloned() {
+            Some(symbol) => self.symbol_type(&symbol)?,
+            None => {
+                let value = self.interpreter.get_global(first).ok()?;
+                self.interpreter.infer_type(&value).ok()?
+            }
+        };
+
+        for member in members {
+            current = struct_fields(&current)?
+                .into_iter()
+                .find(|field| &field.ident == member)?
+                .value_type;
+        }
+
+        Some(current)
+    }
+
+    fn field_completions(&mut self, path: &[String], offset: usize) -> Vec<Completion> {
+        let fields = self
+            .path_type(path, offset)
+            .and_then(|object_type| struct_fields(&object_type))
+            .unwrap_or_default();
+
+        fields
+            .into_iter()
+            .map(|field| Completion {
+                label: field.ident,
+                kind: CompletionKind::Field,
+                detail: Some(field.value_type.to_string()),
+            })
+            .collect()
+    }
+
+    // The chain of identifiers before the `.` at `dot`, such as `person.address` in
+    // `person.address.city`.
+    fn object_path(&self, dot: usize) -> Vec<String> {
+        let mut path = vec![];
+        let mut j = dot;
+
+        while j >= 1 && self.tokens[j - 1].is_ident() {
+            path.push(self.tokens[j - 1].text.to_owned());
+
+            if j >= 2 && self.tokens[j - 2].is(".") {
+                j -= 2;
+            } else {
+                break;
+            }
+        }
+
+        path.reverse();
+        path
+    }
+
+    // The identifier at `offset` if it refers to a declaration, rather than being a member access
+    // or an object key.
+    fn reference_at(&self, offset: usize) -> Option<&Token<'a>> {
+        let (i, token) = self.ident_at(offset)?;
+
+        if self.symbols.symbol_at(offset).is_some() {
+            return Some(token);
+        }
+
+        let is_member = i > 0 && self.tokens[i - 1].is(".");
+        let is_key = self.tokens.get(i + 1).is_some_and(|next| next.is(":"))
+            && !self.tokens.get(i + 2).is_some_and(|next| next.is(":"));
+
+        if is_member || is_key {
+            None
+        } else {
+            Some(token)
+        }
+    }
+
+    fn ident_at(&self, offset: usize) -> Option<(usize, &Token<'a>)> {
+        self.tokens
+            .iter()
+            .enumerate()
+            .find(|(_, token)| token.start <= offset && offset <= token.end && token.is_ident())
+    }
+}
+
+fn struct_fields(struct_type: &TypeVariant) -> Option<Vec<StructField>> {
+    match struct_type {
+        TypeVariant::Type(NalaType::Struct(fields))
+        | TypeVariant::Composite(CompositeType {
+            outer: NalaType::Struct(fields),
+            ..
+        }) => Some(fields.clone()),
+        _ => None,
+    }
+}
+
+fn has_calls(expr: &Expr) -> bool {
+    match expr {
+        Expr::Addition(addition) => addition_has_calls(addition),
+        Expr::Eq(left, right) | Expr::Gt(left, right) | Expr::Lt(left, right) => {
+            has_calls(left) || addition_has_calls(right)
+        }
+    }
+}
+
+fn addition_has_calls(addition: &Addition) -> bool {
+    match addition {
+        Addition::Add(left, right) | Addition::Sub(left, right) => {
+            addition_has_calls(left) || multiplication_has_calls(right)
+        }
+        Addition::Multiplication(multiplication) => multiplication_has_calls(multiplication),
+    }
+}
+
+fn multiplication_has_calls(multiplication: &Multiplication) -> bool {
+    match multiplication {
+        Multiplication::Mult(left, _) | Multiplication::Div(left, _) => {
+            multiplication_has_calls(left)
+        }
+        Multiplication::Unary(Unary::Primary(primary)) => match primary {
+            Primary::Array(array) => array.elems.iter().any(has_calls),
+            Primary::Call(Call::Call(..)) => true,
+            Primary::Call(Call::PlaceExpression(place)) => place_has_calls(place),
+            Primary::EnumVariant(_, _, data) => data.as_ref().is_some_and(|data| has_calls(data)),
+            Primary::Literal(_) => false,
+            Primary::Map(map) => map
+                .entries
+                .iter()
+                .any(|entry| has_calls(&entry.key) || has_calls(&entry.value)),
+            Primary::Object(object) => object.entries.iter().any(|entry| has_calls(&entry.value)),
+            Primary::Set(set) => set.elems.iter().any(has_calls),
+        },
+    }
+}
+
+fn place_has_calls(place: &PlaceExpression) -> bool {
+    match place {
+        PlaceExpression::Identifier(..) => false,
+        PlaceExpression::Index(place, index) => place_has_calls(place) || has_calls(index),
+        PlaceExpression::MemberAccess(place, _) => place_has_calls(place),
+    }
+}
@@ -0,0 +1,53 @@
+use lsp_types::{Position, Range};
+
+// Converts between byte offsets and LSP positions, whose characters are counted in UTF-16 code units.
+pub struct Document {
+    pub text: String,
+    line_starts: Vec<usize>,
+}
+
+impl Document {
+    pub fn new(text: String) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self { text, line_starts }
+    }
+
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.text.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+
+        let character = self.text[line_start..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum::<usize>();
+
+        Position::new(line as u32, character as u32)
+    }
+
+    pub fn range(&self, start: usize, end: usize) -> Range {
+        Range::new(self.position(start), self.position(end))
+    }
+
+    pub fn offset(&self, position: Position) -> usize {
+        let line_start = match self.line_starts.get(position.line as usize) {
+            Some(start) => *start,
+            None => return self.text.len(),
+        };
+
+        let mut character = 0;
+
+        for (i, c) in self.text[line_start..].char_indices() {
+            if character >= position.character as usize || c == '\n' {
+                return line_start + i;
+            }
+
+            character += c.len_utf16();
+        }
+
+        self.text.len()
+    }
+}
@@ -0,0 +1,19 @@
+mod analysis;
+mod document;
+mod server;
+mod symbols;
+mod tokens;
+
+use lsp_server::Connection;
+
+use server::ServerError;
+
+// Speaks the Language Server Protocol over stdio. Editors should launch this binary for `.nl` files.
+fn main() -> Result<(), ServerError> {
+    let (connection, io_threads) = Connection::stdio();
+
+    server::run(connection)?;
+    io_threads.join()?;
+
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use std::{collections::HashMap, error::Error};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, Request as _},
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+    Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams, HoverProviderCapability, Location,
+    MarkupContent, MarkupKind, OneOf, PublishDiagnosticsParams, SaveOptions, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, Url,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    analysis::{Analysis, CompletionKind},
+    document::Document,
+};
+
+pub type ServerError = Box<dyn Error + Send + Sync>;
+
+pub fn run(connection: Connection) -> Result<(), ServerError> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                    include_text: Some(true),
+                })),
+                ..Default::default()
+            },
+        )),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![String::from(".")]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut server = Server {
+        connection: &connection,
+        documents: HashMap::new(),
+    };
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+
+                server.handle_request(request)?;
+            }
+            Message::Notification(notification) => server.handle_notification(notification)?,
+            Message::Response(_) => (),
+        }
+    }
+
+    Ok(())
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    documents: HashMap<Url, Document>,
+}
+
+impl Server<'_> {
+    fn handle_request(&mut self, request: Request) -> Result<(), ServerError> {
+        let id = request.id.clone();
+
+        let result = match request.method.as_str() {
+            GotoDefinition::METHOD => {
+                let params: GotoDefinitionParams = parse_params(request.params)?;
+                serde_json::to_value(self.definition(params))?
+            }
+            HoverRequest::METHOD => {
+                let params: HoverParams = parse_params(request.params)?;
+                serde_json::to_value(self.hover(params))?
+            }
+            Completion::METHOD => {
+                let params: CompletionParams = parse_params(request.params)?;
+                serde_json::to_value(self.completion(params))?
+            }
+            method => {
+                let message = format!("Unsupported request `{method}`.");
+                let response = Response::new_err(id, ErrorCode::MethodNotFound as i32, message);
+                return self.send(response.into());
+            }
+        };
+
+        self.respond(id, result)
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), ServerError> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = parse_params(notification.params)?;
+                let uri = params.text_document.uri;
+
+                self.documents
+                    .insert(uri.clone(), Document::new(params.text_document.text));
+
+                self.publish_diagnostics(uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = parse_params(notification.params)?;
+
+                // Only full document sync is supported, so the last change holds the whole text.
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents
+                        .insert(params.text_document.uri, Document::new(change.text));
+                }
+
+                Ok(())
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: DidSaveTextDocumentParams = parse_params(notification.params)?;
+                let uri = params.text_document.uri;
+
+                if let Some(text) = params.text {
+                    self.documents.insert(uri.clone(), Document::new(text));
+                }
+
+                self.publish_diagnostics(uri)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = parse_params(notification.params)?;
+                let uri = params.text_document.uri;
+
+                self.documents.remove(&uri);
+                self.send_diagnostics(uri, vec![])
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        let document = self.documents.get(&uri)?;
+
+        let analysis = Analysis::new(&document.text);
+        let (start, end) = analysis.definition(document.offset(position.position))?;
+
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            uri,
+            document.range(start, end),
+        )))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let document = self.documents.get(&position.text_document.uri)?;
+
+        let mut analysis = Analysis::new(&document.text);
+        let hover = analysis.hover(document.offset(position.position))?;
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```nala\n{}\n```", hover.contents),
+            }),
+            range: Some(document.range(hover.start, hover.end)),
+        })
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let document = self.documents.get(&position.text_document.uri)?;
+
+        let mut analysis = Analysis::new(&document.text);
+
+        let items = analysis
+            .completions(document.offset(position.position))
+            .into_iter()
+            .map(|completion| CompletionItem {
+                label: completion.label,
+                kind: Some(match completion.kind {
+                    CompletionKind::Enum => CompletionItemKind::ENUM,
+                    CompletionKind::Field => CompletionItemKind::FIELD,
+                    CompletionKind::Function => CompletionItemKind::FUNCTION,
+                    CompletionKind::Keyword => CompletionItemKind::KEYWORD,
+                    CompletionKind::Struct => CompletionItemKind::STRUCT,
+                    CompletionKind::TypeParam => CompletionItemKind::TYPE_PARAMETER,
+                    CompletionKind::Variable => CompletionItemKind::VARIABLE,
+                }),
+                detail: completion.detail,
+                ..Default::default()
+            })
+            .collect();
+
+        Some(CompletionResponse::Array(items))
+    }
+
+    fn publish_diagnostics(&self, uri: Url) -> Result<(), ServerError> {
+        let diagnostics = match self.documents.get(&uri) {
+            Some(document) => Analysis::new(&document.text)
+                .diagnostics()
+                .iter()
+                .map(|diagnostic| Diagnostic {
+                    range: document.range(diagnostic.start, diagnostic.end),
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some(String::from("nala")),
+                    message: diagnostic.message.clone(),
+                    ..Default::default()
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        self.send_diagnostics(uri, diagnostics)
+    }
+
+    fn send_diagnostics(&self, uri: Url, diagnostics: Vec<Diagnostic>) -> Result<(), ServerError> {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_owned(), params);
+        self.send(notification.into())
+    }
+
+    fn respond(&self, id: RequestId, result: serde_json::Value) -> Result<(), ServerError> {
+        self.send(Response::new_ok(id, result).into())
+    }
+
+    fn send(&self, message: Message) -> Result<(), ServerError> {
+        self.connection.sender.send(message)?;
+        Ok(())
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: serde_json::Value) -> Result<T, ServerError> {
+    Ok(serde_json::from_value(params)?)
+}
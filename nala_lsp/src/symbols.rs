@@ -0,0 +1,334 @@
+use crate::tokens::{tokenize, Token, TokenKind};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SymbolKind {
+    Enum,
+    Function,
+    Param,
+    Struct,
+    TypeParam,
+    Variable,
+}
+
+#[derive(Clone, Debug)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    // The keyword which declared a variable, either `const` or `mut`. Variables bound by `for`,
+    // `catch` and match patterns have none.
+    pub keyword: Option<&'static str>,
+    // The source text of the declared type, if any.
+    pub declared_type: Option<String>,
+    // The source text of a function's declaration, up to its block.
+    pub signature: Option<String>,
+    pub start: usize,
+    pub end: usize,
+    scope: usize,
+    visible_from: usize,
+}
+
+// A block delimited by braces. Object literals are treated as blocks too, which is harmless since
+// nothing can be declared inside them.
+struct BlockScope {
+    start: usize,
+    end: usize,
+    parent: Option<usize>,
+}
+
+// Every declaration in a file and the block it is visible in.
+pub struct SymbolIndex {
+    scopes: Vec<BlockScope>,
+    symbols: Vec<Symbol>,
+}
+
+impl SymbolIndex {
+    pub fn build(code: &str) -> Self {
+        let tokens: Vec<Token> = tokenize(code)
+            .into_iter()
+            .filter(|token| token.kind != TokenKind::Comment)
+            .collect();
+
+        let mut builder = Builder {
+            code,
+            tokens: &tokens,
+            index: SymbolIndex {
+                scopes: vec![BlockScope {
+                    start: 0,
+                    end: code.len(),
+                    parent: None,
+                }],
+                symbols: vec![],
+            },
+            scope_stack: vec![0],
+            pending: vec![],
+        };
+
+        for i in 0..tokens.len() {
+            builder.visit(i);
+        }
+
+        builder.index
+    }
+
+    // The declaration whose name is at `offset`, if any.
+    pub fn symbol_at(&self, offset: usize) -> Option<&Symbol> {
+        self.symbols
+            .iter()
+            .find(|symbol| symbol.start <= offset && offset <= symbol.end)
+    }
+
+    // Finds the declaration that `name`, referenced at `offset`, refers to.
+    pub fn resolve(&self, name: &str, offset: usize) -> Option<&Symbol> {
+        self.scope_chain(offset).into_iter().find_map(|scope| {
+            self.symbols
+                .iter()
+                .filter(|symbol| {
+                    symbol.scope == scope && symbol.name == name && symbol.visible_from <= offset
+                })
+                .max_by_key(|symbol| symbol.visible_from)
+        })
+    }
+
+    // Every declaration visible at `offset`, excluding those shadowed by an inner declaration.
+    pub fn visible(&self, offset: usize) -> Vec<&Symbol> {
+        let mut visible = Vec::<&Symbol>::new();
+
+        for scope in self.scope_chain(offset) {
+            for symbol in self.symbols.iter() {
+                if symbol.scope == scope
+                    && symbol.visible_from <= offset
+                    && !visible.iter().any(|s| s.name == symbol.name)
+                {
+                    visible.push(symbol);
+                }
+            }
+        }
+
+        visible
+    }
+
+    pub fn globals(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.iter().filter(|symbol| symbol.scope == 0)
+    }
+
+    pub fn is_global(&self, symbol: &Symbol) -> bool {
+        symbol.scope == 0
+    }
+
+    fn scope_chain(&self, offset: usize) -> Vec<usize> {
+        let innermost = self
+            .scopes
+            .iter()
+            .enumerate()
+            .filter(|(_, scope)| scope.start <= offset && offset < scope.end)
+            .max_by_key(|(_, scope)| scope.start)
+            .map_or(0, |(i, _)| i);
+
+        let mut chain = vec![innermost];
+
+        while let Some(parent) = self.scopes[*chain.last().unwrap()].parent {
+            chain.push(parent);
+        }
+
+        chain
+    }
+}
+
+struct Builder<'a> {
+    code: &'a str,
+    tokens: &'a [Token<'a>],
+    index: SymbolIndex,
+    scope_stack: Vec<usize>,
+    // Symbols declared ahead of the block they belong to, such as function parameters.
+    pending: Vec<Symbol>,
+}
+
+impl<'a> Builder<'a> {
+    fn visit(&mut self, i: usize) {
+        let token = &self.tokens[i];
+
+        match token.text {
+            "{" if token.kind == TokenKind::Punct => self.open_scope(token.start),
+            "}" if token.kind == TokenKind::Punct => self.close_scope(token.end),
+            "const" | "mut" if self.ident_at(i + 1) => {
+                let keyword = if token.text == "const" {
+                    "const"
+                } else {
+                    "mut"
+                };
+                let declared_type = self.declared_type(i + 2, &["="]);
+                let mut symbol = self.symbol(i + 1, SymbolKind::Variable);
+                symbol.keyword = Some(keyword);
+                symbol.declared_type = declared_type;
+                self.declare(symbol);
+            }
+            "func" if self.ident_at(i + 1) => self.visit_func(i),
+            "struct" if self.ident_at(i + 1) => self.declare_hoisted(i + 1, SymbolKind::Struct),
+            "enum" if self.ident_at(i + 1) => self.declare_hoisted(i + 1, SymbolKind::Enum),
+            "for" if self.ident_at(i + 1) => {
+                let symbol = self.symbol(i + 1, SymbolKind::Variable);
+                self.pending.push(symbol);
+            }
+            "catch" if self.is(i + 1, "(") && self.ident_at(i + 2) => {
+                let symbol = self.symbol(i + 2, SymbolKind::Variable);
+                self.pending.push(symbol);
+            }
+            // A pattern binding such as `Option::Some(value) =>`.
+            "::" if self.ident_at(i + 1)
+                && self.is(i + 2, "(")
+                && self.ident_at(i + 3)
+                && self.is(i + 4, ")")
+                && self.is(i + 5, "=>") =>
+            {
+                let symbol = self.symbol(i + 3, SymbolKind::Variable);
+                self.pending.push(symbol);
+            }
+            _ => (),
+        }
+    }
+
+    fn visit_func(&mut self, i: usize) {
+        let mut j = i + 2;
+
+        if self.is(j, "<") && self.ident_at(j + 1) {
+            let symbol = self.symbol(j + 1, SymbolKind::TypeParam);
+            self.pending.push(symbol);
+            j += 3;
+        }
+
+        if self.is(j, "(") {
+            let mut depth = 0;
+
+            while let Some(token) = self.tokens.get(j) {
+                match token.text {
+                    "(" | "<" => depth += 1,
+                    ")" | ">" => depth -= 1,
+                    _ => (),
+                }
+
+                if depth == 0 {
+                    break;
+                }
+
+                if depth == 1 && self.ident_at(j) && self.is(j + 1, ":") {
+                    let mut symbol = self.symbol(j, SymbolKind::Param);
+                    symbol.declared_type = self.declared_type(j + 1, &[",", ")"]);
+                    self.pending.push(symbol);
+                }
+
+                j += 1;
+            }
+        }
+
+        let signature_end = self.tokens[i..]
+            .iter()
+            .find(|token| token.is("{"))
+            .map_or(self.code.len(), |token| token.start);
+
+        let mut symbol = self.symbol(i + 1, SymbolKind::Function);
+        symbol.signature = Some(normalize(&self.code[self.tokens[i].start..signature_end]));
+        self.declare_hoisted_symbol(symbol);
+    }
+
+    // Reads a type annotation starting with the `:` at `colon`, up to one of `terminators` outside
+    // of any type arguments.
+    fn declared_type(&self, colon: usize, terminators: &[&str]) -> Option<String> {
+        if !self.is(colon, ":") {
+            return None;
+        }
+
+        let start = self.tokens.get(colon + 1)?.start;
+        let mut end = start;
+        let mut depth = 0;
+
+        for token in self.tokens[colon + 1..].iter() {
+            if depth == 0 && terminators.contains(&token.text) {
+                break;
+            }
+
+            match token.text {
+                "<" => depth += 1,
+                ">" => depth -= 1,
+                _ => (),
+            }
+
+            end = token.end;
+        }
+
+        Some(normalize(&self.code[start..end]))
+    }
+
+    fn open_scope(&mut self, start: usize) {
+        let scope = self.index.scopes.len();
+
+        self.index.scopes.push(BlockScope {
+            start,
+            end: self.code.len(),
+            parent: self.scope_stack.last().copied(),
+        });
+
+        self.scope_stack.push(scope);
+
+        for mut symbol in self.pending.drain(..) {
+            symbol.scope = scope;
+            symbol.visible_from = start;
+            self.index.symbols.push(symbol);
+        }
+    }
+
+    fn close_scope(&mut self, end: usize) {
+        if self.scope_stack.len() > 1 {
+            let scope = self.scope_stack.pop().unwrap();
+            self.index.scopes[scope].end = end;
+        }
+    }
+
+    fn declare(&mut self, mut symbol: Symbol) {
+        symbol.scope = *self.scope_stack.last().unwrap();
+        symbol.visible_from = symbol.end;
+        self.index.symbols.push(symbol);
+    }
+
+    fn declare_hoisted(&mut self, i: usize, kind: SymbolKind) {
+        let symbol = self.symbol(i, kind);
+        self.declare_hoisted_symbol(symbol);
+    }
+
+    // Types and functions are visible throughout their block, so that they can be referred to
+    // before their declaration, such as by a function declared earlier.
+    fn declare_hoisted_symbol(&mut self, mut symbol: Symbol) {
+        let scope = *self.scope_stack.last().unwrap();
+        symbol.scope = scope;
+        symbol.visible_from = self.index.scopes[scope].start;
+        self.index.symbols.push(symbol);
+    }
+
+    fn symbol(&self, i: usize, kind: SymbolKind) -> Symbol {
+        let token = &self.tokens[i];
+
+        Symbol {
+            name: token.text.to_owned(),
+            kind,
+            keyword: None,
+            declared_type: None,
+            signature: None,
+            start: token.start,
+            end: token.end,
+            scope: 0,
+            visible_from: 0,
+        }
+    }
+
+    fn is(&self, i: usize, text: &str) -> bool {
+        self.tokens.get(i).is_some_and(|token| token.is(text))
+    }
+
+    fn ident_at(&self, i: usize) -> bool {
+        self.tokens.get(i).is_some_and(|token| token.is_ident())
+    }
+}
+
+// Collapses whitespace, so that source spanning several lines reads well in a single line.
+fn normalize(code: &str) -> String {
+    code.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
@@ -0,0 +1,99 @@
+// A lightweight scanner used to locate identifiers and declarations, since the AST does not record
+// source positions. It only needs to be accurate enough to find identifiers and braces, so it is
+// tolerant of code which doesn't parse.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenKind {
+    Comment,
+    Ident,
+    Number,
+    Punct,
+    String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Token<'_> {
+    pub fn is(&self, text: &str) -> bool {
+        self.kind != TokenKind::String && self.kind != TokenKind::Comment && self.text == text
+    }
+
+    pub fn is_ident(&self) -> bool {
+        self.kind == TokenKind::Ident && !KEYWORDS.contains(&self.text)
+    }
+}
+
+pub const KEYWORDS: [&str; 16] = [
+    "break", "catch", "const", "elif", "else", "enum", "for", "func", "if", "in", "match", "mut",
+    "struct", "test", "try", "wiles",
+];
+
+const PUNCTUATION: [&str; 3] = ["::<", "::", "=>"];
+
+pub fn tokenize(code: &str) -> Vec<Token<'_>> {
+    let bytes = code.as_bytes();
+    let mut tokens = Vec::<Token>::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        let start = pos;
+
+        let kind = if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        } else if code[pos..].starts_with("//") {
+            pos = find_from(code, pos, "\n").unwrap_or(code.len());
+            TokenKind::Comment
+        } else if code[pos..].starts_with("/*") {
+            pos = find_from(code, pos + 2, "*/").map_or(code.len(), |end| end + 2);
+            TokenKind::Comment
+        } else if c == b'\'' {
+            pos = find_from(code, pos + 1, "'").map_or(code.len(), |end| end + 1);
+            TokenKind::String
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            pos = scan_while(bytes, pos, |c| c.is_ascii_alphanumeric() || c == b'_');
+            TokenKind::Ident
+        } else if c.is_ascii_digit() {
+            pos = scan_while(bytes, pos, |c| c.is_ascii_digit() || c == b'.');
+            TokenKind::Number
+        } else {
+            let punctuation = PUNCTUATION.iter().find(|p| code[pos..].starts_with(*p));
+
+            // Any other character is a token of its own, taking care not to split a multi-byte
+            // character.
+            pos += punctuation.map_or_else(
+                || code[pos..].chars().next().unwrap().len_utf8(),
+                |p| p.len(),
+            );
+            TokenKind::Punct
+        };
+
+        tokens.push(Token {
+            kind,
+            text: &code[start..pos],
+            start,
+            end: pos,
+        });
+    }
+
+    tokens
+}
+
+fn find_from(code: &str, from: usize, pattern: &str) -> Option<usize> {
+    code[from..].find(pattern).map(|index| from + index)
+}
+
+fn scan_while(bytes: &[u8], mut pos: usize, predicate: impl Fn(u8) -> bool) -> usize {
+    while pos < bytes.len() && predicate(bytes[pos]) {
+        pos += 1;
+    }
+
+    pos
+}
@@ -0,0 +1,329 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+use serde_json::{json, Value};
+
+const URI: &str = "file:///project/main.nl";
+
+// Drives the server over stdio the way an editor would.
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_nala_lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+
+        let mut client = Client {
+            child,
+            stdin,
+            stdout,
+            next_id: 0,
+        };
+
+        client.request("initialize", json!({ "capabilities": {} }));
+        client.notify("initialized", json!({}));
+        client
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+        loop {
+            let message = self.receive();
+
+            if message["id"] == json!(id) {
+                return message["result"].clone();
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    fn open(&mut self, text: &str) {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": URI, "languageId": "nala", "version": 1, "text": text }
+            }),
+        );
+    }
+
+    fn diagnostics(&mut self) -> Vec<Value> {
+        loop {
+            let message = self.receive();
+
+            if message["method"] == "textDocument/publishDiagnostics" {
+                return message["params"]["diagnostics"].as_array().unwrap().clone();
+            }
+        }
+    }
+
+    fn at(&mut self, method: &str, line: u32, character: u32) -> Value {
+        self.request(
+            method,
+            json!({
+                "textDocument": { "uri": URI },
+                "position": { "line": line, "character": character }
+            }),
+        )
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+
+        loop {
+            let mut header = String::new();
+            self.stdout.read_line(&mut header).unwrap();
+            let header = header.trim();
+
+            if header.is_empty() {
+                break;
+            }
+
+            if let Some(value) = header.strip_prefix("Content-Length: ") {
+                length = value.parse().unwrap();
+            }
+        }
+
+        let mut body = vec![0; length];
+        self.stdout.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn shutdown(mut self) {
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+fn labels(completions: &Value) -> Vec<&str> {
+    completions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn it_publishes_parse_errors() {
+    let mut client = Client::start();
+
+    client.open("const x = 5;\nconst = 6;\n");
+    let diagnostics = client.diagnostics();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 1, "character": 6 })
+    );
+    assert_eq!(diagnostics[0]["severity"], json!(1));
+
+    client.notify(
+        "textDocument/didSave",
+        json!({ "textDocument": { "uri": URI }, "text": "const x = 5;\nconst y = 6;\n" }),
+    );
+
+    assert!(client.diagnostics().is_empty());
+    client.shutdown();
+}
+
+#[test]
+fn it_publishes_type_errors() {
+    let mut client = Client::start();
+
+    client.open("func greet(person: Person): Void {\n    print(person);\n}\n");
+    let diagnostics = client.diagnostics();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0]["range"],
+        json!({ "start": { "line": 0, "character": 5 }, "end": { "line": 0, "character": 10 } })
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_goes_to_definitions() {
+    let mut client = Client::start();
+
+    client.open(
+        r#"struct Point {
+    x: Number,
+}
+
+func double(n: Number): Number {
+    n * 2;
+}
+
+const point: Point = { x: 3 };
+const result = double(point.x);
+"#,
+    );
+
+    client.diagnostics();
+
+    // The function.
+    let definition = client.at("textDocument/definition", 9, 16);
+    assert_eq!(
+        definition["range"],
+        json!({ "start": { "line": 4, "character": 5 }, "end": { "line": 4, "character": 11 } })
+    );
+
+    // The parameter.
+    let definition = client.at("textDocument/definition", 5, 4);
+    assert_eq!(
+        definition["range"]["start"],
+        json!({ "line": 4, "character": 12 })
+    );
+
+    // The struct.
+    let definition = client.at("textDocument/definition", 8, 14);
+    assert_eq!(
+        definition["range"]["start"],
+        json!({ "line": 0, "character": 7 })
+    );
+
+    // A member access is not a reference to a binding.
+    let definition = client.at("textDocument/definition", 9, 28);
+    assert_eq!(definition, Value::Null);
+
+    client.shutdown();
+}
+
+#[test]
+fn it_shows_types_on_hover() {
+    let mut client = Client::start();
+
+    client.open(
+        r#"struct Point {
+    x: Number,
+}
+
+const point: Point = { x: 3 };
+mut names = ['a', 'b'];
+func show(p: Point): Void {
+    print(p.x);
+}
+"#,
+    );
+
+    client.diagnostics();
+
+    let hover = client.at("textDocument/hover", 5, 5);
+    assert_eq!(
+        hover["contents"]["value"],
+        "```nala\nmut names: Array<String>\n```"
+    );
+
+    let hover = client.at("textDocument/hover", 4, 7);
+    assert_eq!(
+        hover["contents"]["value"],
+        "```nala\nconst point: { x: Number, }\n```"
+    );
+
+    let hover = client.at("textDocument/hover", 6, 6);
+    assert_eq!(
+        hover["contents"]["value"],
+        "```nala\nfunc show(p: Point): Void\n```"
+    );
+
+    let hover = client.at("textDocument/hover", 7, 12);
+    assert_eq!(hover["contents"]["value"], "```nala\nx: Number\n```");
+
+    let hover = client.at("textDocument/hover", 7, 5);
+    assert_eq!(
+        hover["contents"]["value"],
+        "```nala\nprint: Func<T,Void>\n```"
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_completes_bindings_in_scope() {
+    let mut client = Client::start();
+
+    client.open(
+        r#"const outer = 1;
+func foo(param: Number): Void {
+    const inner = 2;
+    
+}
+"#,
+    );
+
+    client.diagnostics();
+
+    let inside = client.at("textDocument/completion", 3, 4);
+
+    for expected in ["outer", "foo", "param", "inner", "print", "Option", "const"] {
+        assert!(labels(&inside).contains(&expected), "missing {expected}");
+    }
+
+    let outside = client.at("textDocument/completion", 5, 0);
+
+    assert!(labels(&outside).contains(&"outer"));
+    assert!(!labels(&outside).contains(&"inner"));
+    assert!(!labels(&outside).contains(&"param"));
+
+    client.shutdown();
+}
+
+#[test]
+fn it_completes_struct_fields() {
+    let mut client = Client::start();
+
+    client.open(
+        r#"struct Person {
+    name: String,
+    address: {
+        city: String,
+    },
+}
+
+func greet(person: Person): Void {
+    print(person.);
+    print(person.address.c);
+}
+"#,
+    );
+
+    client.diagnostics();
+
+    let fields = client.at("textDocument/completion", 8, 17);
+    let mut fields = labels(&fields);
+    fields.sort();
+    assert_eq!(fields, vec!["address", "name"]);
+
+    let nested = client.at("textDocument/completion", 9, 26);
+    assert_eq!(labels(&nested), vec!["city"]);
+
+    client.shutdown();
+}
@@ -1,8 +1,312 @@
-use std::env;
+mod repl;
 
-use nala_interpreter;
+use std::{
+    fs,
+    io::{self, IsTerminal, Read},
+    process, thread,
+};
 
-fn main() -> () {
-    let args: Vec<String> = env::args().collect();
-    nala_interpreter::main(&args[1]);
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use nala_interpreter::{
+    capabilities::{Capabilities, Capability},
+    limits::Limits,
+    linter::{LintConfig, Severity, RULES},
+    Engine,
+};
+
+// Exit code for failed scripts, tests and checks. Usage errors exit with clap's code of 2.
+const EXIT_FAILURE: i32 = 1;
+
+// Runaway recursion raises an error at this depth, rather than overflowing the native stack.
+const MAX_CALL_DEPTH: usize = 10_000;
+
+// Enough stack for `MAX_CALL_DEPTH` calls on the tree-walking interpreter, each of which nests
+// several Rust frames. It's only reserved, so unused stack costs nothing.
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+#[derive(Parser)]
+#[command(
+    name = "nala",
+    version,
+    about = "Run Nala scripts, tests and the REPL.",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    // Running a script is the default, so `nala script.nl` behaves like `nala run script.nl`.
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a script. This is the default when no subcommand is given.
+    Run(RunArgs),
+    /// Check that Nala files parse and resolve without running them.
+    Check {
+        /// Files or directories to check.
+        #[arg(default_value = ".")]
+        paths: Vec<String>,
+    },
+    /// Format Nala files.
+    Fmt {
+        /// Report unformatted files instead of rewriting them, exiting with a failure code if any.
+        #[arg(long)]
+        check: bool,
+        /// Files or directories to format.
+        #[arg(default_value = ".")]
+        paths: Vec<String>,
+    },
+    /// Report likely mistakes in Nala files, exiting with a failure code if any has error severity.
+    Lint {
+        /// Print findings as a JSON array instead of one per line.
+        #[arg(long)]
+        json: bool,
+        /// Don't report a rule.
+        #[arg(long, value_name = "RULE")]
+        allow: Vec<String>,
+        /// Report a rule as a warning.
+        #[arg(long, value_name = "RULE")]
+        warn: Vec<String>,
+        /// Report a rule as an error.
+        #[arg(long, value_name = "RULE")]
+        deny: Vec<String>,
+        /// List the available rules and exit.
+        #[arg(long)]
+        list_rules: bool,
+        /// Files or directories to lint.
+        #[arg(default_value = ".")]
+        paths: Vec<String>,
+    },
+    /// Run the `test` blocks of Nala files.
+    Test {
+        #[command(flatten)]
+        allow: AllowArgs,
+        /// Files or directories to search for tests.
+        #[arg(default_value = ".")]
+        paths: Vec<String>,
+    },
+    /// Start an interactive session.
+    Repl {
+        #[command(flatten)]
+        allow: AllowArgs,
+    },
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Evaluate the given code instead of a script.
+    #[arg(short = 'e', long = "eval", value_name = "CODE")]
+    eval: Option<String>,
+
+    /// Don't print "Execution completed." after a successful run.
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// The engine to run the script on.
+    #[arg(long, value_enum, default_value_t = EngineArg::Tree)]
+    engine: EngineArg,
+
+    #[command(flatten)]
+    allow: AllowArgs,
+
+    /// The script to run, or `-` to read it from stdin, followed by arguments passed to the script.
+    /// Scripts can read these arguments with the `args` builtin.
+    #[arg(
+        trailing_var_arg = true,
+        allow_hyphen_values = true,
+        value_name = "SCRIPT"
+    )]
+    inputs: Vec<String>,
+}
+
+// Scripts, tests and REPL sessions run without any capabilities unless they're allowed here.
+#[derive(Args)]
+struct AllowArgs {
+    /// Allow network access, as used by `http`.
+    #[arg(long)]
+    allow_net: bool,
+    /// Allow reading files.
+    #[arg(long)]
+    allow_read: bool,
+    /// Allow writing files.
+    #[arg(long)]
+    allow_write: bool,
+    /// Allow reading from stdin, as used by `read` and `readnum`.
+    #[arg(long)]
+    allow_stdin: bool,
+    /// Allow reading environment variables, as used by `env`.
+    #[arg(long)]
+    allow_env: bool,
+    /// Allow reading the time, as used by `now`.
+    #[arg(long)]
+    allow_clock: bool,
+    /// Allow generating random numbers, as used by `random`.
+    #[arg(long)]
+    allow_random: bool,
+    /// Allow every capability.
+    #[arg(short = 'A', long)]
+    allow_all: bool,
+}
+
+impl AllowArgs {
+    fn limits(&self) -> Limits {
+        Limits {
+            max_call_depth: Some(MAX_CALL_DEPTH),
+            capabilities: self.capabilities(),
+            ..Default::default()
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        if self.allow_all {
+            return Capabilities::all();
+        }
+
+        [
+            (self.allow_net, Capability::Net),
+            (self.allow_read, Capability::Read),
+            (self.allow_write, Capability::Write),
+            (self.allow_stdin, Capability::Stdin),
+            (self.allow_env, Capability::Env),
+            (self.allow_clock, Capability::Clock),
+            (self.allow_random, Capability::Random),
+        ]
+        .iter()
+        .filter(|(allowed, _)| *allowed)
+        .fold(Capabilities::none(), |capabilities, &(_, capability)| {
+            capabilities.with(capability)
+        })
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum EngineArg {
+    /// The tree-walking interpreter.
+    Tree,
+    /// The bytecode compiler and VM, which is faster for long-running scripts.
+    Vm,
+}
+
+impl From<EngineArg> for Engine {
+    fn from(engine: EngineArg) -> Self {
+        match engine {
+            EngineArg::Tree => Engine::Tree,
+            EngineArg::Vm => Engine::Vm,
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    // A thread that panics has already reported why, so it's only counted as a failure here.
+    let success = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || run_command(cli))
+        .unwrap()
+        .join()
+        .unwrap_or(false);
+
+    if !success {
+        process::exit(EXIT_FAILURE);
+    }
+}
+
+// Returns whether the command succeeded.
+fn run_command(cli: Cli) -> bool {
+    match cli.command {
+        Some(Command::Run(args)) => run(args),
+        Some(Command::Check { paths }) => nala_interpreter::check(&paths),
+        Some(Command::Fmt { check, paths }) => nala_interpreter::fmt(&paths, check),
+        Some(Command::Lint {
+            json,
+            allow,
+            warn,
+            deny,
+            list_rules,
+            paths,
+        }) => {
+            if list_rules {
+                for rule in RULES.iter() {
+                    println!("{:<20}{}", rule.name, rule.description);
+                }
+
+                true
+            } else {
+                lint_config(&allow, &warn, &deny)
+                    .map(|config| nala_interpreter::lint(&paths, &config, json))
+                    .unwrap_or_else(|message| {
+                        eprintln!("{message}");
+                        false
+                    })
+            }
+        }
+        Some(Command::Test { allow, paths }) => nala_interpreter::test(&paths, allow.limits()),
+        Some(Command::Repl { allow }) => {
+            repl::run(allow.limits());
+            true
+        }
+        // With nothing to run, start the REPL unless a script is being piped in.
+        None if cli.run.eval.is_none()
+            && cli.run.inputs.is_empty()
+            && io::stdin().is_terminal() =>
+        {
+            repl::run(cli.run.allow.limits());
+            true
+        }
+        None => run(cli.run),
+    }
+}
+
+fn run(args: RunArgs) -> bool {
+    let mut inputs = args.inputs.into_iter();
+
+    let (code, source) = match args.eval {
+        Some(code) => (Ok(code), String::from("<eval>")),
+        None => match inputs.next() {
+            Some(path) if path != "-" => (fs::read_to_string(&path), path),
+            _ => (read_stdin(), String::from("<stdin>")),
+        },
+    };
+
+    match code {
+        Ok(code) => nala_interpreter::run(
+            code,
+            &source,
+            inputs.collect(),
+            args.quiet,
+            args.engine.into(),
+            args.allow.limits(),
+        ),
+        Err(err) => {
+            eprintln!("Error loading nala file: {}", err);
+            false
+        }
+    }
+}
+
+// Later flags take precedence, so `--deny` overrides `--warn`, which overrides `--allow`.
+fn lint_config(allow: &[String], warn: &[String], deny: &[String]) -> Result<LintConfig, String> {
+    let mut config = LintConfig::default();
+
+    for (rules, severity) in [
+        (allow, Severity::Allow),
+        (warn, Severity::Warning),
+        (deny, Severity::Error),
+    ] {
+        for rule in rules {
+            config.set(rule, severity)?;
+        }
+    }
+
+    Ok(config)
+}
+
+fn read_stdin() -> io::Result<String> {
+    let mut code = String::new();
+    io::stdin().read_to_string(&mut code)?;
+    Ok(code)
 }
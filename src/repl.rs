@@ -0,0 +1,80 @@
+use std::{env, path::PathBuf};
+
+use nala_interpreter::{
+    io_context::ConsoleContext,
+    limits::Limits,
+    repl::{Repl, ReplResponse},
+};
+use rustyline::{error::ReadlineError, DefaultEditor};
+
+const PROMPT: &str = ">> ";
+const CONTINUATION_PROMPT: &str = ".. ";
+
+pub fn run(limits: Limits) {
+    let mut repl = match Repl::new(ConsoleContext::new()) {
+        Ok(repl) => repl,
+        Err(e) => {
+            eprintln!("Nala Runtime Error: {}", e.message);
+            return;
+        }
+    };
+
+    repl.set_limits(limits);
+
+    let mut editor = match DefaultEditor::new() {
+        Ok(editor) => editor,
+        Err(err) => {
+            eprintln!("Could not start REPL: {err}");
+            return;
+        }
+    };
+
+    let history = history_path();
+
+    if let Some(history) = &history {
+        // A missing history file just means this is the first session.
+        let _ = editor.load_history(history);
+    }
+
+    println!("Nala REPL. Enter `:help` for help, or `:quit` to exit.");
+
+    loop {
+        let prompt = if repl.is_incomplete() {
+            CONTINUATION_PROMPT
+        } else {
+            PROMPT
+        };
+
+        let line = match editor.readline(prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => {
+                repl.reset();
+                continue;
+            }
+            Err(ReadlineError::Eof) => break,
+            Err(err) => {
+                eprintln!("Could not read input: {err}");
+                break;
+            }
+        };
+
+        if !line.trim().is_empty() {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+
+        match repl.eval_line(&line) {
+            ReplResponse::Empty | ReplResponse::Incomplete => (),
+            ReplResponse::Output(output) => println!("{output}"),
+            ReplResponse::Error(message) => eprintln!("{message}"),
+            ReplResponse::Exit => break,
+        }
+    }
+
+    if let Some(history) = &history {
+        let _ = editor.save_history(history);
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".nala_history"))
+}
@@ -2,11 +2,24 @@ extern crate nala_interpreter;
 extern crate regex;
 
 use nala_interpreter::{
-    errors::RuntimeError, interpreter::eval_program, io_context::TestContext, parser,
+    errors::RuntimeError,
+    interpreter::eval_program_with_limits,
+    io_context::TestContext,
+    limits::{CancelHandle, Limits},
+    parser,
     resolved::value::Value,
+    vm::run_program_with_limits,
 };
 
 pub fn parse_and_run(nala: &str, test_context: &mut TestContext) -> Result<Value, RuntimeError> {
+    parse_and_run_with_limits(nala, test_context, Limits::default())
+}
+
+pub fn parse_and_run_with_limits(
+    nala: &str,
+    test_context: &mut TestContext,
+    limits: Limits,
+) -> Result<Value, RuntimeError> {
     let lines = nala.trim_start().trim_end().split("\n");
     let output: String = lines
         .enumerate()
@@ -18,9 +31,95 @@ pub fn parse_and_run(nala: &str, test_context: &mut TestContext) -> Result<Value
 
     let result = parser::parse_code(nala.to_owned());
 
-    match result {
-        Ok(parsed) => eval_program(parsed, test_context),
+    let parsed = match result {
+        Ok(parsed) => parsed,
         Err(_) => panic!("Could not parse nala!"),
+    };
+
+    // Every script runs on both engines, which must agree on its result and output.
+    let mut vm_context = test_context.clone();
+    let vm_result = run_program_with_limits(
+        parsed.clone(),
+        &mut vm_context,
+        limits.clone(),
+        CancelHandle::new(),
+    );
+    let result = eval_program_with_limits(parsed, test_context, limits, CancelHandle::new());
+
+    match (&result, &vm_result) {
+        (Ok(expected), Ok(actual)) => assert!(
+            same_value(expected, actual),
+            "The VM returned `{}` where the interpreter returned `{}`.",
+            actual,
+            expected
+        ),
+        (Err(expected), Err(actual)) => {
+            assert_eq!(
+                expected.message, actual.message,
+                "The VM raised a different error."
+            );
+            assert_eq!(
+                expected.kind, actual.kind,
+                "The VM raised a different kind of error."
+            );
+        }
+        _ => panic!(
+            "The VM returned {:?} where the interpreter returned {:?}.",
+            vm_result, result
+        ),
+    }
+
+    assert_eq!(
+        test_context.get_output(),
+        vm_context.get_output(),
+        "The VM printed different output."
+    );
+
+    result
+}
+
+// Compares values structurally. Functions have no notion of equality, so they're compared by type.
+fn same_value(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Func(_), Value::Func(_)) => left.to_string() == right.to_string(),
+        (Value::Array(left), Value::Array(right)) => {
+            let left = left.borrow();
+            let right = right.borrow();
+
+            left.len() == right.len()
+                && left.iter().zip(right.iter()).all(|(l, r)| same_value(l, r))
+        }
+        (Value::Object(left), Value::Object(right)) => {
+            let left = left.borrow();
+            let right = right.borrow();
+
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .all(|(key, l)| right.get(key).map_or(false, |r| same_value(l, r)))
+        }
+        (Value::Map(left), Value::Map(right)) => {
+            let left = left.borrow();
+            let right = right.borrow();
+
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right.iter())
+                    .all(|((lk, l), (rk, r))| lk == rk && same_value(l, r))
+        }
+        (Value::Set(left), Value::Set(right)) => *left.borrow() == *right.borrow(),
+        (Value::Variant(left), Value::Variant(right)) => {
+            left.enum_ident == right.enum_ident
+                && left.variant_ident == right.variant_ident
+                && match (&left.data, &right.data) {
+                    (Some(l), Some(r)) => same_value(l, r),
+                    (None, None) => true,
+                    _ => false,
+                }
+        }
+        (Value::Break(left), Value::Break(right)) => same_value(left, right),
+        _ => left == right,
     }
 }
 
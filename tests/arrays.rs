@@ -107,7 +107,7 @@ fn it_errors_when_indexing_array_with_string() {
 
 #[test]
 fn it_errors_when_indexing_non_array() {
-    let expected_message = rgx!("Cannot index into a value which is not an array.");
+    let expected_message = rgx!("Cannot index into a value which is not an array or map.");
 
     let nala = r#"
         const num = 7;
@@ -123,7 +123,7 @@ fn it_errors_when_indexing_non_array() {
 #[test]
 fn it_errors_when_passing_number_arg_to_len() {
     let expected_message =
-        rgx!("Passed value `7` of type `Number` to function where `Array<T>` was expected.");
+        rgx!("Cannot get the length of `7`, which is not an Array, Map, Set or String.");
 
     let nala = r#"
         const num = 7;
@@ -148,6 +148,249 @@ fn it_errors_when_declaring_array_multiple_types() {
     assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
 }
 
+#[test]
+fn it_errors_when_slicing_out_of_bounds() {
+    let expected_message = rgx!("Slice range 1..4 is out of bounds for an array of length 2.");
+
+    let nala = "slice([1, 2], 1, 4);";
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_changes_arrays_in_place() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const nums = [1, 2];
+        const same = nums;
+
+        push(nums, 3);
+        insert(nums, 0, 0);
+        print(pop(nums));
+        print(remove(nums, 1));
+        print(len(same));
+        print(same[1]);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["Some(3)", "1", "2", "2"]);
+}
+
+#[test]
+fn it_pushes_onto_empty_arrays() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const names: Array<String> = [];
+        print(pop(names));
+        push(names, 'Walter');
+        print(names[0]);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["None", "Walter"]);
+}
+
+#[test]
+fn it_errors_when_pushing_value_of_other_type() {
+    let expected_message = rgx!("Arrays can contain elements of only a single type. Found elements of types `Number` and `String`.");
+
+    let nala = "push([1, 2], 'three');";
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_errors_when_removing_out_of_bounds() {
+    let expected_message = rgx!("Index 2 is out of bounds for an array of length 2.");
+
+    let nala = "remove([1, 2], 2);";
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_runs_higher_order_array_builtins() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func double(n: Number): Number { n * 2; }
+        func isEven(n: Number): Bool { n / 2 == floor(n / 2); }
+        func add(total: Number, n: Number): Number { total + n; }
+        func isBig(n: Number): Bool { n > 10; }
+
+        const nums = [1, 2, 3, 4];
+        const doubled = map(nums, double);
+        const evens = filter(nums, isEven);
+
+        print(doubled[3]);
+        print(len(evens));
+        print(reduce(nums, 0, add));
+        print(find(nums, isEven));
+        print(find(nums, isBig));
+        print(any(nums, isBig));
+        print(all(doubled, isEven));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["8", "2", "10", "Some(2)", "None", "false", "true"]
+    );
+}
+
+#[test]
+fn it_calls_closures_from_builtins() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func adder(amount: Number): Func<Number, Number> {
+            func add(n: Number): Number { n + amount; }
+            add;
+        }
+
+        const added = map([1, 2], adder(10));
+        print(added[1]);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["12"]);
+}
+
+#[test]
+fn it_catches_errors_raised_by_callbacks() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func check(n: Number): Bool {
+            if (n > 1) {
+                panic('too big');
+            }
+
+            true;
+        }
+
+        func safeCheck(n: Number): Bool {
+            try { check(n); } catch (e) { false; }
+        }
+
+        try {
+            all([1, 2], check);
+        } catch (e) {
+            print(e.message);
+        }
+
+        print(all([1, 2], safeCheck));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["too big", "false"]);
+}
+
+#[test]
+fn it_errors_when_predicate_does_not_return_bool() {
+    let expected_message = rgx!("where `Func<Bool>` was expected");
+
+    let nala = r#"
+        func double(n: Number): Number { n * 2; }
+        filter([1, 2], double);
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_sorts_by_key() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        struct Person {
+            name: String,
+            age: Number
+        }
+
+        func byName(person: Person): String { person.name; }
+        func byAge(person: Person): Number { person.age; }
+
+        const people = [
+            { name: 'Walter', age: 50 },
+            { name: 'Jesse', age: 25 },
+            { name: 'Skyler', age: 40 }
+        ];
+
+        const byNames = sortBy(people, byName);
+        const byAges = sortBy(people, byAge);
+
+        print(byNames[0].name);
+        print(byAges[2].name);
+        print(people[0].name);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["Jesse", "Walter", "Walter"]);
+}
+
+#[test]
+fn it_combines_arrays() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const both = concat([1, 2], [3]);
+        const reversed = reverse(both);
+        const flat = flatten([[1], [2, 3]]);
+        const pairs = zip(['a', 'b', 'c'], [1, 2]);
+        const indexed = enumerate(['x', 'y']);
+        const nums = range(2, 5);
+
+        print(len(both));
+        print(reversed[0]);
+        print(len(flat));
+        print(len(pairs));
+        print(pairs[1].first);
+        print(pairs[1].second);
+        print(indexed[1].index);
+        print(indexed[1].value);
+        print(nums[0]);
+        print(len(nums));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["3", "3", "3", "2", "b", "2", "1", "y", "2", "3"]
+    );
+}
+
+#[test]
+fn it_searches_arrays_and_strings() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        print(contains([1, 2, 3], 2));
+        print(contains(['a'], 'b'));
+        print(indexOf(['a', 'b'], 'b'));
+        print(indexOf([1], 2));
+        print(contains('hello', 'ell'));
+        print(indexOf('hello', 'l'));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["true", "false", "Some(1)", "None", "true", "Some(2)"]
+    );
+}
+
 // TODO: This fails with a parse error. Need to fix.
 // #[test]
 // fn it_allows_member_access_after_array_index_in_assign() {
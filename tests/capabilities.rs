@@ -0,0 +1,122 @@
+use nala_interpreter::{
+    capabilities::{Capabilities, Capability},
+    errors::ErrorKind,
+    interpreter::Interpreter,
+    io_context::TestContext,
+    limits::Limits,
+};
+use test_util::parse_and_run_with_limits;
+
+fn allowing(capabilities: Capabilities) -> Limits {
+    Limits {
+        capabilities,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn it_allows_every_capability_by_default() {
+    let mut ctx = TestContext::new();
+    ctx.mock_inputs(vec!["hello"]);
+
+    let result = parse_and_run_with_limits("print(read());", &mut ctx, Limits::default());
+
+    assert!(result.is_ok());
+    assert_eq!(ctx.get_output(), vec!["hello"]);
+}
+
+#[test]
+fn it_raises_permission_errors_for_denied_builtins() {
+    let mut ctx = TestContext::new();
+    ctx.mock_inputs(vec!["hello"]);
+
+    let limits = allowing(Capabilities::all().without(Capability::Stdin));
+    let error = parse_and_run_with_limits("read();", &mut ctx, limits).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Permission);
+    assert_eq!(
+        error.message,
+        "Calling `read` requires the `stdin` capability."
+    );
+}
+
+#[test]
+fn it_gates_the_environment_clock_and_random_numbers() {
+    let cases = [
+        ("env('HOME');", Capability::Env, "env", "env"),
+        ("now();", Capability::Clock, "now", "clock"),
+        ("random();", Capability::Random, "random", "random"),
+    ];
+
+    for (code, capability, builtin, name) in cases {
+        let limits = allowing(Capabilities::all().without(capability));
+        let error = parse_and_run_with_limits(code, &mut TestContext::new(), limits).unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::Permission);
+        assert_eq!(
+            error.message,
+            format!("Calling `{builtin}` requires the `{name}` capability.")
+        );
+
+        let limits = allowing(Capabilities::none().with(capability));
+        assert!(parse_and_run_with_limits(code, &mut TestContext::new(), limits).is_ok());
+    }
+}
+
+#[test]
+fn it_allows_builtins_with_granted_capabilities() {
+    let mut ctx = TestContext::new();
+    ctx.mock_inputs(vec!["7"]);
+
+    let limits = allowing(Capabilities::none().with(Capability::Stdin));
+    let result = parse_and_run_with_limits("print(readnum() + 1);", &mut ctx, limits);
+
+    assert!(result.is_ok());
+    assert_eq!(ctx.get_output(), vec!["8"]);
+}
+
+#[test]
+fn it_leaves_builtins_without_capabilities_available() {
+    let mut ctx = TestContext::new();
+
+    let limits = allowing(Capabilities::none());
+    let result = parse_and_run_with_limits("print(floor(2.5));", &mut ctx, limits);
+
+    assert!(result.is_ok());
+    assert_eq!(ctx.get_output(), vec!["2"]);
+}
+
+#[test]
+fn it_catches_permission_errors() {
+    let nala = r#"
+        try {
+            http({ method: 'GET', url: 'https://example.com', body: '' });
+        } catch (e) {
+            print(e.kind);
+        }
+    "#;
+
+    let mut ctx = TestContext::new();
+
+    let result = parse_and_run_with_limits(nala, &mut ctx, allowing(Capabilities::none()));
+
+    assert!(result.is_ok());
+    assert_eq!(ctx.get_output(), vec!["PermissionError"]);
+}
+
+#[test]
+fn it_changes_interpreter_capabilities() {
+    let mut ctx = TestContext::new();
+    ctx.mock_inputs(vec!["first", "second"]);
+
+    let mut interpreter = Interpreter::new(ctx).unwrap();
+    interpreter.set_limits(allowing(Capabilities::none()));
+
+    let error = interpreter.eval_str("read();").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Permission);
+
+    interpreter.set_limits(allowing(Capabilities::all()));
+    interpreter.eval_str("print(read());").unwrap();
+
+    assert_eq!(interpreter.ctx_mut().get_output(), vec!["first"]);
+}
@@ -0,0 +1,348 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+fn write_script(name: &str, code: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("nala_cli_tests_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join(name);
+    fs::write(&path, code).unwrap();
+    path
+}
+
+fn nala() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_nala"))
+}
+
+#[test]
+fn it_runs_passing_tests_with_success_exit_code() {
+    let path = write_script(
+        "passing.nl",
+        r#"
+            test 'adds' {
+                assertEq(3, 1 + 2);
+            }
+        "#,
+    );
+
+    let output = nala().arg("test").arg(&path).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains("test adds ... ok"));
+    assert!(stdout.contains("test result: ok. 1 passed; 0 failed"));
+}
+
+#[test]
+fn it_runs_failing_tests_with_failure_exit_code() {
+    let path = write_script(
+        "failing.nl",
+        r#"
+            test 'adds' {
+                assertEq(4, 1 + 2);
+            }
+        "#,
+    );
+
+    let output = nala().arg("test").arg(&path).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("test adds ... FAILED"));
+    assert!(stdout.contains("AssertionError: Assertion failed: values are not equal."));
+    assert!(stdout.contains("test result: FAILED. 0 passed; 1 failed"));
+}
+
+#[test]
+fn it_runs_scripts_with_args() {
+    let path = write_script(
+        "args.nl",
+        r#"
+            for arg in args() {
+                print(arg);
+            }
+        "#,
+    );
+
+    let output = nala()
+        .args(["run", "--quiet"])
+        .arg(&path)
+        .args(["one", "--two"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "one\n--two\n");
+}
+
+#[test]
+fn it_runs_scripts_without_subcommand() {
+    let path = write_script("hello.nl", "print('hello');");
+
+    let output = nala().arg(&path).output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "hello\nExecution completed.\n"
+    );
+}
+
+#[test]
+fn it_evaluates_code() {
+    let output = nala().args(["-q", "-e", "print(2 + 3);"]).output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "5\n");
+}
+
+#[test]
+fn it_evaluates_code_on_the_vm() {
+    let output = nala()
+        .args(["-q", "--engine", "vm", "-e", "print(2 + 3);"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "5\n");
+}
+
+#[test]
+fn it_reads_scripts_from_stdin() {
+    let mut child = nala()
+        .args(["run", "-q", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"print('piped');")
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "piped\n");
+}
+
+#[test]
+fn it_denies_capabilities_by_default() {
+    let output = nala().args(["-e", "read();"]).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Calling `read` requires the `stdin` capability."));
+}
+
+#[test]
+fn it_allows_capabilities_with_flags() {
+    for flag in ["--allow-stdin", "--allow-all"] {
+        let mut child = nala()
+            .args(["run", "-q", flag, "-e", "print(read());"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        child.stdin.take().unwrap().write_all(b"typed\n").unwrap();
+
+        let output = child.wait_with_output().unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "typed\n");
+    }
+}
+
+#[test]
+fn it_applies_capability_flags_to_tests() {
+    let path = write_script(
+        "capabilities.nl",
+        r#"
+            test 'reads' {
+                assertEq(false, exists('missing.txt'));
+            }
+        "#,
+    );
+
+    let output = nala().arg("test").arg(&path).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("Calling `exists` requires the `read` capability."));
+
+    let output = nala()
+        .args(["test", "--allow-read"])
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+}
+
+#[test]
+fn it_reports_runtime_errors_with_failure_exit_code() {
+    let output = nala().args(["-e", "missing;"]).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Nala Runtime Error: Identifier 'missing' was not found in this scope.\n"
+    );
+}
+
+#[test]
+fn it_reports_runaway_recursion_instead_of_overflowing_the_stack() {
+    for engine in ["tree", "vm"] {
+        let output = nala()
+            .args([
+                "--engine",
+                engine,
+                "-e",
+                "func f(n: Number): Void { f(n + 1); } f(0);",
+            ])
+            .output()
+            .unwrap();
+
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "Nala Runtime Error: Execution exceeded the maximum call depth of 10000.\n"
+        );
+    }
+}
+
+#[test]
+fn it_reports_resolution_errors_apart_from_runtime_errors() {
+    let output = nala()
+        .args(["-e", "print('before'); print(x); const x = 5;"])
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(stderr.starts_with("Nala Resolution Error:"));
+    assert!(stderr.contains("Identifier 'x' was used before its declaration."));
+}
+
+#[test]
+fn it_reports_parse_errors_with_failure_exit_code() {
+    let output = nala().args(["-e", "const = 5;"]).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.starts_with("Nala Parse Error:"));
+}
+
+#[test]
+fn it_reports_missing_scripts_with_failure_exit_code() {
+    let output = nala().arg("does_not_exist.nl").output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.starts_with("Error loading nala file:"));
+}
+
+#[test]
+fn it_checks_files() {
+    let valid = write_script("valid.nl", "const x = 5;");
+    let invalid = write_script("invalid.nl", "const = 5;");
+
+    let output = nala().arg("check").arg(&valid).output().unwrap();
+    assert!(output.status.success());
+
+    let output = nala().arg("check").arg(&invalid).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("invalid.nl"));
+}
+
+#[test]
+fn it_checks_files_for_resolution_errors() {
+    let path = write_script("unresolved.nl", "print(x);\nconst x = 5;");
+
+    let output = nala().arg("check").arg(&path).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("Identifier 'x' was used before its declaration."));
+}
+
+#[test]
+fn it_formats_files() {
+    let path = write_script("unformatted.nl", "const x=5;");
+
+    let output = nala().args(["fmt", "--check"]).arg(&path).output().unwrap();
+    assert!(!output.status.success());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "const x=5;");
+
+    let output = nala().arg("fmt").arg(&path).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "const x = 5;\n");
+
+    let output = nala().args(["fmt", "--check"]).arg(&path).output().unwrap();
+    assert!(output.status.success());
+}
+
+#[test]
+fn it_lints_files() {
+    let path = write_script("lint.nl", "const x = 5;\nmut y = 6;\nprint(y);\n");
+
+    let output = nala().arg("lint").arg(&path).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains(":1: warning[unused-const]: Constant `x` is declared but never used."));
+    assert!(stdout.contains(":2: warning[unnecessary-mut]:"));
+
+    let output = nala()
+        .args([
+            "lint",
+            "--json",
+            "--deny",
+            "unused-const",
+            "--allow",
+            "unnecessary-mut",
+        ])
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let findings: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(
+        findings,
+        serde_json::json!([{
+            "file": path.to_str().unwrap(),
+            "rule": "unused-const",
+            "severity": "error",
+            "line": 1,
+            "message": "Constant `x` is declared but never used."
+        }])
+    );
+
+    let output = nala()
+        .args(["lint", "--deny", "no-such-rule"])
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Unknown lint rule `no-such-rule`."));
+}
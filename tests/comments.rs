@@ -0,0 +1,112 @@
+use nala_interpreter::io_context::TestContext;
+use test_util::parse_and_run;
+
+#[test]
+fn it_allows_comments_between_array_elements() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const xs = [
+            1, // one
+            2, /* two */
+        ];
+
+        print(len(xs));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["2"]);
+}
+
+#[test]
+fn it_allows_comments_before_else() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        if (false) {
+            print('if');
+        } // note
+        elif (false) {
+            print('elif');
+        } /* note */ else {
+            print('else');
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["else"]);
+}
+
+#[test]
+fn it_allows_comments_in_declarations() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        struct Point {
+            // The horizontal axis.
+            x: Number,
+            y: Number, // The vertical axis.
+        }
+
+        enum Shape {
+            Dot, // No area.
+            // A radius.
+            Circle(Number),
+        }
+
+        const p: Point = { x: 1, /* inline */ y: 2 };
+        const s = Shape::Circle(p.y);
+
+        print(p.x + p.y);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["3"]);
+}
+
+#[test]
+fn it_allows_comments_in_call_arguments() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func add(a: Number, b: Number): Number {
+            a + b;
+        }
+
+        print(add(
+            1, // first
+            // second
+            2
+        ));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["3"]);
+}
+
+#[test]
+fn it_allows_blocks_holding_only_comments() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func todo(): Void {
+            // Nothing yet.
+        }
+
+        todo();
+        print('done');
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["done"]);
+}
+
+#[test]
+fn it_does_not_treat_comment_markers_in_strings_as_comments() {
+    let mut ctx = TestContext::new();
+
+    let nala = "print('// not a comment'); // a comment";
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["// not a comment"]);
+}
@@ -0,0 +1,149 @@
+use nala_interpreter::{
+    errors::ErrorKind, interpreter::Interpreter, io_context::TestContext, resolved::value::Value,
+};
+
+#[test]
+fn it_keeps_bindings_between_evaluations() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter.eval_str("const greeting = 'hello';").unwrap();
+    interpreter.eval_str("print(greeting);").unwrap();
+
+    assert_eq!(interpreter.ctx_mut().get_output(), vec!["hello"]);
+}
+
+#[test]
+fn it_returns_result_of_evaluation() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let result = interpreter.eval_str("2 + 3;").unwrap();
+
+    assert_eq!(result, Value::Num(5.0));
+}
+
+#[test]
+fn it_gets_globals() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter.eval_str("mut count = 7;").unwrap();
+
+    assert_eq!(interpreter.get_global("count").unwrap(), Value::Num(7.0));
+    assert!(interpreter.get_global("missing").is_err());
+}
+
+#[test]
+fn it_sets_globals() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter
+        .set_global("name", Value::String("Walter".into()))
+        .unwrap();
+
+    interpreter.eval_str("print('Hello, ' + name);").unwrap();
+
+    interpreter
+        .set_global("name", Value::String("Jesse".into()))
+        .unwrap();
+
+    interpreter.eval_str("print('Hello, ' + name);").unwrap();
+
+    assert_eq!(
+        interpreter.ctx_mut().get_output(),
+        vec!["Hello, Walter", "Hello, Jesse"]
+    );
+}
+
+#[test]
+fn it_sets_globals_named_after_builtins() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter.set_global("range", Value::Num(3.0)).unwrap();
+    interpreter.eval_str("print(range + 1);").unwrap();
+
+    assert_eq!(interpreter.ctx_mut().get_output(), vec!["4"]);
+}
+
+#[test]
+fn it_errors_when_setting_immutable_global() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter.eval_str("const name = 'Walter';").unwrap();
+
+    let result = interpreter.set_global("name", Value::String("Jesse".into()));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn it_calls_functions_repeatedly() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter
+        .eval_str(
+            r#"
+            func add(a: Number, b: Number): Number {
+                a + b;
+            }
+        "#,
+        )
+        .unwrap();
+
+    for i in 0..3 {
+        let result = interpreter
+            .call_function("add", vec![Value::Num(i as f32), Value::Num(10.0)])
+            .unwrap();
+
+        assert_eq!(result, Value::Num(i as f32 + 10.0));
+    }
+}
+
+#[test]
+fn it_type_checks_function_call_args() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter
+        .eval_str(
+            r#"
+            func double(n: Number): Number {
+                n * 2;
+            }
+        "#,
+        )
+        .unwrap();
+
+    let wrong_type = interpreter.call_function("double", vec![Value::String("x".into())]);
+    assert!(wrong_type.is_err());
+
+    let wrong_count = interpreter.call_function("double", vec![]);
+    assert!(wrong_count.is_err());
+}
+
+#[test]
+fn it_calls_builtin_functions() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let result = interpreter
+        .call_function("floor", vec![Value::Num(2.7)])
+        .unwrap();
+
+    assert_eq!(result, Value::Num(2.0));
+}
+
+#[test]
+fn it_errors_when_calling_non_function() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter.eval_str("const foo = 7;").unwrap();
+
+    assert!(interpreter.call_function("foo", vec![]).is_err());
+}
+
+#[test]
+fn it_reports_parse_errors() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let result = interpreter.eval_str("const = ;");
+
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind, ErrorKind::Parse);
+}
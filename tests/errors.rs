@@ -0,0 +1,138 @@
+use nala_interpreter::{errors::ErrorKind, io_context::TestContext};
+use test_util::parse_and_run;
+
+#[test]
+fn it_catches_runtime_errors() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        try {
+            const result = 5 / 0;
+            print('unreachable');
+        } catch (e) {
+            print(e.message);
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["Cannot divide by zero."]);
+}
+
+#[test]
+fn it_exposes_error_kind_to_catch_block() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        try {
+            print(undeclared);
+        } catch (e) {
+            print(e.kind);
+        }
+
+        try {
+            panic('bad record');
+        } catch (e) {
+            print(e.kind);
+            print(e.message);
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["RuntimeError", "Panic", "bad record"]
+    );
+}
+
+#[test]
+fn it_skips_catch_block_when_no_error() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        try {
+            print('foo');
+        } catch (e) {
+            print('bar');
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["foo"]);
+}
+
+#[test]
+fn it_continues_loop_after_caught_error() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func process(record: Number): Number {
+            if (record == 2) {
+                panic('bad record');
+            }
+
+            record * 10;
+        }
+
+        for record in [1, 2, 3] {
+            try {
+                print(process(record));
+            } catch (e) {
+                print('skipped: ' + e.message);
+            }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["10", "skipped: bad record", "30"]);
+}
+
+#[test]
+fn it_passes_caught_error_as_error_struct() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func describe(error: Error): String {
+            error.kind + ': ' + error.message;
+        }
+
+        try {
+            panic('oops');
+        } catch (e) {
+            print(describe(e));
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["Panic: oops"]);
+}
+
+#[test]
+fn it_errors_on_uncaught_panic() {
+    let nala = r#"
+        panic('something went wrong');
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+
+    let error = result.unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Panic);
+    assert_eq!(error.message, "something went wrong");
+}
+
+#[test]
+fn it_propagates_errors_raised_in_catch_block() {
+    let nala = r#"
+        try {
+            panic('first');
+        } catch (e) {
+            panic('second');
+        }
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().message, "second");
+}
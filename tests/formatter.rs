@@ -0,0 +1,220 @@
+use nala_interpreter::formatter::format_code;
+
+fn assert_formats(code: &str, expected: &str) {
+    let formatted = format_code(code).unwrap();
+    assert_eq!(formatted, expected);
+
+    // Formatting must be idempotent.
+    assert_eq!(format_code(&formatted).unwrap(), expected);
+}
+
+#[test]
+fn it_formats_declarations() {
+    assert_formats(
+        "const   x=5;mut y : Array<Number> = [1,2,3];\ny = [];",
+        "const x = 5;\nmut y: Array<Number> = [1, 2, 3];\ny = [];\n",
+    );
+}
+
+#[test]
+fn it_formats_expressions() {
+    assert_formats(
+        "const z = a+b*2-c/d == 'foo';\nprint(items[0].name);\nfoo::<Number>(1, -2.5);",
+        "const z = a + b * 2 - c / d == 'foo';\nprint(items[0].name);\nfoo::<Number>(1, -2.5);\n",
+    );
+}
+
+#[test]
+fn it_formats_blocks() {
+    let code = r#"
+func add<T>(a: Number,b: Number): Number { a + b; }
+if (x > 1) { print('big'); } elif (x < 1) {
+print('small'); }
+else { print('one'); }
+for item in items { wiles (true) { break(item); } }
+test 'adds' { assertEq(3, add(1, 2)); }
+try { panic('oh no'); } catch (e) { print(e.message); }
+"#;
+
+    let expected = r#"func add<T>(a: Number, b: Number): Number {
+    a + b;
+}
+if (x > 1) {
+    print('big');
+} elif (x < 1) {
+    print('small');
+} else {
+    print('one');
+}
+for item in items {
+    wiles (true) {
+        break(item);
+    }
+}
+test 'adds' {
+    assertEq(3, add(1, 2));
+}
+try {
+    panic('oh no');
+} catch (e) {
+    print(e.message);
+}
+"#;
+
+    assert_formats(code, expected);
+}
+
+#[test]
+fn it_formats_types() {
+    let code = r#"
+struct Person { name: String, address: { street: String, city: String }, tags: Array<String> }
+enum Result<T> { Ok(T), Err(Func<Number, Void>), Empty }
+"#;
+
+    let expected = r#"struct Person {
+    name: String,
+    address: {
+        street: String,
+        city: String,
+    },
+    tags: Array<String>,
+}
+enum Result<T> {
+    Ok(T),
+    Err(Func<Number, Void>),
+    Empty,
+}
+"#;
+
+    assert_formats(code, expected);
+}
+
+#[test]
+fn it_formats_patterns() {
+    let code = r#"
+match (result) { Option::Some(value) => { print(value); } Option::None => { print(Option::None); } }
+"#;
+
+    let expected = r#"match (result) {
+    Option::Some(value) => {
+        print(value);
+    }
+    Option::None => {
+        print(Option::None);
+    }
+}
+"#;
+
+    assert_formats(code, expected);
+}
+
+#[test]
+fn it_formats_short_objects_inline() {
+    assert_formats(
+        "const point = {\n  x: 1,\n  y: 2,\n};",
+        "const point = { x: 1, y: 2 };\n",
+    );
+}
+
+#[test]
+fn it_formats_maps() {
+    assert_formats(
+        "const ages = Map {'Walter':50,'Jesse':   25};\nconst empty: Map<String, Number> = Map { };",
+        "const ages = Map { 'Walter': 50, 'Jesse': 25 };\nconst empty: Map<String, Number> = Map {};\n",
+    );
+}
+
+#[test]
+fn it_formats_sets() {
+    assert_formats(
+        "const ids = Set {1,2,   3};\nconst empty: Set<Number> = Set { };",
+        "const ids = Set { 1, 2, 3 };\nconst empty: Set<Number> = Set {};\n",
+    );
+}
+
+#[test]
+fn it_splits_long_objects_and_arrays() {
+    let code = r#"
+const person = { name: 'Walter White', alias: 'Heisenberg', address: { street: '308 Negra Arroyo Lane', city: 'Albuquerque' } };
+const names = ['Walter White', 'Jesse Pinkman', 'Saul Goodman', 'Gus Fring'];
+"#;
+
+    let expected = r#"const person = {
+    name: 'Walter White',
+    alias: 'Heisenberg',
+    address: { street: '308 Negra Arroyo Lane', city: 'Albuquerque' },
+};
+const names = [
+    'Walter White',
+    'Jesse Pinkman',
+    'Saul Goodman',
+    'Gus Fring',
+];
+"#;
+
+    assert_formats(code, expected);
+}
+
+#[test]
+fn it_preserves_comments() {
+    let code = r#"
+/* A block comment. */
+const x = 5;   // A trailing comment.
+// An own-line comment.
+func foo(): Void { // Trailing the brace.
+        print(x);
+// Indented to match.
+}
+"#;
+
+    let expected = r#"/* A block comment. */
+const x = 5; // A trailing comment.
+// An own-line comment.
+func foo(): Void { // Trailing the brace.
+    print(x);
+    // Indented to match.
+}
+"#;
+
+    assert_formats(code, expected);
+}
+
+#[test]
+fn it_moves_comments_within_a_line_after_it() {
+    let code = r#"
+const xs = [
+    1, // one
+    2,
+];
+if (true) {
+    print('a');
+} // note
+else {
+    // Nothing yet.
+}
+"#;
+
+    let expected = r#"const xs = [1, 2]; // one
+if (true) {
+    print('a');
+} else {
+    // Nothing yet.
+} // note
+"#;
+
+    assert_formats(code, expected);
+}
+
+#[test]
+fn it_preserves_single_blank_lines() {
+    let code = "const x = 5;\n\n\n\nconst y = 6;\nfunc foo(): Void {\n\n    print(x);\n\n    print(y);\n}\n";
+    let expected =
+        "const x = 5;\n\nconst y = 6;\nfunc foo(): Void {\n    print(x);\n\n    print(y);\n}\n";
+
+    assert_formats(code, expected);
+}
+
+#[test]
+fn it_rejects_invalid_code() {
+    assert!(format_code("const = 5;").is_err());
+}
@@ -0,0 +1,204 @@
+use nala_interpreter::{
+    capabilities::{Capabilities, Capability},
+    errors::ErrorKind,
+    io_context::TestContext,
+    limits::Limits,
+};
+use test_util::{parse_and_run, parse_and_run_with_limits};
+
+#[test]
+fn it_reads_files() {
+    let mut ctx = TestContext::new();
+    ctx.mock_file("data/names.txt", "Ada\nGrace\n");
+
+    let nala = r#"
+        match (readFile('data/names.txt')) {
+            Result::Ok(contents) => { print(trim(contents)); }
+            Result::Err(message) => { print(message); }
+        }
+
+        match (readLines('data/names.txt')) {
+            Result::Ok(lines) => {
+                for line in lines {
+                    print(line);
+                }
+            }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["Ada\nGrace", "Ada", "Grace"]);
+}
+
+#[test]
+fn it_returns_errors_for_missing_files() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        match (readFile('missing.txt')) {
+            Result::Ok(contents) => { print(contents); }
+            Result::Err(message) => { print(message); }
+        }
+
+        match (removeFile('missing.txt')) {
+            Result::Ok(path) => { print(path); }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec![
+            "`missing.txt` does not exist.",
+            "`missing.txt` does not exist."
+        ]
+    );
+}
+
+#[test]
+fn it_writes_and_appends_to_files() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func report(result: Result<String>): Void {
+            match (result) {
+                Result::Ok(path) => { print(path); }
+                Result::Err(message) => { print(message); }
+            }
+        }
+
+        report(writeFile('log.txt', 'first,'));
+        report(appendFile('log.txt', 'second'));
+        report(writeFile('missing/log.txt', 'third'));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["log.txt", "log.txt", "`missing` does not exist."]
+    );
+    assert_eq!(ctx.get_file("log.txt"), Some("first,second"));
+}
+
+#[test]
+fn it_makes_and_lists_directories() {
+    let mut ctx = TestContext::new();
+    ctx.mock_file("notes/b.txt", "");
+
+    let nala = r#"
+        func report(result: Result<String>): Void {
+            match (result) {
+                Result::Ok(path) => { print(path); }
+                Result::Err(message) => { print(message); }
+            }
+        }
+
+        report(makeDir('notes/archive'));
+        report(writeFile('notes/a.txt', 'hello'));
+
+        match (listDir('notes')) {
+            Result::Ok(names) => { print(names); }
+            Result::Err(message) => { print(message); }
+        }
+
+        print(exists('notes/archive'));
+        print(exists('notes/c.txt'));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec![
+            "notes/archive",
+            "notes/a.txt",
+            "['a.txt', 'archive', 'b.txt', ]",
+            "true",
+            "false"
+        ]
+    );
+}
+
+#[test]
+fn it_removes_files() {
+    let mut ctx = TestContext::new();
+    ctx.mock_file("old.txt", "stale");
+
+    let nala = r#"
+        match (removeFile('old.txt')) {
+            Result::Ok(path) => { print(exists(path)); }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["false"]);
+    assert_eq!(ctx.get_file("old.txt"), None);
+}
+
+#[test]
+fn it_requires_the_read_and_write_capabilities() {
+    let mut ctx = TestContext::new();
+    ctx.mock_file("data.txt", "secret");
+
+    let limits = Limits {
+        capabilities: Capabilities::all().without(Capability::Write),
+        ..Default::default()
+    };
+
+    let nala = r#"
+        print(exists('data.txt'));
+        writeFile('data.txt', 'overwritten');
+    "#;
+
+    let error = parse_and_run_with_limits(nala, &mut ctx, limits).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Permission);
+    assert_eq!(ctx.get_output(), vec!["true"]);
+    assert_eq!(
+        error.message,
+        "Calling `writeFile` requires the `write` capability."
+    );
+    assert_eq!(ctx.get_file("data.txt"), Some("secret"));
+
+    let limits = Limits {
+        capabilities: Capabilities::all().without(Capability::Read),
+        ..Default::default()
+    };
+
+    let error = parse_and_run_with_limits("exists('data.txt');", &mut ctx, limits).unwrap_err();
+    assert_eq!(
+        error.message,
+        "Calling `exists` requires the `read` capability."
+    );
+}
+
+#[test]
+fn it_limits_the_length_of_files_read() {
+    let mut ctx = TestContext::new();
+    ctx.mock_file("big.txt", "0123456789abcdef");
+    ctx.mock_file("lines.txt", "short\n0123456789abcdef\n");
+
+    let limits = Limits {
+        max_string_len: Some(10),
+        ..Default::default()
+    };
+
+    let error =
+        parse_and_run_with_limits("readFile('big.txt');", &mut ctx, limits.clone()).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(
+        error.message,
+        "String of length 16 exceeded the limit of 10."
+    );
+
+    let error = parse_and_run_with_limits("readLines('lines.txt');", &mut ctx, limits).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(
+        error.message,
+        "String of length 16 exceeded the limit of 10."
+    );
+}
@@ -64,3 +64,24 @@ fn it_errors_when_returning_wrong_type_for_resolved_generic() {
     assert!(result.is_err());
     assert_eq!(expected_message, &result.clone().unwrap_err().message);
 }
+
+#[test]
+fn it_accepts_declared_types_as_type_arguments() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        struct Person {
+            name: String,
+        }
+
+        func identity<T>(value: T): T {
+            value;
+        }
+
+        const person = identity::<Person>({ name: 'Ada' });
+        print(person.name);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["Ada"]);
+}
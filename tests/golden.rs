@@ -0,0 +1,102 @@
+use std::{env, fs, path::Path};
+
+use nala_interpreter::io_context::{HttpResponse, TestContext};
+use test_util::parse_and_run;
+
+// The directories of scripts whose transcripts are checked against the `.out` file beside them.
+const DIRS: [&str; 2] = ["examples", "tests/golden"];
+
+// Runs every script in `DIRS`, comparing what it printed and read, followed by any error it raised,
+// with its `.out` file. Lines of input are read from a `.in` file beside the script, and HTTP
+// responses from a `.http` file, if any. Run with `UPDATE_GOLDEN=1` to write the `.out` files from
+// the current transcripts instead.
+#[test]
+fn it_matches_golden_transcripts() {
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut failures = vec![];
+
+    for dir in DIRS {
+        let mut scripts: Vec<_> = fs::read_dir(root.join(dir))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "nl"))
+            // The sandbox is for local experiments, and isn't checked in.
+            .filter(|path| !path.ends_with("sandbox.nl"))
+            .collect();
+
+        scripts.sort();
+
+        for script in scripts {
+            let transcript = run_script(&script);
+            let out_path = script.with_extension("out");
+
+            if update {
+                fs::write(&out_path, &transcript).unwrap();
+                continue;
+            }
+
+            match fs::read_to_string(&out_path) {
+                Ok(expected) if expected == transcript => (),
+                Ok(expected) => failures.push(format!(
+                    "{}:\n--- expected\n{expected}--- actual\n{transcript}",
+                    script.display()
+                )),
+                Err(_) => failures.push(format!(
+                    "{}: missing {}",
+                    script.display(),
+                    out_path.display()
+                )),
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Transcripts differ from their golden files. Run with UPDATE_GOLDEN=1 to update them.\n\n{}",
+        failures.join("\n")
+    );
+}
+
+fn run_script(script: &Path) -> String {
+    let code = fs::read_to_string(script).unwrap();
+    let mut ctx = TestContext::new();
+
+    if let Ok(inputs) = fs::read_to_string(script.with_extension("in")) {
+        ctx.mock_inputs(inputs.lines().collect());
+    }
+
+    if let Ok(responses) = fs::read_to_string(script.with_extension("http")) {
+        mock_responses(&mut ctx, &responses);
+    }
+
+    let result = parse_and_run(&code, &mut ctx);
+    let mut transcript = ctx.get_transcript_text();
+
+    if let Err(error) = result {
+        transcript.push_str(&format!("{}: {}\n", error.kind, error.message));
+    }
+
+    transcript
+}
+
+// Each response is a `METHOD URL STATUS` line followed by a line holding its body. Requests without
+// a response fail, as if the host couldn't be reached.
+fn mock_responses(ctx: &mut TestContext, responses: &str) {
+    let mut lines = responses.lines().filter(|line| !line.is_empty());
+
+    while let (Some(request), Some(body)) = (lines.next(), lines.next()) {
+        let [method, url, status] = request.split(' ').collect::<Vec<_>>()[..] else {
+            panic!("Expected `METHOD URL STATUS`, found `{}`.", request);
+        };
+
+        ctx.mock_http(
+            method,
+            url,
+            HttpResponse {
+                status: status.parse().unwrap(),
+                body: body.to_owned(),
+            },
+        );
+    }
+}
@@ -0,0 +1,37 @@
+func makeCounter(): Func<Number> {
+    mut count = 0;
+
+    func increment(): Number {
+        count = count + 1;
+        count;
+    }
+
+    increment;
+}
+
+const first = makeCounter();
+const second = makeCounter();
+
+print(first());
+print(first());
+print(second());
+
+enum Shape {
+    Circle(Number),
+    Square(Number),
+}
+
+const shapes = [Shape::Circle(1), Shape::Square(2)];
+
+for shape in shapes {
+    match (shape) {
+        Shape::Circle(radius) => {
+            print('circle');
+            print(radius);
+        }
+        Shape::Square(side) => {
+            print('square');
+            print(side);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+print('What is your name?');
+const name = read();
+print('Hello, ' + name + '!');
+
+print('How old are you?');
+const age = readnum();
+print('Next year you will be:');
+print(age + 1);
+
+try {
+    read();
+} catch (e) {
+    print(e.message);
+}
@@ -0,0 +1,7 @@
+func divide(a: Number, b: Number): Number {
+    a / b;
+}
+
+print(divide(10, 2));
+print(divide(1, 0));
+print('unreachable');
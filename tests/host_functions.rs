@@ -0,0 +1,272 @@
+use std::{cell::Cell, collections::HashMap, rc::Rc};
+
+use nala_interpreter::{
+    ast::types::primitive_type::PrimitiveType,
+    errors::RuntimeError,
+    interpreter::Interpreter,
+    io_context::TestContext,
+    resolved::{func_value::Param, value::Value},
+    types::{composite_type::CompositeType, nala_type::NalaType, type_variant::TypeVariant},
+};
+
+fn primitive(primitive: PrimitiveType) -> TypeVariant {
+    TypeVariant::Type(NalaType::PrimitiveType(primitive))
+}
+
+#[test]
+fn it_calls_registered_closure_with_captured_state() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let users = HashMap::from([(1, "Walter"), (2, "Jesse")]);
+
+    interpreter
+        .register_function(
+            "lookupUser",
+            vec![Param {
+                ident: String::from("id"),
+                param_type: primitive(PrimitiveType::Number),
+            }],
+            primitive(PrimitiveType::String),
+            move |args, _evaluator| {
+                let id = match &args[0] {
+                    Value::Num(id) => *id as i32,
+                    _ => unreachable!(),
+                };
+
+                users
+                    .get(&id)
+                    .map(|name| Value::String((*name).into()))
+                    .ok_or_else(|| RuntimeError::new(&format!("No user with id {id}.")))
+            },
+        )
+        .unwrap();
+
+    interpreter
+        .eval_str(
+            r#"
+            print(lookupUser(2));
+            print(lookupUser(1));
+        "#,
+        )
+        .unwrap();
+
+    assert_eq!(interpreter.ctx_mut().get_output(), vec!["Jesse", "Walter"]);
+
+    let missing = interpreter.eval_str("lookupUser(3);");
+    assert_eq!(missing.unwrap_err().message, "No user with id 3.");
+}
+
+#[test]
+fn it_allows_registered_closures_to_mutate_shared_state() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+
+    interpreter
+        .register_function(
+            "tick",
+            vec![],
+            primitive(PrimitiveType::Void),
+            move |_args, _evaluator| {
+                counter.set(counter.get() + 1);
+                Ok(Value::Void)
+            },
+        )
+        .unwrap();
+
+    interpreter
+        .eval_str(
+            r#"
+            for i in [1, 2, 3] {
+                tick();
+            }
+        "#,
+        )
+        .unwrap();
+
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn it_gives_registered_closures_access_to_context() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter
+        .register_function(
+            "shout",
+            vec![Param {
+                ident: String::from("message"),
+                param_type: primitive(PrimitiveType::String),
+            }],
+            primitive(PrimitiveType::Void),
+            |args, evaluator| {
+                let message = args[0].as_string().unwrap();
+                evaluator.ctx().print(&message.to_uppercase());
+                Ok(Value::Void)
+            },
+        )
+        .unwrap();
+
+    interpreter.eval_str("shout('hello');").unwrap();
+
+    assert_eq!(interpreter.ctx_mut().get_output(), vec!["HELLO"]);
+}
+
+#[test]
+fn it_type_checks_args_to_registered_closures() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter
+        .register_function(
+            "double",
+            vec![Param {
+                ident: String::from("n"),
+                param_type: primitive(PrimitiveType::Number),
+            }],
+            primitive(PrimitiveType::Number),
+            |args, _evaluator| match &args[0] {
+                Value::Num(n) => Ok(Value::Num(n * 2.0)),
+                _ => unreachable!(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(interpreter.eval_str("double(4);").unwrap(), Value::Num(8.0));
+
+    assert!(interpreter.eval_str("double('four');").is_err());
+}
+
+#[test]
+fn it_type_checks_return_values_of_registered_closures() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter
+        .register_function(
+            "broken",
+            vec![],
+            primitive(PrimitiveType::Number),
+            |_args, _evaluator| Ok(Value::String("not a number".into())),
+        )
+        .unwrap();
+
+    assert!(interpreter.eval_str("broken();").is_err());
+}
+
+#[test]
+fn it_errors_when_registering_existing_name() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let result = interpreter.register_function(
+        "print",
+        vec![],
+        primitive(PrimitiveType::Void),
+        |_args, _evaluator| Ok(Value::Void),
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn it_passes_args_to_registered_closures_in_order() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let params = ["first", "second", "third"]
+        .iter()
+        .map(|ident| Param {
+            ident: ident.to_string(),
+            param_type: primitive(PrimitiveType::String),
+        })
+        .collect();
+
+    interpreter
+        .register_function(
+            "joinAll",
+            params,
+            primitive(PrimitiveType::String),
+            |args, _evaluator| {
+                let parts: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                Ok(Value::String(parts.join("-").into()))
+            },
+        )
+        .unwrap();
+
+    let result = interpreter.eval_str("joinAll('c', 'a', 'b');").unwrap();
+
+    assert_eq!(result, Value::String("c-a-b".into()));
+}
+
+#[test]
+fn it_lets_registered_closures_call_script_functions() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let params = vec![
+        Param {
+            ident: String::from("callback"),
+            param_type: TypeVariant::Composite(CompositeType {
+                outer: NalaType::PrimitiveType(PrimitiveType::Func),
+                inner: vec![primitive(PrimitiveType::Number)],
+                generic_type_param: None,
+            }),
+        },
+        Param {
+            ident: String::from("times"),
+            param_type: primitive(PrimitiveType::Number),
+        },
+    ];
+
+    interpreter
+        .register_function(
+            "repeat",
+            params,
+            primitive(PrimitiveType::Number),
+            |args, evaluator| {
+                let (callback, times) = match (&args[0], &args[1]) {
+                    (Value::Func(callback), Value::Num(times)) => (callback, *times),
+                    _ => unreachable!(),
+                };
+
+                let mut result = Value::Num(0.0);
+
+                for _ in 0..times as usize {
+                    result = evaluator.call(Rc::clone(callback), vec![result])?;
+                }
+
+                Ok(result)
+            },
+        )
+        .unwrap();
+
+    let result = interpreter
+        .eval_str(
+            r#"
+            func addTwo(n: Number): Number {
+                print(n);
+                n + 2;
+            }
+
+            repeat(addTwo, 3);
+        "#,
+        )
+        .unwrap();
+
+    assert_eq!(result, Value::Num(6.0));
+    assert_eq!(interpreter.ctx_mut().get_output(), vec!["0", "2", "4"]);
+
+    // Callbacks are checked exactly as calls from scripts are.
+    let error = interpreter
+        .eval_str(
+            r#"
+            func greet(name: String): Number {
+                1;
+            }
+
+            repeat(greet, 1);
+        "#,
+        )
+        .unwrap_err();
+
+    assert!(error
+        .message
+        .ends_with("of type `Number` to function where `String` was expected."));
+}
@@ -1 +1,97 @@
-// TODO: Create tests for http builtin.
+use nala_interpreter::io_context::{HttpRequest, HttpResponse, TestContext};
+use test_util::parse_and_run;
+
+#[test]
+fn it_sends_requests_through_the_context() {
+    let mut ctx = TestContext::new();
+
+    ctx.mock_http(
+        "POST",
+        "https://example.com/items",
+        HttpResponse {
+            status: 201,
+            body: String::from("{ \"name\": \"lamp\" }"),
+        },
+    );
+
+    let nala = r#"
+        struct Item {
+            name: String,
+        }
+
+        const result: HttpResult<Item> = http({
+            method: 'POST',
+            url: 'https://example.com/items',
+            body: 'lamp',
+        });
+
+        match (result.statusCode) {
+            Option::Some(statusCode) => { print(statusCode); }
+            Option::None => { print('no status'); }
+        }
+
+        match (result.body) {
+            Option::Some(item) => { print(item.name); }
+            Option::None => { print('no body'); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["201 Created", "lamp"]);
+    assert_eq!(
+        ctx.get_requests(),
+        [HttpRequest {
+            method: String::from("POST"),
+            url: String::from("https://example.com/items"),
+            body: Some(String::from("lamp")),
+        }]
+    );
+}
+
+#[test]
+fn it_returns_no_body_for_non_json_responses() {
+    let mut ctx = TestContext::new();
+
+    ctx.mock_http(
+        "GET",
+        "https://example.com",
+        HttpResponse {
+            status: 404,
+            body: String::from("Not Found"),
+        },
+    );
+
+    let nala = r#"
+        const result: HttpResult<String> = http({ method: 'GET', url: 'https://example.com', body: '' });
+
+        match (result.statusCode) {
+            Option::Some(statusCode) => { print(statusCode); }
+            Option::None => { print('no status'); }
+        }
+
+        match (result.body) {
+            Option::Some(body) => { print(body); }
+            Option::None => { print('no body'); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["404 Not Found", "no body"]);
+}
+
+#[test]
+fn it_returns_no_status_when_requests_fail() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const result: HttpResult<String> = http({ method: 'GET', url: 'https://unreachable', body: '' });
+
+        match (result.statusCode) {
+            Option::Some(statusCode) => { print(statusCode); }
+            Option::None => { print('no status'); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["no status"]);
+}
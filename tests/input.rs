@@ -1,4 +1,4 @@
-use nala_interpreter::io_context::TestContext;
+use nala_interpreter::io_context::{TestContext, TranscriptEntry};
 use test_util::parse_and_run;
 
 #[test]
@@ -42,3 +42,108 @@ fn it_runs_input_numbers() {
     assert!(parse_and_run(nala, &mut ctx).is_ok());
     assert_eq!(ctx.get_output(), output);
 }
+
+#[test]
+fn it_reads_args() {
+    let mut ctx = TestContext::new();
+    ctx.mock_args(vec!["first", "second"]);
+
+    let nala = r#"
+        for arg in args() {
+            print(arg);
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["first", "second"]);
+}
+
+#[test]
+fn it_reads_empty_args() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        for arg in args() {
+            print(arg);
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert!(ctx.get_output().is_empty());
+}
+
+#[test]
+fn it_reads_inputs_in_order() {
+    let mut ctx = TestContext::new();
+    ctx.mock_inputs(vec!["first", "second"]);
+
+    let nala = r#"
+        print(read());
+        print(read());
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["first", "second"]);
+}
+
+#[test]
+fn it_errors_when_reading_past_end_of_input() {
+    let mut ctx = TestContext::new();
+    ctx.mock_inputs(vec!["only"]);
+
+    let nala = r#"
+        print(read());
+
+        try {
+            read();
+        } catch (e) {
+            print(e.message);
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["only", "Reached the end of input."]);
+}
+
+#[test]
+fn it_reaches_end_of_input_when_signalled() {
+    let mut ctx = TestContext::new();
+    ctx.mock_eof();
+    ctx.mock_inputs(vec!["after"]);
+
+    let nala = r#"
+        try {
+            read();
+        } catch (e) {
+            print('end of input');
+        }
+
+        print(read());
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["end of input", "after"]);
+}
+
+#[test]
+fn it_records_prompts_and_inputs_in_order() {
+    let mut ctx = TestContext::new();
+    ctx.mock_inputs(vec!["Ada"]);
+
+    let nala = r#"
+        print('Name?');
+        const name = read();
+        print('Hi ' + name);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_transcript(),
+        [
+            TranscriptEntry::Print(String::from("Name?")),
+            TranscriptEntry::Read(String::from("Ada")),
+            TranscriptEntry::Print(String::from("Hi Ada")),
+        ]
+    );
+    assert_eq!(ctx.get_transcript_text(), "Name?\n> Ada\nHi Ada\n");
+}
@@ -0,0 +1,110 @@
+use std::{io::ErrorKind, time::Duration};
+
+use nala_interpreter::io_context::{IoContext, TestContext};
+use test_util::parse_and_run;
+
+#[test]
+fn it_reads_and_writes_fake_files() {
+    let mut ctx = TestContext::new();
+    ctx.mock_file("data/names.txt", "Ada\n");
+
+    assert_eq!(ctx.read_file("data/names.txt").unwrap(), "Ada\n");
+
+    ctx.append_file("data/names.txt", "Grace\n").unwrap();
+    ctx.write_file("data/empty.txt", "").unwrap();
+
+    assert_eq!(ctx.get_file("data/names.txt"), Some("Ada\nGrace\n"));
+    assert_eq!(
+        ctx.list_dir("data").unwrap(),
+        vec!["empty.txt", "names.txt"]
+    );
+
+    ctx.remove_file("data/empty.txt").unwrap();
+    assert!(!ctx.exists("data/empty.txt"));
+    assert!(ctx.exists("data"));
+}
+
+#[test]
+fn it_fails_like_a_real_file_system() {
+    let mut ctx = TestContext::new();
+
+    assert_eq!(
+        ctx.read_file("missing.txt").unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    assert_eq!(
+        ctx.write_file("missing/file.txt", "").unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    assert_eq!(
+        ctx.remove_file("missing.txt").unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+
+    ctx.make_dir("nested/dirs").unwrap();
+    ctx.write_file("nested/dirs/file.txt", "contents").unwrap();
+
+    assert_eq!(ctx.list_dir("nested").unwrap(), vec!["dirs"]);
+}
+
+#[test]
+fn it_fakes_the_environment_and_clock() {
+    let nala = r#"
+        print(env('HOME'));
+
+        const time = now();
+        print(time.days);
+        print(time.seconds);
+        print(time.millis);
+    "#;
+
+    let mut ctx = TestContext::new();
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["None", "0", "0", "0"]);
+
+    let mut ctx = TestContext::new();
+    ctx.mock_env("HOME", "/home/nala");
+    ctx.mock_time(Duration::from_millis(2 * 86_400_000 + 61_250));
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["Some('/home/nala')", "2", "61", "250"]
+    );
+}
+
+#[test]
+fn it_generates_repeatable_random_numbers() {
+    let nala = r#"
+        for i in range(0, 3) {
+            const num = random();
+            assert(num < 0 == false);
+            assert(num < 1);
+            print(num);
+        }
+    "#;
+
+    let mut first = TestContext::new();
+    let mut second = TestContext::new();
+
+    assert!(parse_and_run(nala, &mut first).is_ok());
+    assert!(parse_and_run(nala, &mut second).is_ok());
+    assert_eq!(first.get_output(), second.get_output());
+
+    let mut reseeded = TestContext::new();
+    reseeded.mock_seed(7);
+
+    assert!(parse_and_run(nala, &mut reseeded).is_ok());
+    assert_ne!(first.get_output(), reseeded.get_output());
+}
+
+#[test]
+fn it_records_errors_apart_from_output() {
+    let mut ctx = TestContext::new();
+
+    ctx.print("out");
+    ctx.print_err("err");
+
+    assert_eq!(ctx.get_output(), vec!["out"]);
+    assert_eq!(ctx.get_errors(), vec!["err"]);
+}
@@ -0,0 +1,301 @@
+use nala_interpreter::io_context::TestContext;
+use test_util::parse_and_run;
+
+#[test]
+fn it_parses_json() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const text = '{ "name": "lamp", "price": 12.5, "tags": ["home", "light"], "sold": false, "owner": null }';
+
+        match (jsonParse(text)) {
+            Result::Ok(item) => {
+                print(item.name);
+                print(item.price);
+                print(item.tags[1]);
+                print(item.sold);
+                print(item.owner);
+            }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["lamp", "12.5", "light", "false", "None"]
+    );
+}
+
+#[test]
+fn it_returns_an_error_for_invalid_json() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        match (jsonParse('{ "name": ')) {
+            Result::Ok(value) => { print('parsed'); }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["Invalid JSON: EOF while parsing a value at line 1 column 10."]
+    );
+}
+
+#[test]
+fn it_validates_parsed_json_against_a_type_argument() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        struct Person {
+            name: String,
+            age: Number,
+        }
+
+        match (jsonParse::<Person>('{ "name": "Ada", "age": 36 }')) {
+            Result::Ok(person) => { print(person.name); }
+            Result::Err(message) => { print(message); }
+        }
+
+        match (jsonParse::<Person>('{ "name": "Ada", "age": "36" }')) {
+            Result::Ok(person) => { print(person.name); }
+            Result::Err(message) => { print('invalid person'); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["Ada", "invalid person"]);
+}
+
+#[test]
+fn it_requires_the_fields_of_a_type_argument() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        struct Person {
+            name: String,
+            age: Number,
+            nickname: Option<String>,
+        }
+
+        match (jsonParse::<Person>('{ "name": "Ada" }')) {
+            Result::Ok(person) => { print(person.age); }
+            Result::Err(message) => { print(message); }
+        }
+
+        match (jsonParse::<Person>('{ "name": "Ada", "age": 36 }')) {
+            Result::Ok(person) => { print(person.nickname); }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec![
+            "Parsed JSON does not fit type `{ age: Number, name: String, nickname: Option<String>, }`.",
+            "None",
+        ]
+    );
+}
+
+#[test]
+fn it_checks_every_field_when_json_has_extra_fields() {
+    let nala = r#"
+        struct Person {
+            age: Number,
+        }
+
+        match (jsonParse::<Person>('{ "aaa": 1, "bbb": 2, "ccc": 3, "age": "notanumber" }')) {
+            Result::Ok(person) => { print(person.age); }
+            Result::Err(message) => { print('invalid person'); }
+        }
+
+        match (jsonParse::<Person>('{ "aaa": 1, "age": 36 }')) {
+            Result::Ok(person) => { print(person.age); }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    // Fields are held in a hash map, so this is run several times to cover different orders.
+    for _ in 0..10 {
+        let mut ctx = TestContext::new();
+        assert!(parse_and_run(nala, &mut ctx).is_ok());
+        assert_eq!(ctx.get_output(), vec!["invalid person", "36"]);
+    }
+}
+
+#[test]
+fn it_stringifies_values() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const item = {
+            name: 'lamp',
+            price: 12.5,
+            count: 3,
+            tags: ['home', 'light'],
+            maker: Option::Some('Acme'),
+        };
+
+        print(jsonStringify(item, false));
+        print(jsonStringify([Option::None], false));
+        print(jsonStringify(Map { 'a': true }, false));
+        print(jsonStringify(Set { 1, 2 }, false));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec![
+            r#"{"count":3,"maker":"Acme","name":"lamp","price":12.5,"tags":["home","light"]}"#,
+            "[null]",
+            r#"{"a":true}"#,
+            "[1,2]",
+        ]
+    );
+}
+
+#[test]
+fn it_stringifies_values_prettily() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        print(jsonStringify({ name: 'lamp', tags: ['home'] }, true));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["{\n  \"name\": \"lamp\",\n  \"tags\": [\n    \"home\"\n  ]\n}"]
+    );
+}
+
+#[test]
+fn it_round_trips_json() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const text = '{"count":3,"nested":{"ok":true},"owner":null,"tags":["a","b"]}';
+
+        match (jsonParse(text)) {
+            Result::Ok(value) => { print(jsonStringify(value, false) == text); }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["true"]);
+}
+
+#[test]
+fn it_errors_when_stringifying_functions() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        jsonStringify({ log: print }, false);
+    "#;
+
+    let result = parse_and_run(nala, &mut ctx);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().message.starts_with("Cannot convert `"));
+}
+
+#[test]
+fn it_returns_an_error_for_numbers_that_lose_precision() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        match (jsonParse('{"id": 123456789012}')) {
+            Result::Ok(value) => { print(value.id); }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["Cannot convert `123456789012` to a `Number` without losing precision."]
+    );
+}
+
+#[test]
+fn it_parses_arrays_of_a_single_type() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        match (jsonParse('[1, null, 3]')) {
+            Result::Ok(values) => { print(values); }
+            Result::Err(message) => { print(message); }
+        }
+
+        match (jsonParse('[null, "a"]')) {
+            Result::Ok(values) => { print(values); }
+            Result::Err(message) => { print(message); }
+        }
+
+        match (jsonParse('[1, null, "a"]')) {
+            Result::Ok(values) => { print(values); }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec![
+            "[Some(1), None, Some(3), ]",
+            "[None, Some('a'), ]",
+            "Arrays can contain elements of only a single type. Found elements of types `Option<Number>` and `Option<String>`.",
+        ]
+    );
+}
+
+#[test]
+fn it_parses_empty_arrays_given_a_type_argument() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const empty: Array<Number> = [];
+
+        match (jsonParse::<Array<Number>>(jsonStringify(empty, false))) {
+            Result::Ok(values) => { print(len(values)); }
+            Result::Err(message) => { print(message); }
+        }
+
+        match (jsonParse('{ "values": [] }')) {
+            Result::Ok(value) => { print('parsed'); }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec![
+            "0",
+            "Cannot infer the type of an empty array. Pass a type argument, as in `jsonParse::<Array<Number>>(text)`.",
+        ]
+    );
+}
+
+#[test]
+fn it_returns_an_error_for_numbers_out_of_range() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        match (jsonParse('1e40')) {
+            Result::Ok(value) => { print(value); }
+            Result::Err(message) => { print(message); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["Cannot convert `1e40` to a `Number`, as it's out of range."]
+    );
+}
@@ -0,0 +1,345 @@
+use std::{thread, time::Duration};
+
+use nala_interpreter::{
+    errors::ErrorKind,
+    interpreter::{eval_program_with_limits, Interpreter},
+    io_context::TestContext,
+    limits::{CancelHandle, Limits},
+    parser::parse_code,
+    vm::run_program_with_limits,
+};
+use test_util::parse_and_run_with_limits;
+
+#[test]
+fn it_stops_endless_loops_after_max_steps() {
+    let limits = Limits {
+        max_steps: Some(1000),
+        ..Default::default()
+    };
+
+    let result = parse_and_run_with_limits("wiles (true) { 1; }", &mut TestContext::new(), limits);
+    let error = result.unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(error.message, "Execution exceeded the limit of 1000 steps.");
+}
+
+#[test]
+fn it_counts_calls_and_for_loop_iterations_as_steps() {
+    let nala = r#"
+        func noop(): Void {
+            const x = 1;
+        }
+
+        const items = [1, 2, 3, 4, 5];
+
+        for item in items {
+            noop();
+        }
+    "#;
+
+    let limits = Limits {
+        max_steps: Some(10),
+        ..Default::default()
+    };
+
+    assert!(parse_and_run_with_limits(nala, &mut TestContext::new(), limits).is_ok());
+
+    let limits = Limits {
+        max_steps: Some(9),
+        ..Default::default()
+    };
+
+    let error = parse_and_run_with_limits(nala, &mut TestContext::new(), limits).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Limit);
+}
+
+#[test]
+fn it_stops_runaway_recursion_at_max_call_depth() {
+    let nala = r#"
+        func recurse(n: Number): Number {
+            recurse(n + 1);
+        }
+
+        recurse(0);
+    "#;
+
+    let limits = Limits {
+        max_call_depth: Some(50),
+        ..Default::default()
+    };
+
+    let error = parse_and_run_with_limits(nala, &mut TestContext::new(), limits).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(
+        error.message,
+        "Execution exceeded the maximum call depth of 50."
+    );
+}
+
+#[test]
+fn it_allows_recursion_within_max_call_depth() {
+    let nala = r#"
+        func countdown(n: Number): Number {
+            if (n == 0) {
+                0;
+            } else {
+                countdown(n - 1);
+            }
+        }
+
+        print(countdown(9));
+    "#;
+
+    let limits = Limits {
+        max_call_depth: Some(10),
+        ..Default::default()
+    };
+
+    let mut ctx = TestContext::new();
+    assert!(parse_and_run_with_limits(nala, &mut ctx, limits).is_ok());
+    assert_eq!(ctx.get_output(), vec!["0"]);
+}
+
+#[test]
+fn it_limits_collection_lengths() {
+    let limits = Limits {
+        max_collection_len: Some(3),
+        ..Default::default()
+    };
+
+    let error = parse_and_run_with_limits(
+        "const nums = [1, 2, 3, 4];",
+        &mut TestContext::new(),
+        limits.clone(),
+    )
+    .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(
+        error.message,
+        "Collection of 4 items exceeded the limit of 3."
+    );
+
+    let error = parse_and_run_with_limits(
+        "const point = { a: 1, b: 2, c: 3, d: 4 };",
+        &mut TestContext::new(),
+        limits,
+    )
+    .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+}
+
+#[test]
+fn it_limits_arrays_built_by_builtins() {
+    let limits = Limits {
+        max_collection_len: Some(3),
+        ..Default::default()
+    };
+
+    let error = parse_and_run_with_limits(
+        "range(0, 1000000000);",
+        &mut TestContext::new(),
+        limits.clone(),
+    )
+    .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+
+    let error = parse_and_run_with_limits(
+        "const nums = [1, 2, 3]; push(nums, 4);",
+        &mut TestContext::new(),
+        limits.clone(),
+    )
+    .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+
+    let error =
+        parse_and_run_with_limits("split('a,b,c,d', ',');", &mut TestContext::new(), limits)
+            .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(
+        error.message,
+        "Collection of 4 items exceeded the limit of 3."
+    );
+}
+
+#[test]
+fn it_counts_calls_made_by_builtins_as_steps() {
+    let nala = r#"
+        func double(n: Number): Number { n * 2; }
+        map(range(0, 100), double);
+    "#;
+
+    let limits = Limits {
+        max_steps: Some(50),
+        ..Default::default()
+    };
+
+    let error = parse_and_run_with_limits(nala, &mut TestContext::new(), limits).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+}
+
+#[test]
+fn it_limits_string_lengths() {
+    let nala = r#"
+        mut text = 'ab';
+
+        wiles (true) {
+            text = text + text;
+        }
+    "#;
+
+    let limits = Limits {
+        max_string_len: Some(100),
+        ..Default::default()
+    };
+
+    let error = parse_and_run_with_limits(nala, &mut TestContext::new(), limits).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(
+        error.message,
+        "String of length 128 exceeded the limit of 100."
+    );
+}
+
+#[test]
+fn it_limits_strings_built_by_builtins() {
+    let limits = Limits {
+        max_string_len: Some(10),
+        ..Default::default()
+    };
+
+    let error = parse_and_run_with_limits(
+        "replace('aaaa', 'a', 'bbb');",
+        &mut TestContext::new(),
+        limits.clone(),
+    )
+    .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(
+        error.message,
+        "String of length 12 exceeded the limit of 10."
+    );
+
+    let error = parse_and_run_with_limits(
+        "join(['abc', 'def', 'ghi'], ', ');",
+        &mut TestContext::new(),
+        limits,
+    )
+    .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(
+        error.message,
+        "String of length 13 exceeded the limit of 10."
+    );
+}
+
+#[test]
+fn it_does_not_catch_limit_errors() {
+    let nala = r#"
+        try {
+            wiles (true) {
+                print('looping');
+            }
+        } catch (e) {
+            print('caught');
+        }
+    "#;
+
+    let limits = Limits {
+        max_steps: Some(100),
+        ..Default::default()
+    };
+
+    let mut ctx = TestContext::new();
+    let error = parse_and_run_with_limits(nala, &mut ctx, limits).unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert!(!ctx.get_output().contains(&"caught"));
+}
+
+#[test]
+fn it_times_out() {
+    let limits = Limits {
+        timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    };
+
+    let error = parse_and_run_with_limits("wiles (true) { 1; }", &mut TestContext::new(), limits)
+        .unwrap_err();
+
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(error.message, "Execution timed out.");
+}
+
+#[test]
+fn it_cancels_from_another_thread() {
+    let program = parse_code("wiles (true) { 1; }".to_owned()).unwrap();
+
+    for run in [eval_program_with_limits, run_program_with_limits] {
+        let cancel = CancelHandle::new();
+        let handle = cancel.clone();
+
+        let canceller = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            handle.cancel();
+        });
+
+        let result = run(
+            program.clone(),
+            &mut TestContext::new(),
+            Limits::default(),
+            cancel,
+        );
+        canceller.join().unwrap();
+
+        let error = result.unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Limit);
+        assert_eq!(error.message, "Execution was cancelled.");
+    }
+}
+
+#[test]
+fn it_applies_limits_to_each_evaluation() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter.set_limits(Limits {
+        max_steps: Some(5),
+        ..Default::default()
+    });
+
+    let loop_three_times = r#"
+        mut i = 0;
+        wiles (i < 3) { i = i + 1; }
+    "#;
+
+    interpreter.eval_str(loop_three_times).unwrap();
+    interpreter
+        .eval_str("i = 0; wiles (i < 3) { i = i + 1; }")
+        .unwrap();
+
+    let error = interpreter
+        .eval_str("i = 0; wiles (i < 10) { i = i + 1; }")
+        .unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Limit);
+}
+
+#[test]
+fn it_cancels_an_interpreter() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter.cancel_handle().cancel();
+
+    let error = interpreter.eval_str("wiles (true) { 1; }").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Limit);
+    assert_eq!(error.message, "Execution was cancelled.");
+}
@@ -0,0 +1,169 @@
+use nala_interpreter::linter::*;
+
+fn lint(code: &str) -> Vec<(&'static str, usize)> {
+    lint_code(code, &LintConfig::default())
+        .unwrap()
+        .into_iter()
+        .map(|finding| (finding.rule, finding.line))
+        .collect()
+}
+
+#[test]
+fn it_reports_unused_consts() {
+    let code = r#"const used = 1;
+const unused = 2;
+const _ignored = 3;
+print(used);"#;
+
+    assert_eq!(lint(code), vec![(UNUSED_CONST, 2)]);
+}
+
+#[test]
+fn it_counts_uses_in_functions_declared_before_the_const() {
+    let code = r#"func show(): Void {
+    print(message);
+}
+
+const message = 'hello';
+show();"#;
+
+    assert_eq!(lint(code), vec![]);
+}
+
+#[test]
+fn it_reports_unnecessary_mut() {
+    let code = r#"mut count = 0;
+mut total = 0;
+mut items = [1, 2];
+count = count + 1;
+items[0] = 5;
+print(total);"#;
+
+    assert_eq!(lint(code), vec![(UNNECESSARY_MUT, 2), (UNNECESSARY_MUT, 3)]);
+}
+
+#[test]
+fn it_reports_shadowed_bindings() {
+    let code = r#"const name = 'outer';
+
+func greet(name: String): Void {
+    print(name);
+}
+
+for item in ['a'] {
+    const name = item;
+    print(name);
+}
+
+print(name);
+greet(name);"#;
+
+    assert_eq!(
+        lint(code),
+        vec![(SHADOWED_BINDING, 3), (SHADOWED_BINDING, 8)]
+    );
+}
+
+#[test]
+fn it_leaves_duplicate_bindings_to_the_resolver() {
+    let code = r#"const value = 1;
+print(value);
+
+func show(value: Number): Void {
+    const value = 2;
+    print(value);
+}
+
+show(value);"#;
+
+    assert_eq!(lint(code), vec![(SHADOWED_BINDING, 4)]);
+}
+
+#[test]
+fn it_reports_unreachable_code() {
+    let code = r#"for item in [1, 2] {
+    break(item); // stop early
+
+    // Never runs.
+    print(item);
+    print(item);
+}"#;
+
+    assert_eq!(lint(code), vec![(UNREACHABLE_CODE, 5)]);
+}
+
+#[test]
+fn it_reports_empty_match_arms() {
+    let code = r#"const maybe = Option::Some(5);
+
+match (maybe) {
+    Option::Some(value) => {
+        print(value);
+    }
+    Option::None => {
+        // Nothing to do.
+    }
+}"#;
+
+    assert_eq!(lint(code), vec![(EMPTY_MATCH_ARM, 7)]);
+}
+
+#[test]
+fn it_locates_findings_past_strings_and_comments() {
+    let code = r#"print('const a = 1;');
+/* const b = 2; */
+// const c = 3;
+const d = 4;"#;
+
+    assert_eq!(lint(code), vec![(UNUSED_CONST, 4)]);
+}
+
+#[test]
+fn it_honors_suppressions() {
+    let code = r#"const a = 1; // nala-ignore: unused-const
+// nala-ignore: unused-const, unnecessary-mut
+
+mut b = 2;
+const c = 3; // nala-ignore: unnecessary-mut
+// nala-ignore
+const d = 4;"#;
+
+    assert_eq!(lint(code), vec![(UNUSED_CONST, 5)]);
+}
+
+#[test]
+fn it_applies_configured_severities() {
+    let code = "const a = 1;\nmut b = 2;\nprint(b);";
+
+    let mut config = LintConfig::default();
+    config.set(UNUSED_CONST, Severity::Error).unwrap();
+    config.set(UNNECESSARY_MUT, Severity::Allow).unwrap();
+
+    let findings = lint_code(code, &config).unwrap();
+
+    assert_eq!(
+        findings,
+        vec![Finding {
+            rule: UNUSED_CONST,
+            severity: Severity::Error,
+            line: 1,
+            message: String::from("Constant `a` is declared but never used."),
+        }]
+    );
+}
+
+#[test]
+fn it_rejects_unknown_rules() {
+    let mut config = LintConfig::default();
+    let result = config.set("no-such-rule", Severity::Error);
+
+    assert_eq!(
+        result,
+        Err(String::from("Unknown lint rule `no-such-rule`."))
+    );
+}
+
+#[test]
+fn it_reports_parse_errors() {
+    assert!(lint_code("const = 5;", &LintConfig::default()).is_err());
+}
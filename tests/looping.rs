@@ -79,3 +79,20 @@ fn it_errors_on_for_on_non_array() {
 
     assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
 }
+
+#[test]
+fn it_runs_for_loops_which_modify_their_array() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const nums = [1, 2, 3];
+
+        for num in nums {
+            nums[2] = 7;
+            print(num);
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["1", "2", "7"]);
+}
@@ -0,0 +1,250 @@
+use nala_interpreter::io_context::TestContext;
+use regex::Regex;
+use test_util::{assert_regex_match, parse_and_run, rgx};
+
+#[test]
+fn it_indexes_maps() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const ages = Map { 'Walter': 50, 'Jesse': 25 };
+        const name = 'Jesse';
+        print(ages['Walter']);
+        print(ages[name]);
+        print(len(ages));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["50", "25", "2"]);
+}
+
+#[test]
+fn it_assigns_map_entries() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const ages = Map { 'Walter': 50 };
+        ages['Walter'] = 51;
+        ages['Skyler'] = 40;
+        print(ages['Walter']);
+        print(ages['Skyler']);
+        print(ages);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["51", "40", "Map { 'Walter': 51, 'Skyler': 40, }"]
+    );
+}
+
+#[test]
+fn it_assigns_into_declared_empty_maps() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const counts: Map<String, Number> = Map {};
+        const words = ['a', 'b', 'a'];
+
+        for word in words {
+            if (has(counts, word)) {
+                counts[word] = counts[word] + 1;
+            } else {
+                counts[word] = 1;
+            }
+        }
+
+        print(counts['a']);
+        print(counts['b']);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["2", "1"]);
+}
+
+#[test]
+fn it_keys_maps_by_numbers_and_bools() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const squares = Map { 2: 4, 3: 9 };
+        const answers = Map { true: 'yes', false: 'no' };
+        print(squares[1 + 2]);
+        print(answers[false]);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["9", "no"]);
+}
+
+#[test]
+fn it_iterates_over_map_entries_in_order() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const ages = Map { 'Walter': 50, 'Jesse': 25, 'Skyler': 40 };
+
+        for entry in ages {
+            print(entry.key);
+            print(entry.value);
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["Walter", "50", "Jesse", "25", "Skyler", "40"]
+    );
+}
+
+#[test]
+fn it_runs_map_builtins() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const ages = Map { 'Walter': 50, 'Jesse': 25, 'Skyler': 40 };
+        const names = keys(ages);
+        const years = values(ages);
+        const pairs = entries(ages);
+
+        print(names);
+        print(years);
+        print(pairs[2].key);
+        print(has(ages, 'Jesse'));
+        print(has(ages, 'Hank'));
+        print(remove(ages, 'Jesse'));
+        print(remove(ages, 'Hank'));
+        print(keys(ages));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec![
+            "['Walter', 'Jesse', 'Skyler', ]",
+            "[50, 25, 40, ]",
+            "Skyler",
+            "true",
+            "false",
+            "Some(25)",
+            "None",
+            "['Walter', 'Skyler', ]",
+        ]
+    );
+}
+
+#[test]
+fn it_passes_maps_to_functions() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func total(prices: Map<String, Number>): Number {
+            mut sum = 0;
+
+            for price in values(prices) {
+                sum = sum + price;
+            }
+
+            sum;
+        }
+
+        print(total(Map { 'apple': 2, 'pear': 3 }));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["5"]);
+}
+
+#[test]
+fn it_errors_when_passing_map_of_wrong_type() {
+    let expected_message = rgx!("where `Map<String,Number>` was expected.");
+
+    let nala = r#"
+        func total(prices: Map<String, Number>): Number {
+            len(prices);
+        }
+
+        total(Map { 'apple': 'cheap' });
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_errors_when_indexing_missing_key() {
+    let expected_message = rgx!("Key `'Hank'` does not exist in map.");
+
+    let nala = r#"
+        const ages = Map { 'Walter': 50 };
+        print(ages['Hank']);
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_errors_when_mixing_key_types() {
+    let expected_message = rgx!(
+        "Maps can contain keys of only a single type. Found keys of types `String` and `Number`."
+    );
+
+    let nala = r#"
+        const ages = Map { 'Walter': 50, 7: 25 };
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_errors_when_assigning_value_of_wrong_type() {
+    let expected_message = rgx!(
+        "Maps can contain values of only a single type. Found values of types `Number` and `String`."
+    );
+
+    let nala = r#"
+        const ages = Map { 'Walter': 50 };
+        ages['Jesse'] = 'young';
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_errors_when_keying_by_function() {
+    let expected_message = rgx!("Functions, maps and sets cannot be hashed.");
+
+    let nala = r#"
+        const ages = Map { print: 50 };
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_errors_when_declaring_map_type_with_one_argument() {
+    let expected_message = rgx!("Type `Map` takes two type arguments");
+
+    let nala = r#"
+        const ages: Map<String> = Map { 'Walter': 50 };
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
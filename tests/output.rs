@@ -19,12 +19,12 @@ fn it_prints_strings_without_quotes() {
 #[test]
 fn it_prints_strings_with_quotes_in_errors() {
     let expected_error = rgx!(
-        "Passed value `'should print'` of type `String` to function where `Array<T>` was expected"
+        "Passed value `'should print'` of type `String` to function where `Number` was expected"
     );
 
     let nala = r#"
         const test = 'should print';
-        len(test);
+        floor(test);
     "#;
 
     let result = parse_and_run(nala, &mut TestContext::new());
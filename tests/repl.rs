@@ -0,0 +1,164 @@
+use nala_interpreter::{
+    capabilities::{Capabilities, Capability},
+    io_context::TestContext,
+    limits::Limits,
+    repl::{Repl, ReplResponse},
+};
+
+fn new_repl() -> Repl<TestContext> {
+    Repl::new(TestContext::new()).unwrap()
+}
+
+fn expect_output(response: ReplResponse) -> String {
+    match response {
+        ReplResponse::Output(output) => output,
+        ReplResponse::Error(message) => panic!("Expected output, got error: {}", message),
+        _ => panic!("Expected output."),
+    }
+}
+
+fn expect_error(response: ReplResponse) -> String {
+    match response {
+        ReplResponse::Error(message) => message,
+        _ => panic!("Expected error."),
+    }
+}
+
+#[test]
+fn it_keeps_bindings_between_lines() {
+    let mut repl = new_repl();
+
+    assert!(matches!(
+        repl.eval_line("const x = 5;"),
+        ReplResponse::Empty
+    ));
+    assert_eq!(expect_output(repl.eval_line("x * 2;")), "10");
+}
+
+#[test]
+fn it_echoes_expressions_without_semicolon() {
+    let mut repl = new_repl();
+
+    assert_eq!(expect_output(repl.eval_line("2 + 3")), "5");
+}
+
+#[test]
+fn it_does_not_echo_void() {
+    let mut repl = new_repl();
+
+    assert!(matches!(
+        repl.eval_line("print('hi');"),
+        ReplResponse::Empty
+    ));
+    assert_eq!(repl.interpreter().ctx_mut().get_output(), vec!["hi"]);
+}
+
+#[test]
+fn it_buffers_multi_line_blocks() {
+    let mut repl = new_repl();
+
+    assert!(matches!(
+        repl.eval_line("func double(n: Number): Number {"),
+        ReplResponse::Incomplete
+    ));
+    assert!(repl.is_incomplete());
+    assert!(matches!(
+        repl.eval_line("    n * 2;"),
+        ReplResponse::Incomplete
+    ));
+    assert!(matches!(repl.eval_line("}"), ReplResponse::Empty));
+    assert!(!repl.is_incomplete());
+    assert_eq!(expect_output(repl.eval_line("double(4)")), "8");
+}
+
+#[test]
+fn it_resets_incomplete_input() {
+    let mut repl = new_repl();
+
+    assert!(matches!(
+        repl.eval_line("if (true) {"),
+        ReplResponse::Incomplete
+    ));
+    repl.reset();
+    assert_eq!(expect_output(repl.eval_line("1 + 1")), "2");
+}
+
+#[test]
+fn it_reports_parse_errors() {
+    let mut repl = new_repl();
+
+    let message = expect_error(repl.eval_line("const = 5;"));
+    assert!(message.starts_with("ParseError"));
+    assert!(!repl.is_incomplete());
+}
+
+#[test]
+fn it_recovers_from_runtime_errors() {
+    let mut repl = new_repl();
+
+    let message = expect_error(repl.eval_line("missing;"));
+    assert_eq!(
+        message,
+        "RuntimeError: Identifier 'missing' was not found in this scope."
+    );
+
+    repl.eval_line("const y = 3;");
+    assert_eq!(expect_output(repl.eval_line("y")), "3");
+}
+
+#[test]
+fn it_shows_types() {
+    let mut repl = new_repl();
+
+    repl.eval_line("const names = ['a', 'b'];");
+
+    assert_eq!(expect_output(repl.eval_line(":type 1 + 1")), "Number");
+    assert_eq!(
+        expect_output(repl.eval_line(":type names")),
+        "Array<String>"
+    );
+}
+
+#[test]
+fn it_shows_scope() {
+    let mut repl = new_repl();
+
+    repl.eval_line("const answer = 42;");
+
+    let scope = expect_output(repl.eval_line(":scope"));
+    assert!(scope.contains("answer"));
+}
+
+#[test]
+fn it_applies_capabilities() {
+    let mut repl = new_repl();
+
+    repl.set_limits(Limits {
+        capabilities: Capabilities::none().with(Capability::Read),
+        ..Default::default()
+    });
+
+    assert_eq!(
+        expect_output(repl.eval_line("exists('missing.txt')")),
+        "false"
+    );
+    assert_eq!(
+        expect_error(repl.eval_line("read()")),
+        "PermissionError: Calling `read` requires the `stdin` capability."
+    );
+}
+
+#[test]
+fn it_exits() {
+    let mut repl = new_repl();
+
+    assert!(matches!(repl.eval_line(":quit"), ReplResponse::Exit));
+}
+
+#[test]
+fn it_rejects_unknown_commands() {
+    let mut repl = new_repl();
+
+    let message = expect_error(repl.eval_line(":frobnicate"));
+    assert!(message.contains("Unknown command"));
+}
@@ -0,0 +1,123 @@
+use nala_interpreter::io_context::TestContext;
+use regex::Regex;
+use test_util::{assert_regex_match, parse_and_run, rgx};
+
+#[test]
+fn it_errors_before_running_when_using_a_binding_before_its_declaration() {
+    let expected_message = rgx!("Identifier 'foo' was used before its declaration.");
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        print('never printed');
+        print(foo);
+        const foo = 7;
+    "#;
+
+    let result = parse_and_run(nala, &mut ctx);
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message);
+    assert!(ctx.get_output().is_empty());
+}
+
+#[test]
+fn it_errors_before_running_when_declaring_a_binding_twice() {
+    let expected_message = rgx!("Binding for foo already exists in local scope.");
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        print('never printed');
+
+        if (true) {
+            const foo = 7;
+            mut foo = 8;
+        }
+    "#;
+
+    let result = parse_and_run(nala, &mut ctx);
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message);
+    assert!(ctx.get_output().is_empty());
+}
+
+#[test]
+fn it_errors_when_declaring_a_parameter_twice() {
+    let expected_message = rgx!("Binding for a already exists in local scope.");
+
+    let nala = r#"
+        func add(a: Number, a: Number): Number {
+            a + a;
+        }
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message);
+}
+
+#[test]
+fn it_uses_enclosing_bindings_until_a_shadowing_declaration() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const foo = 7;
+
+        if (true) {
+            print(foo);
+            const foo = 'bar';
+            print(foo);
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["7", "bar"]);
+}
+
+#[test]
+fn it_lets_functions_use_bindings_declared_after_them() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func outer(): Void {
+            func greet(): Void {
+                print(greeting);
+            }
+
+            const greeting = 'hello';
+            greet();
+        }
+
+        outer();
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["hello"]);
+}
+
+#[test]
+fn it_errors_when_calling_a_function_before_a_binding_it_uses_is_declared() {
+    let expected_message = rgx!("Identifier 'greeting' was not found in this scope.");
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const greeting = 'global';
+
+        func outer(): Void {
+            func greet(): Void {
+                print(greeting);
+            }
+
+            greet();
+            const greeting = 'hello';
+        }
+
+        outer();
+    "#;
+
+    let result = parse_and_run(nala, &mut ctx);
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message);
+}
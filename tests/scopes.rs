@@ -1,4 +1,4 @@
-use nala_interpreter::io_context::TestContext;
+use nala_interpreter::{interpreter::Interpreter, io_context::TestContext};
 use test_util::parse_and_run;
 
 #[test]
@@ -37,3 +37,85 @@ fn it_runs_block_shadowing() {
     assert!(parse_and_run(nala, &mut ctx).is_ok());
     assert_eq!(ctx.get_output(), vec!["bar", "7"]);
 }
+
+#[test]
+fn it_lets_globals_shadow_builtins() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const keys = ['a', 'b'];
+
+        func find(word: String): String {
+            'found ' + word;
+        }
+
+        func describe(): String {
+            find(keys[1]);
+        }
+
+        print(keys);
+        print(describe());
+        print(len(keys));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["['a', 'b', ]", "found b", "2"]);
+}
+
+#[test]
+fn it_runs_long_loops_in_bounded_memory() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let nala = r#"
+        func increment(num: Number): Number {
+            num + 1;
+        }
+
+        mut i = 0;
+
+        wiles (i < 1000000) {
+            if (true) {
+                i = increment(i);
+            }
+        }
+
+        print(i);
+    "#;
+
+    interpreter.eval_str(nala).unwrap();
+
+    assert_eq!(interpreter.ctx_mut().get_output(), vec!["1000000"]);
+    assert!(interpreter.scopes().allocated() < 100);
+}
+
+#[test]
+fn it_frees_scopes_only_closed_over_by_released_scopes() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let nala = r#"
+        func makeAdder(amount: Number): Func<Number, Number> {
+            func add(num: Number): Number {
+                num + amount;
+            }
+
+            add;
+        }
+
+        const addOne = makeAdder(1);
+        mut i = 0;
+
+        wiles (i < 10000) {
+            if (true) {
+                const addTwo = makeAdder(2);
+                i = addOne(addTwo(i)) - 2;
+            }
+        }
+
+        print(addOne(i));
+    "#;
+
+    interpreter.eval_str(nala).unwrap();
+
+    assert_eq!(interpreter.ctx_mut().get_output(), vec!["10001"]);
+    assert!(interpreter.scopes().allocated() < 1000);
+}
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+use nala_interpreter::{
+    interpreter::Interpreter,
+    io_context::TestContext,
+    resolved::{de::from_value, ser::to_value, value::Value},
+};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Character {
+    name: String,
+    age: u32,
+    alias: Option<String>,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Status {
+    Active,
+    Suspended(String),
+}
+
+fn walter() -> Character {
+    Character {
+        name: String::from("Walter White"),
+        age: 50,
+        alias: Some(String::from("Heisenberg")),
+        tags: vec![String::from("chemist"), String::from("teacher")],
+    }
+}
+
+#[test]
+fn it_round_trips_structs() {
+    let value = to_value(&walter()).unwrap();
+    let character: Character = from_value(value).unwrap();
+
+    assert_eq!(character, walter());
+}
+
+#[test]
+fn it_maps_options_to_option_enum() {
+    let some = to_value(&Some(3)).unwrap();
+    let none = to_value(&None::<u32>).unwrap();
+
+    assert_eq!(format!("{some:?}"), "Some(3)");
+    assert_eq!(format!("{none:?}"), "None");
+
+    assert_eq!(from_value::<Option<u32>>(some).unwrap(), Some(3));
+    assert_eq!(from_value::<Option<u32>>(none).unwrap(), None);
+}
+
+#[test]
+fn it_passes_structs_into_scripts() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter
+        .set_global("character", to_value(&walter()).unwrap())
+        .unwrap();
+
+    interpreter
+        .eval_str(
+            r#"
+            print(character.name);
+            print(character.age + 1);
+
+            match (character.alias) {
+                Option::Some(alias) => { print('Alias: ' + alias); }
+                Option::None => { print('No alias.'); }
+            }
+
+            for tag in character.tags {
+                print(tag);
+            }
+        "#,
+        )
+        .unwrap();
+
+    assert_eq!(
+        interpreter.ctx_mut().get_output(),
+        vec![
+            "Walter White",
+            "51",
+            "Alias: Heisenberg",
+            "chemist",
+            "teacher"
+        ]
+    );
+}
+
+#[test]
+fn it_deserializes_results_from_scripts() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    let result = interpreter
+        .eval_str(
+            r#"
+            {
+                name: 'Jesse Pinkman',
+                age: 25,
+                alias: Option::Some('Capn Cook'),
+                tags: ['cook'],
+            };
+        "#,
+        )
+        .unwrap();
+
+    let character: Character = from_value(result).unwrap();
+
+    assert_eq!(
+        character,
+        Character {
+            name: String::from("Jesse Pinkman"),
+            age: 25,
+            alias: Some(String::from("Capn Cook")),
+            tags: vec![String::from("cook")],
+        }
+    );
+}
+
+#[test]
+fn it_round_trips_enums_through_scripts() {
+    let mut interpreter = Interpreter::new(TestContext::new()).unwrap();
+
+    interpreter
+        .eval_str(
+            r#"
+            enum Status {
+                Active,
+                Suspended(String),
+            }
+
+            func suspend(reason: String): Status {
+                Status::Suspended(reason);
+            }
+        "#,
+        )
+        .unwrap();
+
+    let active = to_value(&Status::Active).unwrap();
+    interpreter.set_global("status", active).unwrap();
+    interpreter
+        .eval_str("print(status == Status::Active);")
+        .unwrap();
+
+    let reason = to_value("unpaid").unwrap();
+    let suspended = interpreter.call_function("suspend", vec![reason]).unwrap();
+
+    assert_eq!(interpreter.ctx_mut().get_output(), vec!["true"]);
+    assert_eq!(
+        from_value::<Status>(suspended).unwrap(),
+        Status::Suspended(String::from("unpaid"))
+    );
+}
+
+#[test]
+fn it_converts_json_numbers() {
+    let json: serde_json::Value = serde_json::from_str(r#"{ "count": 3, "ratio": 0.5 }"#).unwrap();
+    let value = to_value(&json).unwrap();
+
+    let Value::Object(fields) = value else {
+        panic!("Expected an object.");
+    };
+
+    let fields = fields.borrow();
+    assert_eq!(fields["count"], Value::Num(3.0));
+    assert_eq!(fields["ratio"], Value::Num(0.5));
+}
+
+#[test]
+fn it_errors_deserializing_mismatched_types() {
+    let value = Value::String("not a number".into());
+    assert!(from_value::<u32>(value).is_err());
+}
+
+#[test]
+fn it_errors_serializing_integers_that_lose_precision() {
+    assert_eq!(to_value(&16_777_216_i64).unwrap(), Value::Num(16_777_216.0));
+
+    let error = to_value(&16_777_217_i64).unwrap_err();
+    assert_eq!(
+        error.message,
+        "Cannot convert `16777217` to a `Number` without losing precision."
+    );
+
+    assert!(to_value(&16_777_217_u32).is_err());
+    assert!(to_value(&u64::MAX).is_err());
+}
+
+#[test]
+fn it_errors_serializing_doubles_out_of_range() {
+    assert_eq!(to_value(&0.5_f64).unwrap(), Value::Num(0.5));
+    assert!(to_value(&1e40_f64).is_err());
+}
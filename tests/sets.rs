@@ -0,0 +1,226 @@
+use nala_interpreter::io_context::TestContext;
+use regex::Regex;
+use test_util::{assert_regex_match, parse_and_run, rgx};
+
+#[test]
+fn it_drops_duplicate_elements() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const ids = Set { 3, 1, 3, 2, 1 };
+        print(ids);
+        print(len(ids));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["Set { 3, 1, 2, }", "3"]);
+}
+
+#[test]
+fn it_deduplicates_arrays() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const ids = [7, 4, 7, 7, 4];
+        print(values(toSet(ids)));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["[7, 4, ]"]);
+}
+
+#[test]
+fn it_tests_membership() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const names = Set { 'Walter', 'Jesse' };
+        print(has(names, 'Jesse'));
+        print(has(names, 'Hank'));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["true", "false"]);
+}
+
+#[test]
+fn it_pushes_onto_and_removes_from_sets() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const seen: Set<String> = Set {};
+        push(seen, 'a');
+        push(seen, 'b');
+        push(seen, 'a');
+        print(seen);
+        remove(seen, 'a');
+        print(has(seen, 'a'));
+        remove(seen, 'a');
+        print(seen);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["Set { 'a', 'b', }", "false", "Set { 'b', }"]
+    );
+}
+
+#[test]
+fn it_combines_sets() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const first = Set { 1, 2, 3 };
+        const second = Set { 2, 3, 4 };
+        print(union(first, second));
+        print(intersection(first, second));
+        print(difference(first, second));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["Set { 1, 2, 3, 4, }", "Set { 2, 3, }", "Set { 1, }"]
+    );
+}
+
+#[test]
+fn it_iterates_over_sets_in_order() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const names = Set { 'Walter', 'Jesse', 'Walter', 'Skyler' };
+
+        for name in names {
+            print(name);
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["Walter", "Jesse", "Skyler"]);
+}
+
+#[test]
+fn it_compares_sets_regardless_of_order() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        print(Set { 1, 2 } == Set { 2, 1 });
+        print(Set { 1, 2 } == Set { 1, 3 });
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["true", "false"]);
+}
+
+#[test]
+fn it_hashes_nested_values_by_content() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const point = { x: 1, y: 2 };
+        const points = Set { point, { y: 2, x: 1 }, { x: 2, y: 1 } };
+        const pairs = Set { [1, 2], [1, 2], [2, 1] };
+        const options = Set { Option::Some(1), Option::Some(1), Option::Some(2) };
+
+        print(len(points));
+        print(len(pairs));
+        print(len(options));
+        print(has(options, Option::Some(2)));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["2", "2", "2", "true"]);
+}
+
+#[test]
+fn it_keeps_elements_as_they_were_added() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const pair = [1, 2];
+        const pairs = Set { pair };
+        pair[0] = 5;
+
+        print(has(pairs, [1, 2]));
+        print(has(pairs, pair));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["true", "false"]);
+}
+
+#[test]
+fn it_keys_maps_by_nested_values() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const distances = Map { [0, 0]: 0, [3, 4]: 5 };
+        print(distances[[3, 4]]);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["5"]);
+}
+
+#[test]
+fn it_passes_sets_to_functions() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func count(ids: Set<Number>): Number {
+            len(ids);
+        }
+
+        print(count(Set { 1, 1, 2 }));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["2"]);
+}
+
+#[test]
+fn it_errors_when_mixing_element_types() {
+    let expected_message = rgx!(
+        "Sets can contain elements of only a single type. Found elements of types `Number` and `String`."
+    );
+
+    let nala = r#"
+        const ids = Set { 1, 2 };
+        push(ids, 'three');
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_errors_when_adding_functions() {
+    let expected_message = rgx!("Functions, maps and sets cannot be hashed.");
+
+    let nala = r#"
+        const funcs = Set { print };
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
+
+#[test]
+fn it_errors_when_inferring_type_of_empty_set() {
+    let expected_message = rgx!("Cannot infer type of an empty set.");
+
+    let nala = r#"
+        const ids = Set {};
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_regex_match!(expected_message, &result.clone().unwrap_err().message)
+}
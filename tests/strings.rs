@@ -0,0 +1,158 @@
+use nala_interpreter::io_context::TestContext;
+use test_util::parse_and_run;
+
+#[test]
+fn it_gets_string_lengths_in_characters() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        print(len('hello'));
+        print(len(''));
+        print(len('café'));
+        print(len('e\u{301}'));
+        print(len('👩‍👩‍👧'));
+    "#;
+
+    // Accents combined with a letter and emoji joined into one glyph each count as a single character.
+    let nala = nala.replace("e\\u{301}", "e\u{301}");
+
+    assert!(parse_and_run(&nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["5", "0", "4", "1", "1"]);
+}
+
+#[test]
+fn it_splits_and_joins_strings() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const parts = split('a,b,,c', ',');
+        print(len(parts));
+        print(join(parts, ' | '));
+        print(join(split('héllo', ''), '-'));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["4", "a | b |  | c", "h-é-l-l-o"]);
+}
+
+#[test]
+fn it_trims_and_changes_case() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        print('[' + trim('  padded  ') + ']');
+        print(upper('straße'));
+        print(lower('ÀB'));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["[padded]", "STRASSE", "àb"]);
+}
+
+#[test]
+fn it_searches_strings() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        print(contains('haystack', 'st'));
+        print(contains('haystack', 'needle'));
+        print(startsWith('haystack', 'hay'));
+        print(endsWith('haystack', 'hay'));
+
+        match (indexOf('naïve café', 'café')) {
+            Option::Some(index) => { print(index); }
+            Option::None => { print('not found'); }
+        }
+
+        match (indexOf('haystack', 'needle')) {
+            Option::Some(index) => { print(index); }
+            Option::None => { print('not found'); }
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(
+        ctx.get_output(),
+        vec!["true", "false", "true", "false", "6", "not found"]
+    );
+}
+
+#[test]
+fn it_types_index_of_as_an_option() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const index: Option<Number> = indexOf('abc', 'c');
+        print(index);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["Some(2)"]);
+}
+
+#[test]
+fn it_ignores_matches_inside_characters() {
+    let mut ctx = TestContext::new();
+
+    // The accent alone is part of the character `é`, so isn't found by itself.
+    let nala = "print(contains('cafe\u{301}', '\u{301}'));";
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["false"]);
+}
+
+#[test]
+fn it_replaces_and_takes_substrings() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        print(replace('one fish two fish', 'fish', 'cat'));
+        print(substring('héllo wörld', 6, 11));
+        print(substring('abc', 1, 1));
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["one cat two cat", "wörld", ""]);
+}
+
+#[test]
+fn it_iterates_over_characters() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        for char in chars('añb') {
+            print(char);
+        }
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["a", "ñ", "b"]);
+}
+
+#[test]
+fn it_errors_on_out_of_range_substrings() {
+    let result = parse_and_run("substring('abc', 2, 5);", &mut TestContext::new());
+
+    assert_eq!(
+        result.unwrap_err().message,
+        "Substring range 2..5 is out of bounds for a string of length 3."
+    );
+
+    let result = parse_and_run("substring('abc', 1.5, 2);", &mut TestContext::new());
+
+    assert_eq!(
+        result.unwrap_err().message,
+        "Expected `start` to be a whole number of at least 0, but got 1.5."
+    );
+}
+
+#[test]
+fn it_type_checks_string_builtin_args() {
+    let result = parse_and_run("upper(5);", &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert!(result
+        .unwrap_err()
+        .message
+        .contains("of type `Number` to function where `String` was expected"));
+}
@@ -0,0 +1,160 @@
+use regex::Regex;
+
+use nala_interpreter::{
+    errors::ErrorKind, interpreter::testing::eval_tests, io_context::TestContext, parser,
+};
+use test_util::{assert_regex_match, parse_and_run, rgx};
+
+#[test]
+fn it_passes_assert_on_true() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        assert(1 == 1);
+        print('done');
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["done"]);
+}
+
+#[test]
+fn it_errors_when_assert_fails() {
+    let nala = r#"
+        assert(1 == 2);
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind, ErrorKind::Assertion);
+}
+
+#[test]
+fn it_passes_assert_eq_on_equal_structures() {
+    let nala = r#"
+        assertEq({ name: 'Walter', tags: ['chemist', 'teacher'] }, { name: 'Walter', tags: ['chemist', 'teacher'] });
+        assertEq(Option::Some(3), Option::Some(3));
+    "#;
+
+    assert!(parse_and_run(nala, &mut TestContext::new()).is_ok());
+}
+
+#[test]
+fn it_reports_differences_when_assert_eq_fails() {
+    let expected_error = rgx!(r"\.tags\[1\]: expected 'teacher', found 'cook'");
+
+    let nala = r#"
+        assertEq({ name: 'Walter', tags: ['chemist', 'teacher'] }, { name: 'Walter', tags: ['chemist', 'cook'] });
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+
+    let error = result.unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Assertion);
+    assert_regex_match!(expected_error, &error.message);
+}
+
+#[test]
+fn it_reports_length_differences_when_assert_eq_fails() {
+    let expected_error = rgx!(r"<root>: expected length 3, found length 2");
+
+    let nala = r#"
+        assertEq([1, 2, 3], [1, 2]);
+    "#;
+
+    let result = parse_and_run(nala, &mut TestContext::new());
+
+    assert!(result.is_err());
+
+    let message = result.unwrap_err().message;
+    assert_regex_match!(expected_error, &message);
+}
+
+#[test]
+fn it_skips_test_blocks_during_normal_runs() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        test 'never runs' {
+            print('in test');
+        }
+
+        print('in script');
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["in script"]);
+}
+
+#[test]
+fn it_still_allows_test_as_identifier() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const test = 'foo';
+        print(test);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["foo"]);
+}
+
+#[test]
+fn it_runs_test_blocks_with_declarations() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func double(n: Number): Number {
+            n * 2;
+        }
+
+        print('top-level statements are not run');
+
+        test 'doubles numbers' {
+            assertEq(4, double(2));
+        }
+
+        test 'fails on purpose' {
+            assertEq(5, double(2));
+        }
+    "#;
+
+    let program = parser::parse_code(nala.to_owned()).unwrap();
+    let results = eval_tests(program, &mut ctx);
+
+    assert_eq!(results.len(), 2);
+
+    assert_eq!(results[0].name, "doubles numbers");
+    assert!(results[0].passed());
+
+    assert_eq!(results[1].name, "fails on purpose");
+    assert!(!results[1].passed());
+
+    assert!(ctx.get_output().is_empty());
+}
+
+#[test]
+fn it_runs_each_test_in_fresh_scopes() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const items = [1];
+
+        test 'mutates shared array' {
+            items[0] = 2;
+            assertEq(2, items[0]);
+        }
+
+        test 'sees fresh array' {
+            assertEq(1, items[0]);
+        }
+    "#;
+
+    let program = parser::parse_code(nala.to_owned()).unwrap();
+    let results = eval_tests(program, &mut ctx);
+
+    assert!(results.iter().all(|r| r.passed()));
+}
@@ -0,0 +1,126 @@
+use nala_interpreter::{io_context::TestContext, parser, resolved::value::Value, vm::run_program};
+use test_util::parse_and_run;
+
+fn run_on_vm(nala: &str, ctx: &mut TestContext) -> Value {
+    let program = parser::parse_code(nala.to_owned()).unwrap();
+    run_program(program, ctx).unwrap()
+}
+
+#[test]
+fn it_shares_captured_bindings_with_closures() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        mut count = 0;
+
+        func increment(): Void {
+            count = count + 1;
+        }
+
+        increment();
+        increment();
+        print(count);
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["2"]);
+}
+
+#[test]
+fn it_captures_fresh_bindings_each_iteration() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func none(): Void {
+            print('none');
+        }
+
+        mut first = none;
+        mut last = none;
+
+        for word in ['foo', 'bar'] {
+            func printWord(): Void {
+                print(word);
+            }
+
+            if (word == 'foo') {
+                first = printWord;
+            }
+
+            last = printWord;
+        }
+
+        first();
+        last();
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["foo", "bar"]);
+}
+
+#[test]
+fn it_resolves_bindings_of_enclosing_functions_lexically() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        const message = 'outer';
+
+        func outer(): Void {
+            func inner(): Void {
+                print(message);
+            }
+
+            inner();
+
+            if (true) {
+                const message = 'shadowed';
+                inner();
+            }
+        }
+
+        outer();
+    "#;
+
+    assert!(parse_and_run(nala, &mut ctx).is_ok());
+    assert_eq!(ctx.get_output(), vec!["outer", "outer"]);
+}
+
+#[test]
+fn it_runs_deep_recursion_on_the_vm() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        func depth(n: Number): Number {
+            if (n == 0) {
+                0;
+            } else {
+                1 + depth(n - 1);
+            }
+        }
+
+        print(depth(100000));
+    "#;
+
+    run_on_vm(nala, &mut ctx);
+    assert_eq!(ctx.get_output(), vec!["100000"]);
+}
+
+#[test]
+fn it_runs_long_loops_on_the_vm() {
+    let mut ctx = TestContext::new();
+
+    let nala = r#"
+        mut total = 0;
+        mut i = 0;
+
+        wiles (i < 100000) {
+            total = total + i;
+            i = i + 1;
+        }
+
+        print(i);
+    "#;
+
+    run_on_vm(nala, &mut ctx);
+    assert_eq!(ctx.get_output(), vec!["100000"]);
+}